@@ -1,13 +1,29 @@
+use std::fmt;
+use std::path::PathBuf;
 use std::time::Instant;
 
 use anyhow::{Ok, Result};
 use crossterm::event::{KeyCode, KeyModifiers};
-use tokio::sync::mpsc::{channel, unbounded_channel, Receiver};
+use tokio::sync::{
+    mpsc::{channel, Receiver, Sender},
+    oneshot::{self, error::TryRecvError},
+};
+
+use bytes::Bytes;
 
 use crate::cli::args::{AppMode, Args, ProtocolType};
-use crate::protocols::{common, Message, ProtocolHandler};
+use crate::config::history::{HistoryEntry, SendHistory};
+use crate::config::slots::{QuickSlot, SlotFile, SLOT_COUNT};
+use crate::protocols::common::{ConnectionInfo, RequestTiming, WriteAck};
+use crate::protocols::file_transfer::{format_bytes, FileTransfer, TransferState};
+use crate::protocols::registry::HandlerRegistry;
+use crate::protocols::{Message, MessageDirection, MessageType, ProtocolHandler};
 use crate::ui::layout::{AppLayout, LayoutType};
-use crate::ui::widgets::{input_dialog::InputDialog, message_view::MessageView, status_bar::StatusBar};
+use crate::ui::widgets::input_dialog::{FormatType, FrameType, InputDialog, LineEnding, ALL_CLIENTS};
+use crate::ui::widgets::message_view::{MessageView, ViewMessage};
+use crate::ui::widgets::repeater_panel::RepeaterPanel;
+use crate::ui::widgets::status_bar::StatusBar;
+use crate::utils::repeater::{RepeatSpec, Repeater};
 // use crate
 
 /// 应用程序状态
@@ -17,9 +33,36 @@ pub enum InputMode {
 }
 
 /// 数据显示格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum DisplayFormat {
+    /// 文本 (控制字符转义显示)
     String,
+    /// 十六进制
     Hex,
+    /// 文本和十六进制同时显示
+    Both,
+}
+
+impl DisplayFormat {
+    /// 切换到下一种显示格式
+    pub fn next(self) -> Self {
+        match self {
+            DisplayFormat::String => DisplayFormat::Hex,
+            DisplayFormat::Hex => DisplayFormat::Both,
+            DisplayFormat::Both => DisplayFormat::String,
+        }
+    }
+}
+
+impl fmt::Display for DisplayFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            DisplayFormat::String => "Text",
+            DisplayFormat::Hex => "Hex",
+            DisplayFormat::Both => "Text+Hex",
+        };
+        write!(f, "{}", name)
+    }
 }
 
 /// 应用程序统计数据
@@ -28,6 +71,8 @@ pub struct Stats {
     pub received_bytes: usize,
     pub connected: bool,
     pub last_activity: Instant,
+    /// 最近一次请求的耗时 (HTTP 类客户端)
+    pub timing: Option<RequestTiming>,
 }
 
 impl Default for Stats {
@@ -37,6 +82,7 @@ impl Default for Stats {
             received_bytes: 0,
             connected: false,
             last_activity: Instant::now(),
+            timing: None,
         }
     }
 }
@@ -59,8 +105,30 @@ pub struct App {
     pub input_dialog: Option<InputDialog>,
     /// 统计数据
     pub stats: Stats,
+    /// 数据显示格式
+    pub display_format: DisplayFormat,
+    /// 字符串发送时追加的行结束符 (本次会话内保持)
+    pub line_ending: LineEnding,
+    /// 字符串发送时是否处理转义序列 (本次会话内保持)
+    pub escapes: bool,
+    /// 发送历史
+    pub history: SendHistory,
+    /// 正在进行的文件发送
+    pub file_transfer: Option<FileTransfer>,
+    /// 等待第一个客户端连接后发送的文件 (服务器模式的 --send-file)
+    pending_send_file: Option<PathBuf>,
+    /// 快捷发送槽位 (F1-F12)
+    pub slots: SlotFile,
+    /// 重复发送任务
+    pub repeaters: Vec<Repeater>,
+    /// 重复发送任务面板
+    pub repeater_panel: RepeaterPanel,
+    /// 下一个重复发送任务编号
+    next_repeater_id: usize,
+    /// 已交给协议处理器、等待写出确认的发送
+    pending_sends: Vec<PendingSend>,
     /// UI到服务端的消息发送通道
-    // pub ui_to_server_tx: Option<Sender<Message>>,
+    pub ui_to_server_tx: Option<Sender<Message>>,
     /// 协议处理器
     pub protocol_handler: Box<dyn ProtocolHandler + Send + Sync>,
     /// 服务端到UI的消息接收通道
@@ -68,6 +136,20 @@ pub struct App {
     pub args: Args,
 }
 
+/// 等待协议处理器写出确认的发送
+struct PendingSend {
+    /// 写出后显示的消息
+    message: ViewMessage,
+    /// 发送时间
+    timestamp: String,
+    /// 发送目标
+    target: Option<String>,
+    /// 数据字节数
+    length: usize,
+    /// 写出确认
+    written_rx: oneshot::Receiver<bool>,
+}
+
 impl App {
     pub async fn new(args: Args) -> Result<Self> {
         // 根据参数确定布局方式
@@ -78,85 +160,159 @@ impl App {
         };
 
         let (server_to_ui_tx, server_to_ui_rx) = channel::<Message>(1000);
-        // let mut ui_to_server_tx = None;
-
-        // 设置发送和接收视图的标题
-        let (send_title, recv_title) = match args.protocol {
-            ProtocolType::Tcp => match args.mode {
-                AppMode::Server => {
-                    // TCP Server 模式
-                    // let mut handler = common::create_protocol_handler("tcp", true, args.local_addr, None).await?;
-                    // ui_to_server_tx = handler.get_ui_to_server_sender();
-
-                    ("TCP Server Send", "TCP Server Receive")
-                }
-                AppMode::Client => ("TCP Client Send", "TCP Client Receive"),
-            },
-            ProtocolType::Udp => match args.mode {
-                AppMode::Server => ("UDP Server Send", "UDP Server Receive"),
-                AppMode::Client => ("UDP Client Send", "UDP Client Receive"),
-            },
-            ProtocolType::WebSocket => match args.mode {
-                AppMode::Server => ("WebSocket Server Send", "WebSocket Server Receive"),
-                AppMode::Client => ("WebSocket Client Send", "WebSocket Client Receive"),
-            },
-            ProtocolType::Http => match args.mode {
-                AppMode::Server => ("HTTP Server Send", "HTTP Server Receive"),
-                AppMode::Client => ("HTTP Client Send", "HTTP Client Receive"),
-            },
-            ProtocolType::Http2 => match args.mode {
-                AppMode::Server => ("HTTP/2 Server Send", "HTTP/2 Server Receive"),
-                AppMode::Client => ("HTTP/2 Client Send", "HTTP/2 Client Receive"),
-            },
-            ProtocolType::Http3 => match args.mode {
-                AppMode::Server => ("HTTP/3 Server Send", "HTTP/3 Server Receive"),
-                AppMode::Client => ("HTTP/3 Client Send", "HTTP/3 Client Receive"),
-            },
+
+        // 按协议和模式创建处理器，视图标题来自注册信息
+        let registry = HandlerRegistry::default();
+        let registration = registry.get(args.protocol, args.mode)?;
+        let handler = registry.create(&args, server_to_ui_tx).await?;
+        let ui_to_server_tx = handler.get_ui_to_server_sender();
+
+        // 历史文件损坏时使用空历史，并在发送区提示
+        let (history, history_error) = match SendHistory::load(args.protocol) {
+            core::result::Result::Ok(history) => (history, None),
+            Err(e) => (SendHistory::in_memory(), Some(e)),
         };
 
-        let handler =
-            common::create_protocol_handler("tcp", true, Some(server_to_ui_tx), args.local_addr, None).await?;
+        // 槽位文件无效时使用空列表且不保存，避免覆盖原文件
+        let (slots, slots_error) = match SlotFile::load(args.slots.clone()) {
+            core::result::Result::Ok(slots) => (slots, None),
+            Err(e) => (SlotFile::in_memory(), Some(e)),
+        };
 
-        let app = Self {
+        let mut app = Self {
             should_quit: false,
             input_mode: InputMode::Normal,
             layout: AppLayout::new(layout_type),
-            send_view: MessageView::new(send_title),
-            receive_view: MessageView::new(recv_title),
+            send_view: MessageView::new(&registration.send_title()),
+            receive_view: MessageView::new(&registration.receive_title()),
             status_bar: StatusBar::default(),
             input_dialog: None,
             stats: Stats::default(),
-            // ui_to_server_tx,
+            display_format: DisplayFormat::String,
+            line_ending: LineEnding::None,
+            escapes: true,
+            history,
+            file_transfer: None,
+            pending_send_file: None,
+            slots,
+            repeaters: Vec::new(),
+            repeater_panel: RepeaterPanel::default(),
+            next_repeater_id: 1,
+            pending_sends: Vec::new(),
+            ui_to_server_tx,
             protocol_handler: handler,
             server_to_ui_rx: Some(server_to_ui_rx),
             args,
         };
+        for e in [history_error, slots_error].into_iter().flatten() {
+            app.show_send_error(format!("{:#}", e));
+        }
+
+        // 客户端模式立即发送命令行指定的文件，服务器模式等待客户端连接
+        if let Some(path) = app.args.file_send.send_file.clone() {
+            match app.args.mode {
+                AppMode::Client => app.start_file_transfer(path, None),
+                AppMode::Server => app.pending_send_file = Some(path),
+            }
+        }
 
         Ok(app)
     }
 
     pub fn receive_message(&mut self) {
-        // 从 Option 中取出接收器的所有权
-        if let Some(server_to_ui_rx) = self.server_to_ui_rx.as_mut() {
-            // 处理接收到的消息
-            match server_to_ui_rx.try_recv() {
-                core::result::Result::Ok(message) => match message.content {
-                    common::MessageType::Text(txt) => {
-                        self.add_received_message(txt, None);
-                    }
-                    common::MessageType::ClientConnected => {
-                        self.receive_view
-                            .add_connection(&message.connection_info.unwrap().connection_id);
-                    }
-                    common::MessageType::ClientDisconnected => {
-                        self.receive_view
-                            .close_connection_by_title(&message.connection_info.unwrap().connection_id);
+        // 处理所有已到达的消息，没有消息可接收时继续执行
+        while let Some(message) = self.server_to_ui_rx.as_mut().and_then(|rx| rx.try_recv().ok()) {
+            self.handle_server_message(message);
+        }
+        self.update_pending_sends();
+    }
+
+    /// 处理协议处理器的写出确认，写出后才显示在发送区并计入统计
+    fn update_pending_sends(&mut self) {
+        let mut index = 0;
+        while index < self.pending_sends.len() {
+            let written = match self.pending_sends[index].written_rx.try_recv() {
+                Err(TryRecvError::Empty) => {
+                    index += 1;
+                    continue;
+                }
+                core::result::Result::Ok(written) => written,
+                Err(TryRecvError::Closed) => false,
+            };
+            let send = self.pending_sends.remove(index);
+            let message = if written {
+                self.stats.sent_bytes += send.length;
+                self.stats.last_activity = Instant::now();
+                send.message
+            } else {
+                ViewMessage::text(send.timestamp, format!("[Error] Not sent ({} bytes dropped)", send.length))
+            };
+            match send.target {
+                Some(target) => self.send_view.add_message_to_connection(&target, message),
+                None => self.send_view.add_message(message),
+            }
+        }
+    }
+
+    /// 处理协议处理器发来的单条消息
+    fn handle_server_message(&mut self, message: Message) {
+        match message.content {
+            MessageType::Text(_)
+            | MessageType::Binary(_)
+            | MessageType::Ping(_)
+            | MessageType::Pong(_)
+            | MessageType::Close { .. } => {
+                let connection_id = message.connection_info.map(|info| info.connection_id);
+                match message.direction {
+                    MessageDirection::Received => self.add_received_message(&message.content, connection_id),
+                    // 协议处理器自动发出的回复 (如 HTTP 默认响应)
+                    MessageDirection::Sent => self.add_sent_message(&message.content, connection_id),
+                }
+            }
+            MessageType::ClientConnected => {
+                let connection_id = message.connection_info.unwrap().connection_id;
+                self.receive_view.add_connection(&connection_id);
+                self.send_view.add_connection(&connection_id);
+                self.set_connected(true);
+                if let Some(path) = self.pending_send_file.take() {
+                    self.start_file_transfer(path, Some(connection_id));
+                }
+            }
+            MessageType::ClientDisconnected => {
+                let connection_id = message.connection_info.unwrap().connection_id;
+                self.receive_view.close_connection_by_title(&connection_id);
+                self.send_view.close_connection_by_title(&connection_id);
+                let connected = !self.protocol_handler.get_connections().is_empty();
+                self.set_connected(connected);
+            }
+            MessageType::ClientStale => {
+                let connection_id = message.connection_info.unwrap().connection_id;
+                self.receive_view.set_connection_stale(&connection_id);
+                self.send_view.set_connection_stale(&connection_id);
+            }
+            MessageType::Timing(timing) => {
+                self.stats.timing = Some(timing);
+            }
+            MessageType::Partial { preview, total } => {
+                let connection_id = message.connection_info.map(|info| info.connection_id).unwrap_or_default();
+                let partial = (total > 0).then(|| {
+                    let prefix = format!("[{}] [{}]", chrono::Local::now().format("%H:%M:%S"), connection_id);
+                    self.view_message(prefix, &MessageType::Partial { preview, total })
+                });
+                self.receive_view.set_partial(&connection_id, partial);
+            }
+            MessageType::Event(text) => {
+                let prefix = format!("[{}]", chrono::Local::now().format("%H:%M:%S"));
+                // 发送方向的事件是协议处理器已发出请求的描述，显示在发送视图且不计入统计
+                let (view, text) = match message.direction {
+                    MessageDirection::Sent => (&mut self.send_view, ViewMessage::text(prefix, text)),
+                    MessageDirection::Received => {
+                        (&mut self.receive_view, ViewMessage::text(prefix, format!("[Event] {}", text)))
                     }
-                    common::MessageType::Binary(_) => todo!(),
-                    common::MessageType::Hex(_) => todo!(),
-                },
-                core::result::Result::Err(_) => {
-                    // 没有消息可接收，继续执行
+                };
+                match message.connection_info {
+                    Some(info) => view.add_message_to_connection(&info.connection_id, text),
+                    None => view.add_message(text),
                 }
             }
         }
@@ -178,10 +334,70 @@ impl App {
                 self.should_quit = true;
             }
 
+            // 取消正在进行的文件发送
+            (KeyCode::Esc, _) => {
+                if let Some(transfer) = &self.file_transfer {
+                    transfer.cancel();
+                }
+            }
+
             // 输入模式 (I)
             (KeyCode::Char('i'), KeyModifiers::NONE) => {
                 self.input_mode = InputMode::Editing;
-                self.input_dialog = Some(InputDialog::new());
+                self.input_dialog = Some(self.new_input_dialog());
+            }
+
+            // 切换数据显示格式 (F)
+            (KeyCode::Char('f'), KeyModifiers::NONE) => {
+                self.display_format = self.display_format.next();
+                self.send_view.set_display_format(self.display_format);
+                self.receive_view.set_display_format(self.display_format);
+            }
+
+            // 切换十六进制转储显示 (D: 接收区, Shift+D: 发送区)
+            (KeyCode::Char('d'), KeyModifiers::NONE) => self.receive_view.toggle_hex_dump(),
+            (KeyCode::Char('D'), _) => self.send_view.toggle_hex_dump(),
+
+            // 选中消息 (Up/Down: 接收区, Shift+Up/Down: 发送区)
+            (KeyCode::Up, KeyModifiers::NONE) => self.receive_view.select_previous(),
+            (KeyCode::Down, KeyModifiers::NONE) => self.receive_view.select_next(),
+            (KeyCode::Up, KeyModifiers::SHIFT) => self.send_view.select_previous(),
+            (KeyCode::Down, KeyModifiers::SHIFT) => self.send_view.select_next(),
+
+            // 展开或折叠选中消息的转储 (E: 接收区, Shift+E: 发送区)
+            (KeyCode::Char('e'), KeyModifiers::NONE) => self.receive_view.toggle_dump_expanded(),
+            (KeyCode::Char('E'), _) => self.send_view.toggle_dump_expanded(),
+
+            // 快捷发送槽位: F1-F12 发送，Shift+F1-F12 编辑
+            (KeyCode::F(key), modifiers) if (1..=SLOT_COUNT).contains(&key) => {
+                if modifiers.contains(KeyModifiers::SHIFT) {
+                    self.edit_slot(key);
+                } else {
+                    self.send_slot(key);
+                }
+            }
+
+            // 重复发送任务: R 选择，P 暂停/恢复，X 删除
+            (KeyCode::Char('r'), KeyModifiers::NONE) => self.repeater_panel.select_next(self.repeaters.len()),
+            (KeyCode::Char('p'), KeyModifiers::NONE) => {
+                if let Some(repeater) = self.repeaters.get_mut(self.repeater_panel.selected) {
+                    repeater.toggle_pause(Instant::now());
+                }
+            }
+            (KeyCode::Char('x'), KeyModifiers::NONE) if self.repeater_panel.selected < self.repeaters.len() => {
+                let repeater = self.repeaters.remove(self.repeater_panel.selected);
+                self.repeater_panel.clamp(self.repeaters.len());
+                self.add_repeater_message(repeater.id, repeater.target.as_deref(), format!("removed after {} sends", repeater.sent));
+            }
+
+            // 切换连接标签页
+            (KeyCode::Tab, _) => {
+                self.send_view.next_tab();
+                self.receive_view.next_tab();
+            }
+            (KeyCode::BackTab, _) => {
+                self.send_view.prev_tab();
+                self.receive_view.prev_tab();
             }
             _ => {}
         }
@@ -191,62 +407,528 @@ impl App {
     /// 处理编辑模式键盘输入
     fn handle_editing_mode_key(&mut self, key: KeyCode, modifiers: KeyModifiers) -> Result<()> {
         if let Some(dialog) = &mut self.input_dialog {
+            // 历史搜索状态下按键用于编辑搜索关键字，其他按键接受搜索结果后按正常方式处理
+            if dialog.is_searching() {
+                let handled = match key {
+                    KeyCode::Char('r') if modifiers.contains(KeyModifiers::CONTROL) => {
+                        dialog.search_history();
+                        true
+                    }
+                    KeyCode::Char(c) if !modifiers.intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) => {
+                        dialog.search_push(c);
+                        true
+                    }
+                    KeyCode::Backspace => {
+                        dialog.search_pop();
+                        true
+                    }
+                    KeyCode::Esc => {
+                        dialog.cancel_search();
+                        true
+                    }
+                    _ => {
+                        dialog.accept_search();
+                        false
+                    }
+                };
+                if handled {
+                    return Ok(());
+                }
+            }
+
             match key {
                 KeyCode::Esc => {
                     self.input_mode = InputMode::Normal;
                     self.input_dialog = None;
                 }
-                KeyCode::Enter => {
-                    // 获取输入内容并发送
-                    if let Some(input) = dialog.submit() {
-                        // 处理输入的内容，实际发送逻辑将由具体协议实现
-                        self.send_message(input);
+                // Shift+Enter 和 Alt+Enter 交给编辑器换行
+                KeyCode::Enter if !modifiers.intersects(KeyModifiers::SHIFT | KeyModifiers::ALT) => {
+                    let target = dialog.selected_client().filter(|c| c.as_str() != ALL_CLIENTS).cloned();
+                    // 编辑槽位时保存到槽位文件 (仅服务器模式保存目标客户端)
+                    if let Some(key) = dialog.slot.as_ref().map(|slot| slot.key) {
+                        let slot = dialog.to_slot(target.filter(|_| self.args.mode == AppMode::Server));
+                        self.save_slot(key, slot);
+                    } else if let Some(mut dialog) = self.input_dialog.take() {
+                        if let Err(e) = self.submit(&dialog, target) {
+                            // 输入无效时保留对话框，提示错误原因
+                            dialog.error = Some(e);
+                            self.input_dialog = Some(dialog);
+                            return Ok(());
+                        }
                     }
                     self.input_mode = InputMode::Normal;
                     self.input_dialog = None;
                 }
-                KeyCode::Char(c) => {
-                    dialog.input.push(c);
+                KeyCode::Char('t') if modifiers.contains(KeyModifiers::CONTROL) => {
+                    dialog.cycle_frame_type();
+                    dialog.error = None;
+                }
+                KeyCode::Char('f') if modifiers.contains(KeyModifiers::CONTROL) => {
+                    dialog.toggle_format();
+                    dialog.error = None;
                 }
-                KeyCode::Backspace => {
-                    dialog.input.pop();
+                KeyCode::Char('e') if modifiers.contains(KeyModifiers::CONTROL) => {
+                    dialog.toggle_name_focus();
+                }
+                KeyCode::Char('p') if modifiers.contains(KeyModifiers::CONTROL) && dialog.slot.is_none() => {
+                    dialog.focus_next_repeat_field();
+                }
+                KeyCode::Char('l') if modifiers.contains(KeyModifiers::CONTROL) => {
+                    dialog.cycle_line_ending();
+                    // 编辑槽位时只修改槽位的行结束符
+                    if dialog.slot.is_none() {
+                        self.line_ending = dialog.line_ending;
+                    }
+                }
+                KeyCode::Char('x') if modifiers.contains(KeyModifiers::CONTROL) => {
+                    dialog.toggle_escapes();
+                    if dialog.slot.is_none() {
+                        self.escapes = dialog.escapes;
+                    }
+                }
+                KeyCode::Tab => {
+                    dialog.select_next_client();
+                }
+                // 历史记录: 光标在首行时上键、在末行时下键切换历史，Ctrl+R 反向搜索
+                KeyCode::Up if modifiers.is_empty() && !dialog.is_editing_field() && dialog.editor.cursor_row() == 0 => {
+                    dialog.history_previous();
+                }
+                KeyCode::Down
+                    if modifiers.is_empty()
+                        && !dialog.is_editing_field()
+                        && dialog.editor.cursor_row() + 1 == dialog.editor.line_count() =>
+                {
+                    dialog.history_next();
+                }
+                KeyCode::Char('r') if modifiers.contains(KeyModifiers::CONTROL) => {
+                    dialog.search_history();
+                }
+                _ => {
+                    if dialog.active_editor().handle_key(key, modifiers) {
+                        dialog.error = None;
+                        dialog.hex_suggestion = false;
+                    }
                 }
-                _ => {}
             }
         }
         Ok(())
     }
 
-    fn send_message(&mut self, message: String) {
+    /// 按对话框的设置发送: 分块发送文件、添加重复发送任务或发送一次，输入无效时返回错误原因
+    fn submit(&mut self, dialog: &InputDialog, target: Option<String>) -> core::result::Result<(), String> {
+        if let Some(path) = dialog.file_path() {
+            if dialog.repeat_spec()?.is_some() {
+                return Err("File transfers cannot repeat, clear the repeat interval (Ctrl+P)".to_string());
+            }
+            if let Some((e, false)) = dialog.validation() {
+                return Err(e);
+            }
+            if self.file_transfer.is_some() {
+                return Err("A file transfer is already running".to_string());
+            }
+            self.start_file_transfer(path, target);
+        } else if dialog.can_submit() {
+            let content = build_content(dialog)?;
+            match dialog.repeat_spec()? {
+                Some(spec) => self.add_repeater(content, target, spec),
+                None => {
+                    self.send_message(content, target);
+                }
+            }
+        } else {
+            return core::result::Result::Ok(());
+        }
+        self.record_history(dialog.history_entry());
+        core::result::Result::Ok(())
+    }
+
+    /// 发送快捷槽位的内容
+    fn send_slot(&mut self, key: u8) {
+        let Some(slot) = self.slots.get(key).cloned() else {
+            self.show_send_error(format!("F{} is empty, press Shift+F{} to edit it", key, key));
+            return;
+        };
+        let mut dialog = self.new_input_dialog();
+        dialog.load_slot(&slot);
+        // 客户端模式忽略槽位中的目标 (槽位文件可在不同连接间共享)
+        let target = slot.target.clone().filter(|_| self.args.mode == AppMode::Server);
+        if let Err(e) = self.submit(&dialog, target) {
+            self.show_send_error(format!("F{} {}: {}", key, slot.name, e));
+        }
+    }
+
+    /// 打开快捷槽位编辑对话框
+    fn edit_slot(&mut self, key: u8) {
+        let mut dialog = self.new_input_dialog();
+        dialog.edit_slot(key, self.slots.get(key));
+        self.input_mode = InputMode::Editing;
+        self.input_dialog = Some(dialog);
+    }
+
+    /// 保存快捷槽位到槽位文件，`slot` 为 None 时清除槽位
+    fn save_slot(&mut self, key: u8, slot: Option<QuickSlot>) {
+        let text = match slot {
+            Some(slot) => {
+                let text = format!("[Slot] Saved F{} {}", key, slot.name);
+                self.slots.set(slot);
+                text
+            }
+            None => {
+                self.slots.remove(key);
+                format!("[Slot] Cleared F{}", key)
+            }
+        };
+        match self.slots.save() {
+            core::result::Result::Ok(()) => {
+                let location = self.slots.path().map_or("memory".to_string(), |path| path.display().to_string());
+                self.send_view.add_message(ViewMessage::text(
+                    format!("[{}]", chrono::Local::now().format("%H:%M:%S")),
+                    format!("{} to {}", text, location),
+                ));
+            }
+            Err(e) => self.show_send_error(format!("{:#}", e)),
+        }
+    }
+
+    /// 处理粘贴的文本，未打开输入对话框时先打开
+    pub fn handle_paste(&mut self, text: &str) {
+        if self.input_dialog.is_none() {
+            self.input_mode = InputMode::Editing;
+            self.input_dialog = Some(self.new_input_dialog());
+        }
+        if let Some(dialog) = &mut self.input_dialog {
+            dialog.paste(text);
+        }
+    }
+
+    /// 创建输入对话框并填充当前连接列表
+    fn new_input_dialog(&self) -> InputDialog {
+        let mut dialog = InputDialog::new();
+        dialog.line_ending = self.line_ending;
+        dialog.escapes = self.escapes;
+        dialog.history = self.history.entries().to_vec();
+        if self.args.protocol == ProtocolType::WebSocket {
+            dialog.frame_type = Some(FrameType::Text);
+        }
+        if self.protocol_handler.supports_broadcast() {
+            dialog.add_client(ALL_CLIENTS.to_string());
+        }
+        let mut connections = self.protocol_handler.get_connections();
+        connections.sort_by(|a, b| a.connection_id.cmp(&b.connection_id));
+        for connection in connections {
+            dialog.add_client(connection.connection_id);
+        }
+        dialog
+    }
+
+    /// 发送消息，协议处理器确认写出后显示在发送区，返回是否成功交给协议处理器
+    fn send_message(&mut self, content: MessageType, target: Option<String>) -> bool {
+        let Some(connection_info) = self.target_connection(target.as_deref()) else {
+            return false;
+        };
+        let Some(ui_to_server_tx) = &self.ui_to_server_tx else {
+            self.send_view.add_message(ViewMessage::text(
+                format!("[{}]", chrono::Local::now().format("%H:%M:%S")),
+                format!("[Error] {} does not support sending", self.protocol_handler.protocol_name()),
+            ));
+            return false;
+        };
+
+        // 交给协议处理器发送
+        let length = content.to_bytes().map_or(0, |data| data.len());
+        let timestamp = format!("[{}]", chrono::Local::now().format("%H:%M:%S"));
+        let message = self.view_message(timestamp.clone(), &content);
+        let (ack, written_rx) = WriteAck::new(None);
+        if let Err(e) = ui_to_server_tx.try_send(Message::new_sent(content, connection_info).with_ack(ack)) {
+            self.send_view.add_message(ViewMessage::text(timestamp, format!("[Error] {}", e)));
+            return false;
+        }
+
+        self.pending_sends.push(PendingSend {
+            message,
+            timestamp,
+            target,
+            length,
+            written_rx,
+        });
+        true
+    }
+
+    /// 查找发送目标的连接信息，目标已断开时提示错误并返回 None (避免被当作广播)
+    fn target_connection(&mut self, target: Option<&str>) -> Option<Option<ConnectionInfo>> {
+        let Some(target) = target else {
+            return Some(None);
+        };
+        let connection = self
+            .protocol_handler
+            .get_connections()
+            .into_iter()
+            .find(|connection| connection.connection_id == target);
+        if connection.is_none() {
+            self.show_send_error(format!("{} is no longer connected", target));
+            return None;
+        }
+        Some(connection)
+    }
+
+    /// 开始分块发送文件
+    fn start_file_transfer(&mut self, path: PathBuf, target: Option<String>) {
+        let Some(ui_to_server_tx) = self.ui_to_server_tx.clone() else {
+            self.show_send_error(format!("{} does not support sending", self.protocol_handler.protocol_name()));
+            return;
+        };
+        if self.file_transfer.is_some() {
+            self.show_send_error("A file transfer is already running".to_string());
+            return;
+        }
+        let Some(connection_info) = self.target_connection(target.as_deref()) else {
+            return;
+        };
+
+        let config = self.args.file_send.chunk_config();
+        match FileTransfer::start(&path, config, ui_to_server_tx, connection_info) {
+            core::result::Result::Ok(transfer) => {
+                self.add_file_message(
+                    target.as_deref(),
+                    format!(
+                        "[File] Sending {} ({} bytes, {}-byte chunks, {} ms delay)",
+                        path.display(),
+                        transfer.total,
+                        config.chunk_size,
+                        config.delay.as_millis()
+                    ),
+                );
+                self.file_transfer = Some(transfer);
+            }
+            Err(e) => self.show_send_error(format!("{:#}", e)),
+        }
+    }
+
+    /// 定时更新 (由主循环按 tick 调用)
+    pub fn on_tick(&mut self) {
+        self.update_file_transfer();
+        self.run_repeaters();
+    }
+
+    /// 添加重复发送任务，第一次发送在下一个 tick 进行
+    fn add_repeater(&mut self, content: MessageType, target: Option<String>, spec: RepeatSpec) {
+        let repeater = Repeater::new(self.next_repeater_id, content, target, spec, Instant::now());
+        self.next_repeater_id += 1;
+        self.add_repeater_message(repeater.id, repeater.target.as_deref(), format!("started, {}", spec.describe()));
+        self.repeaters.push(repeater);
+        self.repeater_panel.selected = self.repeaters.len() - 1;
+    }
+
+    /// 发送到期的重复任务，发送失败时暂停任务，达到次数后移除
+    fn run_repeaters(&mut self) {
+        let now = Instant::now();
+        let mut index = 0;
+        while index < self.repeaters.len() {
+            if self.repeaters[index].poll_due(now) {
+                let repeater = &self.repeaters[index];
+                let (id, content, target) = (repeater.id, repeater.content.clone(), repeater.target.clone());
+                if self.send_message(content, target.clone()) {
+                    self.repeaters[index].record_sent();
+                } else {
+                    self.repeaters[index].paused = true;
+                    self.add_repeater_message(id, target.as_deref(), "paused after a failed send, press P to resume".to_string());
+                }
+            }
+            if self.repeaters[index].is_finished() {
+                let repeater = self.repeaters.remove(index);
+                self.repeater_panel.clamp(self.repeaters.len());
+                self.add_repeater_message(repeater.id, repeater.target.as_deref(), format!("finished after {} sends", repeater.sent));
+            } else {
+                index += 1;
+            }
+        }
+    }
+
+    /// 在发送区显示重复发送任务的状态变化
+    fn add_repeater_message(&mut self, id: usize, target: Option<&str>, text: String) {
+        let message = ViewMessage::text(
+            format!("[{}]", chrono::Local::now().format("%H:%M:%S")),
+            format!("[Repeat #{}] {}", id, text),
+        );
+        match target {
+            Some(target) => self.send_view.add_message_to_connection(target, message),
+            None => self.send_view.add_message(message),
+        }
+    }
+
+    /// 更新文件发送进度，结束时在发送区显示结果
+    fn update_file_transfer(&mut self) {
+        let Some(transfer) = &mut self.file_transfer else {
+            return;
+        };
+        let delta = transfer.take_sent_delta();
+        if delta > 0 {
+            self.stats.sent_bytes += delta as usize;
+            self.stats.last_activity = Instant::now();
+        }
+
+        let status = transfer.status();
+        let text = match status.state {
+            TransferState::Running => return,
+            TransferState::Completed => format!(
+                "[File] Sent {} bytes of {} in {:.1}s ({}/s)",
+                status.sent,
+                transfer.file_name(),
+                transfer.started.elapsed().as_secs_f64(),
+                format_bytes(transfer.rate())
+            ),
+            TransferState::Cancelled => format!(
+                "[File] Cancelled {} after {} of {} bytes",
+                transfer.file_name(),
+                status.sent,
+                transfer.total
+            ),
+            TransferState::Failed(e) => format!(
+                "[Error] Sending {} failed after {} bytes: {}",
+                transfer.file_name(),
+                status.sent,
+                e
+            ),
+        };
+        let target = transfer.target.clone();
+        self.file_transfer = None;
+        self.add_file_message(target.as_deref(), text);
+    }
+
+    /// 在发送区显示文件发送信息
+    fn add_file_message(&mut self, target: Option<&str>, text: String) {
+        let message = ViewMessage::text(format!("[{}]", chrono::Local::now().format("%H:%M:%S")), text);
+        match target {
+            Some(target) => self.send_view.add_message_to_connection(target, message),
+            None => self.send_view.add_message(message),
+        }
+    }
+
+    /// 记录发送历史并保存
+    fn record_history(&mut self, entry: HistoryEntry) {
+        self.history.push(entry);
+        if let Err(e) = self.history.save() {
+            self.show_send_error(format!("{:#}", e));
+        }
+    }
+
+    /// 在发送区显示错误信息
+    fn show_send_error(&mut self, error: String) {
+        self.send_view.add_message(ViewMessage::text(
+            format!("[{}]", chrono::Local::now().format("%H:%M:%S")),
+            format!("[Error] {}", error),
+        ));
+    }
+
+    /// 添加协议处理器自动发出的消息
+    fn add_sent_message(&mut self, content: &MessageType, to: Option<String>) {
         // 更新统计数据
-        self.stats.sent_bytes += message.len();
+        self.stats.sent_bytes += content.to_bytes().map_or(0, |data| data.len());
         self.stats.last_activity = Instant::now();
 
-        // 添加消息到发送视图
-        self.send_view
-            .add_message(format!("[{}] {}", chrono::Local::now().format("%H:%M:%S"), message));
-
-        todo!("发送消息逻辑需要根据具体协议实现");
+        let message = self.view_message(format!("[{}]", chrono::Local::now().format("%H:%M:%S")), content);
+        match to {
+            Some(to) => self.send_view.add_message_to_connection(&to, message),
+            None => self.send_view.add_message(message),
+        }
     }
 
     /// 添加接收到的消息
-    pub fn add_received_message(&mut self, message: String, from: Option<String>) {
+    pub fn add_received_message(&mut self, content: &MessageType, from: Option<String>) {
         // 更新统计数据
-        self.stats.received_bytes += message.len();
+        self.stats.received_bytes += content.to_bytes().map_or(0, |data| data.len());
         self.stats.last_activity = Instant::now();
 
         // 添加消息到接收视图
-        let prefix = if let Some(addr) = from {
+        let prefix = if let Some(addr) = &from {
             format!("[{}] [{}]", chrono::Local::now().format("%H:%M:%S"), addr)
         } else {
             format!("[{}]", chrono::Local::now().format("%H:%M:%S"))
         };
 
-        self.receive_view.add_message(format!("{} {}", prefix, message));
+        let message = self.view_message(prefix, content);
+        match from {
+            Some(addr) => self.receive_view.add_message_to_connection(&addr, message),
+            None => self.receive_view.add_message(message),
+        }
     }
 
     /// 更新连接状态
     pub fn set_connected(&mut self, connected: bool) {
         self.stats.connected = connected;
     }
+
+    /// 将消息内容转换为视图消息，数据类内容保留原始字节，显示时再按显示格式渲染
+    fn view_message(&self, prefix: String, content: &MessageType) -> ViewMessage {
+        let with_marker = |marker: &str| format!("{} {}", prefix, marker);
+        match content {
+            MessageType::Text(text) => ViewMessage::data(prefix, Bytes::from(text.clone())),
+            // 只有 WebSocket 区分文本帧和二进制帧，其他协议的数据不标注帧类型
+            MessageType::Binary(data) if self.args.protocol == ProtocolType::WebSocket => {
+                ViewMessage::data(with_marker("[Binary]"), data.clone())
+            }
+            MessageType::Binary(data) => ViewMessage::data(prefix, data.clone()),
+            MessageType::Ping(data) => ViewMessage::data(with_marker("[Ping]"), data.clone()),
+            MessageType::Pong(data) => ViewMessage::data(with_marker("[Pong]"), data.clone()),
+            MessageType::Close { code: Some(code), reason } => {
+                ViewMessage::text(prefix, format!("[Close] {} {}", code, reason))
+            }
+            MessageType::Close { code: None, .. } => ViewMessage::text(prefix, "[Close]".to_string()),
+            MessageType::ClientConnected => ViewMessage::text(prefix, "[Connected]".to_string()),
+            MessageType::ClientDisconnected => ViewMessage::text(prefix, "[Disconnected]".to_string()),
+            MessageType::ClientStale => ViewMessage::text(prefix, "[Stale]".to_string()),
+            MessageType::Event(text) => ViewMessage::text(prefix, format!("[Event] {}", text)),
+            MessageType::Timing(timing) => ViewMessage::text(prefix, format!("[Timing] {}", timing.describe())),
+            MessageType::Partial { preview, total } if preview.len() < *total => ViewMessage::data(
+                with_marker(&format!("[Pending {} bytes, first {} shown]", total, preview.len())),
+                preview.clone(),
+            ),
+            MessageType::Partial { preview, total } => {
+                ViewMessage::data(with_marker(&format!("[Pending {} bytes]", total)), preview.clone())
+            }
+        }
+    }
+}
+
+/// 将对话框输入按发送格式和帧类型转换为消息内容
+fn build_content(dialog: &InputDialog) -> Result<MessageType, String> {
+    let content = match dialog.frame_type {
+        // 关闭帧的输入为 "<关闭码> <原因>"，不按发送格式解析
+        Some(FrameType::Close) => build_close_content(&dialog.input()),
+        Some(FrameType::Binary) => MessageType::Binary(Bytes::from(dialog.payload()?)),
+        Some(FrameType::Ping) => MessageType::Ping(Bytes::from(dialog.payload()?)),
+        Some(FrameType::Pong) => MessageType::Pong(Bytes::from(dialog.payload()?)),
+        // 文本帧的负载必须是合法的 UTF-8
+        Some(FrameType::Text) => MessageType::Text(
+            String::from_utf8(dialog.payload()?).map_err(|_| "Text frame payload is not valid UTF-8".to_string())?,
+        ),
+        None if dialog.format_type == FormatType::Hex => MessageType::Binary(Bytes::from(dialog.payload()?)),
+        None => match String::from_utf8(dialog.payload()?) {
+            core::result::Result::Ok(text) => MessageType::Text(text),
+            Err(e) => MessageType::Binary(Bytes::from(e.into_bytes())),
+        },
+    };
+    core::result::Result::Ok(content)
+}
+
+/// 解析关闭帧输入 "<关闭码> <原因>"，省略关闭码时使用 1000
+fn build_close_content(input: &str) -> MessageType {
+    let input = input.trim();
+    if input.is_empty() {
+        return MessageType::Close { code: None, reason: String::new() };
+    }
+    let (code, reason) = match input.split_once(' ') {
+        Some((code, reason)) => (code, reason),
+        None => (input, ""),
+    };
+    match code.parse::<u16>() {
+        core::result::Result::Ok(code) => MessageType::Close {
+            code: Some(code),
+            reason: reason.to_string(),
+        },
+        Err(_) => MessageType::Close {
+            code: Some(1000),
+            reason: input.to_string(),
+        },
+    }
 }
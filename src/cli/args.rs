@@ -1,6 +1,13 @@
-use clap::{Parser, Subcommand, Args as ClapArgs};
+use clap::{ArgMatches, Parser, Args as ClapArgs};
 use std::net::SocketAddr;
+use std::fmt;
+use std::path::PathBuf;
+use std::time::Duration;
 
+use crate::protocols::file_transfer::ChunkConfig;
+use crate::protocols::registry::HandlerRegistry;
+
+// 协议子命令由各协议处理器注册，见 HandlerRegistry::command
 /// 终端网络调试工具
 #[derive(Parser, Debug, Clone)]
 #[command(name = "nt", author, version, about)]
@@ -9,137 +16,77 @@ pub struct Cli {
     #[arg(short, long)]
     pub vertical_layout: bool,
 
-    #[command(subcommand)]
-    pub command: Commands,
-}
-
-/// 支持的协议命令
-#[derive(Subcommand, Debug, Clone)]
-pub enum Commands {
-    /// TCP 协议
-    #[command(subcommand)]
-    Tcp(TcpCommands),
-    
-    /// TCP 服务器模式简写
-    #[command(alias = "tcps")]
-    TcpServer(ServerArgs),
-    
-    /// TCP 客户端模式简写
-    #[command(alias = "tcpc")]
-    TcpClient(ClientArgs),
-    
-    /// UDP 协议
-    #[command(subcommand)]
-    Udp(UdpCommands),
-    
-    /// UDP 服务器模式简写
-    #[command(alias = "udps")]
-    UdpServer(ServerArgs),
-    
-    /// UDP 客户端模式简写
-    #[command(alias = "udpc")]
-    UdpClient(ClientArgs),
-    
-    /// WebSocket 协议
-    #[command(alias = "ws", subcommand)]
-    WebSocket(WebSocketCommands),
-    
-    /// HTTP 协议
-    #[command(subcommand)]
-    Http(HttpCommands),
-    
-    /// HTTP/2 协议
-    #[command(subcommand)]
-    Http2(HttpCommands),
-    
-    /// HTTP/3 协议
-    #[command(subcommand)]
-    Http3(HttpCommands),
-}
-
-/// TCP 命令
-#[derive(Subcommand, Debug, Clone)]
-pub enum TcpCommands {
-    /// TCP 服务器模式
-    #[command(alias = "s")]
-    Server(ServerArgs),
-    
-    /// TCP 客户端模式
-    #[command(alias = "c")]
-    Client(ClientArgs),
-}
+    /// 文件发送参数
+    #[command(flatten)]
+    pub file_send: FileSendArgs,
 
-/// UDP 命令
-#[derive(Subcommand, Debug, Clone)]
-pub enum UdpCommands {
-    /// UDP 服务器模式
-    #[command(alias = "s")]
-    Server(ServerArgs),
-    
-    /// UDP 客户端模式
-    #[command(alias = "c")]
-    Client(ClientArgs),
+    /// 快捷发送槽位文件 (F1-F12)，默认为配置目录下的 slots.json
+    #[arg(long, global = true)]
+    pub slots: Option<PathBuf>,
 }
 
-/// WebSocket 命令
-#[derive(Subcommand, Debug, Clone)]
-pub enum WebSocketCommands {
-    /// WebSocket 服务器模式
-    #[command(alias = "s")]
-    Server(ServerArgs),
-    
-    /// WebSocket 客户端模式
-    #[command(alias = "c")]
-    Client(ClientArgs),
+/// 文件发送参数
+#[derive(ClapArgs, Debug, Clone)]
+pub struct FileSendArgs {
+    /// 连接建立后发送的文件 (服务器模式在第一个客户端连接后发送)
+    #[arg(long, global = true)]
+    pub send_file: Option<PathBuf>,
+
+    /// 文件分块大小 (字节)
+    #[arg(long, global = true, default_value_t = 1024, value_parser = clap::value_parser!(u64).range(1..))]
+    pub chunk_size: u64,
+
+    /// 文件分块之间的延迟 (毫秒)
+    #[arg(long, global = true, default_value_t = 0)]
+    pub chunk_delay: u64,
 }
 
-/// HTTP 命令 (对于HTTP/1.1, HTTP/2, HTTP/3)
-#[derive(Subcommand, Debug, Clone)]
-pub enum HttpCommands {
-    /// HTTP 服务器模式
-    #[command(alias = "s")]
-    Server(ServerArgs),
-    
-    /// HTTP 客户端模式
-    #[command(alias = "c")]
-    HttpClient(HttpClientArgs),
+impl FileSendArgs {
+    /// 分块发送配置
+    pub fn chunk_config(&self) -> ChunkConfig {
+        ChunkConfig {
+            chunk_size: self.chunk_size as usize,
+            delay: Duration::from_millis(self.chunk_delay),
+        }
+    }
 }
 
-/// 服务器参数
+/// 服务器监听地址参数
 #[derive(ClapArgs, Debug, Clone)]
-pub struct ServerArgs {
+pub struct ListenArgs {
     /// 服务器地址 (如 127.0.0.1:8000)
     /// 如果只提供端口号则绑定到 127.0.0.1
     pub address: String,
 }
 
-/// 客户端参数
+impl ListenArgs {
+    /// 监听地址
+    pub fn local_addr(&self) -> SocketAddr {
+        parse_address(&self.address)
+    }
+}
+
+/// 客户端本地和远程地址参数
 #[derive(ClapArgs, Debug, Clone)]
-pub struct ClientArgs {
+pub struct EndpointArgs {
     /// 本地地址 (如 127.0.0.1:9000)
     /// 如果只提供端口号则绑定到 127.0.0.1
     pub local: String,
-    
+
     /// 远程服务器地址 (如 192.168.1.1:8000)
     pub remote: String,
 }
 
-/// HTTP 客户端参数
-#[derive(ClapArgs, Debug, Clone)]
-pub struct HttpClientArgs {
-    /// HTTP 请求方法 (GET, POST, PUT 等)
-    pub method: String,
-    
-    /// 目标 URL
-    pub url: String,
-    
-    /// 请求体，适用于 POST/PUT 等方法
-    #[arg(short, long)]
-    pub body: Option<String>,
-    
-    /// 请求头，格式为 "Header-Name: Value"
-    #[arg(short = 'H', long)]
-    pub headers: Vec<String>,
+impl EndpointArgs {
+    /// 本地地址
+    pub fn local_addr(&self) -> SocketAddr {
+        parse_address(&self.local)
+    }
+
+    /// 远程服务器地址
+    pub fn remote_addr(&self) -> SocketAddr {
+        parse_address(&self.remote)
+    }
 }
 
 /// 命令行参数完整结构
@@ -147,25 +94,25 @@ pub struct HttpClientArgs {
 pub struct Args {
     /// 垂直布局标志
     pub vertical_layout: bool,
+
+    /// 文件发送参数
+    pub file_send: FileSendArgs,
+
+    /// 快捷发送槽位文件
+    pub slots: Option<PathBuf>,
     
     /// 使用的协议类型
     pub protocol: ProtocolType,
     
     /// 模式 (服务端或客户端)
     pub mode: AppMode,
-    
-    /// 本地地址
-    pub local_addr: SocketAddr,
-    
-    /// 远程地址 (仅客户端模式)
-    pub remote_addr: Option<SocketAddr>,
-    
-    /// HTTP 特定参数 (仅HTTP协议)
-    pub http_args: Option<HttpClientArgs>,
+
+    /// 协议子命令的参数，由对应的协议处理器在创建时解析
+    pub protocol_matches: ArgMatches,
 }
 
 /// 协议类型
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum ProtocolType {
     Tcp,
     Udp,
@@ -175,93 +122,57 @@ pub enum ProtocolType {
     Http3,
 }
 
+impl ProtocolType {
+    /// 协议的小写标识 (用于配置文件名)
+    pub fn key(&self) -> &'static str {
+        match self {
+            ProtocolType::Tcp => "tcp",
+            ProtocolType::Udp => "udp",
+            ProtocolType::WebSocket => "websocket",
+            ProtocolType::Http => "http",
+            ProtocolType::Http2 => "http2",
+            ProtocolType::Http3 => "http3",
+        }
+    }
+}
+
+impl fmt::Display for ProtocolType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            ProtocolType::Tcp => "TCP",
+            ProtocolType::Udp => "UDP",
+            ProtocolType::WebSocket => "WebSocket",
+            ProtocolType::Http => "HTTP",
+            ProtocolType::Http2 => "HTTP/2",
+            ProtocolType::Http3 => "HTTP/3",
+        };
+        f.write_str(name)
+    }
+}
+
 /// 应用模式
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum AppMode {
     Server,
     Client,
 }
 
-/// 解析命令行参数
-pub fn parse_args() -> Args {
-    let cli = Cli::parse();
-    
-    // 提取信息，转换成我们的Args结构
-    let (protocol, mode, local_addr, remote_addr, http_args) = match &cli.command {
-        Commands::Tcp(cmd) => match cmd {
-            TcpCommands::Server(args) => {
-                (ProtocolType::Tcp, AppMode::Server, parse_address(&args.address), None, None)
-            }
-            TcpCommands::Client(args) => {
-                (ProtocolType::Tcp, AppMode::Client, parse_address(&args.local), Some(parse_address(&args.remote)), None)
-            }
-        },
-        Commands::TcpServer(args) => {
-            (ProtocolType::Tcp, AppMode::Server, parse_address(&args.address), None, None)
-        },
-        Commands::TcpClient(args) => {
-            (ProtocolType::Tcp, AppMode::Client, parse_address(&args.local), Some(parse_address(&args.remote)), None)
-        },
-        Commands::Udp(cmd) => match cmd {
-            UdpCommands::Server(args) => {
-                (ProtocolType::Udp, AppMode::Server, parse_address(&args.address), None, None)
-            }
-            UdpCommands::Client(args) => {
-                (ProtocolType::Udp, AppMode::Client, parse_address(&args.local), Some(parse_address(&args.remote)), None)
-            }
-        },
-        Commands::UdpServer(args) => {
-            (ProtocolType::Udp, AppMode::Server, parse_address(&args.address), None, None)
-        },
-        Commands::UdpClient(args) => {
-            (ProtocolType::Udp, AppMode::Client, parse_address(&args.local), Some(parse_address(&args.remote)), None)
-        },
-        Commands::WebSocket(cmd) => match cmd {
-            WebSocketCommands::Server(args) => {
-                (ProtocolType::WebSocket, AppMode::Server, parse_address(&args.address), None, None)
-            }
-            WebSocketCommands::Client(args) => {
-                (ProtocolType::WebSocket, AppMode::Client, parse_address(&args.local), Some(parse_address(&args.remote)), None)
-            }
-        },
-        Commands::Http(cmd) => match cmd {
-            HttpCommands::Server(args) => {
-                (ProtocolType::Http, AppMode::Server, parse_address(&args.address), None, None)
-            }
-            HttpCommands::HttpClient(args) => {
-                (ProtocolType::Http, AppMode::Client, parse_dummy_addr(), None, Some(args.clone()))
-            }
-        },
-        Commands::Http2(cmd) => match cmd {
-            HttpCommands::Server(args) => {
-                (ProtocolType::Http2, AppMode::Server, parse_address(&args.address), None, None)
-            }
-            HttpCommands::HttpClient(args) => {
-                (ProtocolType::Http2, AppMode::Client, parse_dummy_addr(), None, Some(args.clone()))
-            }
-        },
-        Commands::Http3(cmd) => match cmd {
-            HttpCommands::Server(args) => {
-                (ProtocolType::Http3, AppMode::Server, parse_address(&args.address), None, None)
-            }
-            HttpCommands::HttpClient(args) => {
-                (ProtocolType::Http3, AppMode::Client, parse_dummy_addr(), None, Some(args.clone()))
-            }
-        },
-    };
-
-    Args {
-        vertical_layout: cli.vertical_layout,
-        protocol,
-        mode,
-        local_addr,
-        remote_addr,
-        http_args,
+impl fmt::Display for AppMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AppMode::Server => f.write_str("Server"),
+            AppMode::Client => f.write_str("Client"),
+        }
     }
 }
 
+/// 解析命令行参数，协议子命令及其参数由注册表中的协议处理器提供
+pub fn parse_args() -> Args {
+    HandlerRegistry::default().parse_args(std::env::args_os())
+}
+
 /// 解析地址字符串，如果只提供端口则使用 127.0.0.1
-fn parse_address(addr_str: &str) -> SocketAddr {
+pub fn parse_address(addr_str: &str) -> SocketAddr {
     // 检查是否只有端口号
     if let Ok(port) = addr_str.parse::<u16>() {
         format!("127.0.0.1:{}", port).parse().unwrap()
@@ -276,8 +187,3 @@ fn parse_address(addr_str: &str) -> SocketAddr {
         }
     }
 }
-
-/// 为HTTP客户端模式生成一个虚拟地址，因为HTTP客户端不需要绑定到特定地址
-fn parse_dummy_addr() -> SocketAddr {
-    "127.0.0.1:0".parse().unwrap()
-}
\ No newline at end of file
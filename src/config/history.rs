@@ -0,0 +1,114 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::{fs, path::PathBuf};
+
+use crate::cli::args::ProtocolType;
+use crate::config::app_config_dir;
+use crate::ui::widgets::input_dialog::FormatType;
+
+/// 每个协议保留的最大历史记录数
+const MAX_ENTRIES: usize = 500;
+
+/// 发送历史记录
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    /// 输入的原始文本
+    pub text: String,
+    /// 发送时使用的格式
+    pub format: FormatType,
+}
+
+/// 按协议保存的发送历史 (从旧到新)
+pub struct SendHistory {
+    /// 历史文件路径 (无法确定配置目录时为 None，仅在内存中保存)
+    path: Option<PathBuf>,
+    /// 历史记录
+    entries: Vec<HistoryEntry>,
+}
+
+impl SendHistory {
+    /// 加载协议的发送历史，文件不存在时返回空历史
+    pub fn load(protocol: ProtocolType) -> Result<Self> {
+        let path = app_config_dir().map(|dir| dir.join("history").join(format!("{}.json", protocol.key())));
+        let mut history = Self {
+            path,
+            entries: Vec::new(),
+        };
+        if let Some(path) = history.path.as_ref().filter(|path| path.exists()) {
+            let content = fs::read_to_string(path)
+                .with_context(|| format!("Failed to read send history {}", path.display()))?;
+            history.entries = serde_json::from_str(&content)
+                .with_context(|| format!("Failed to parse send history {}", path.display()))?;
+        }
+        Ok(history)
+    }
+
+    /// 创建不保存到文件的空历史
+    pub fn in_memory() -> Self {
+        Self {
+            path: None,
+            entries: Vec::new(),
+        }
+    }
+
+    /// 所有历史记录
+    pub fn entries(&self) -> &[HistoryEntry] {
+        &self.entries
+    }
+
+    /// 添加历史记录，相同的记录移动到最新位置
+    pub fn push(&mut self, entry: HistoryEntry) {
+        self.entries.retain(|existing| *existing != entry);
+        self.entries.push(entry);
+        if self.entries.len() > MAX_ENTRIES {
+            self.entries.drain(..self.entries.len() - MAX_ENTRIES);
+        }
+    }
+
+    /// 保存到历史文件
+    pub fn save(&self) -> Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+        }
+        let content = serde_json::to_string_pretty(&self.entries)?;
+        fs::write(path, content).with_context(|| format!("Failed to write send history {}", path.display()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(text: &str, format: FormatType) -> HistoryEntry {
+        HistoryEntry {
+            text: text.to_string(),
+            format,
+        }
+    }
+
+    #[test]
+    fn test_push_moves_duplicates_and_limits_size() {
+        let mut history = SendHistory::in_memory();
+        history.push(entry("a", FormatType::String));
+        history.push(entry("01 02", FormatType::Hex));
+        history.push(entry("a", FormatType::String));
+        history.push(entry("a", FormatType::Hex));
+        assert_eq!(
+            history.entries(),
+            &[
+                entry("01 02", FormatType::Hex),
+                entry("a", FormatType::String),
+                entry("a", FormatType::Hex)
+            ]
+        );
+
+        for index in 0..MAX_ENTRIES {
+            history.push(entry(&index.to_string(), FormatType::String));
+        }
+        assert_eq!(history.entries().len(), MAX_ENTRIES);
+        assert_eq!(history.entries()[0].text, "0");
+    }
+}
@@ -1,2 +1,11 @@
+pub mod history;
 pub mod language;
-// pub mod tls;
\ No newline at end of file
+pub mod slots;
+pub mod tls;
+
+use std::path::PathBuf;
+
+/// 应用配置目录 (如 Linux 下的 ~/.config/nt)
+pub fn app_config_dir() -> Option<PathBuf> {
+    dirs_next::config_dir().map(|dir| dir.join("nt"))
+}
\ No newline at end of file
@@ -0,0 +1,164 @@
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::{fs, path::PathBuf};
+
+use crate::config::app_config_dir;
+use crate::ui::widgets::input_dialog::{FormatType, LineEnding};
+
+/// 快捷发送槽位数量 (F1-F12)
+pub const SLOT_COUNT: u8 = 12;
+
+/// 快捷发送槽位
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QuickSlot {
+    /// 功能键编号 (1-12)
+    pub key: u8,
+    /// 显示名称
+    pub name: String,
+    /// 发送内容 (字符串格式支持转义序列)
+    pub text: String,
+    /// 发送格式
+    #[serde(default = "default_format")]
+    pub format: FormatType,
+    /// 字符串格式下追加的行结束符
+    #[serde(default)]
+    pub line_ending: LineEnding,
+    /// 字符串格式下是否处理转义序列
+    #[serde(default = "default_escapes")]
+    pub escapes: bool,
+    /// 发送目标 (服务器模式下的客户端地址，不指定时发送到所有客户端)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target: Option<String>,
+}
+
+fn default_format() -> FormatType {
+    FormatType::String
+}
+
+fn default_escapes() -> bool {
+    true
+}
+
+/// 快捷发送槽位文件 (按功能键编号排序)
+pub struct SlotFile {
+    /// 槽位文件路径 (无法确定配置目录或文件无效时为 None，仅在内存中保存)
+    path: Option<PathBuf>,
+    /// 槽位列表
+    slots: Vec<QuickSlot>,
+}
+
+impl SlotFile {
+    /// 加载槽位文件，未指定路径时使用配置目录下的 slots.json，文件不存在时返回空列表
+    pub fn load(path: Option<PathBuf>) -> Result<Self> {
+        let path = path.or_else(|| app_config_dir().map(|dir| dir.join("slots.json")));
+        let mut file = Self {
+            path,
+            slots: Vec::new(),
+        };
+        if let Some(path) = file.path.clone().filter(|path| path.exists()) {
+            let content = fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read quick-send slots {}", path.display()))?;
+            let slots: Vec<QuickSlot> = serde_json::from_str(&content)
+                .with_context(|| format!("Failed to parse quick-send slots {}", path.display()))?;
+            for slot in slots {
+                if !(1..=SLOT_COUNT).contains(&slot.key) {
+                    bail!("Invalid quick-send slot key {} in {}, expected 1-{}", slot.key, path.display(), SLOT_COUNT);
+                }
+                if file.get(slot.key).is_some() {
+                    bail!("Duplicate quick-send slot F{} in {}", slot.key, path.display());
+                }
+                file.set(slot);
+            }
+        }
+        Ok(file)
+    }
+
+    /// 创建不保存到文件的空槽位列表
+    pub fn in_memory() -> Self {
+        Self {
+            path: None,
+            slots: Vec::new(),
+        }
+    }
+
+    /// 槽位文件路径
+    pub fn path(&self) -> Option<&PathBuf> {
+        self.path.as_ref()
+    }
+
+    /// 所有槽位
+    pub fn slots(&self) -> &[QuickSlot] {
+        &self.slots
+    }
+
+    /// 获取功能键对应的槽位
+    pub fn get(&self, key: u8) -> Option<&QuickSlot> {
+        self.slots.iter().find(|slot| slot.key == key)
+    }
+
+    /// 设置槽位，替换相同功能键的槽位
+    pub fn set(&mut self, slot: QuickSlot) {
+        self.remove(slot.key);
+        let index = self.slots.partition_point(|existing| existing.key < slot.key);
+        self.slots.insert(index, slot);
+    }
+
+    /// 删除槽位
+    pub fn remove(&mut self, key: u8) {
+        self.slots.retain(|slot| slot.key != key);
+    }
+
+    /// 保存到槽位文件
+    pub fn save(&self) -> Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            fs::create_dir_all(dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+        }
+        let content = serde_json::to_string_pretty(&self.slots)?;
+        fs::write(path, content).with_context(|| format!("Failed to write quick-send slots {}", path.display()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_load_and_save_slots() {
+        let path = std::env::temp_dir().join(format!("nt-slots-{}.json", std::process::id()));
+        fs::write(
+            &path,
+            r#"[
+                {"key": 3, "name": "Reset", "text": "AT+RST", "line_ending": "CrLf"},
+                {"key": 1, "name": "Ping", "text": "01 02", "format": "Hex", "target": "127.0.0.1:5000"}
+            ]"#,
+        )
+        .unwrap();
+
+        let mut file = SlotFile::load(Some(path.clone())).unwrap();
+        assert_eq!(file.slots().iter().map(|slot| slot.key).collect::<Vec<_>>(), vec![1, 3]);
+        assert_eq!(file.get(3).unwrap().format, FormatType::String);
+        assert_eq!(file.get(3).unwrap().line_ending, LineEnding::CrLf);
+
+        file.set(QuickSlot {
+            key: 2,
+            name: "Hello".to_string(),
+            text: "hello".to_string(),
+            format: FormatType::String,
+            line_ending: LineEnding::None,
+            escapes: true,
+            target: None,
+        });
+        file.remove(1);
+        file.save().unwrap();
+
+        let reloaded = SlotFile::load(Some(path.clone())).unwrap();
+        assert_eq!(reloaded.slots().iter().map(|slot| slot.key).collect::<Vec<_>>(), vec![2, 3]);
+
+        fs::write(&path, r#"[{"key": 13, "name": "x", "text": "x"}]"#).unwrap();
+        assert!(SlotFile::load(Some(path.clone())).is_err());
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -1,7 +1,10 @@
 use anyhow::{Context, Result};
+use rcgen::{generate_simple_self_signed, CertifiedKey};
 use rustls::{
-    ClientConfig, RootCertStore, ServerConfig,
-    crypto::ring::sign::RsaSigningKey,
+    client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier},
+    crypto::{verify_tls12_signature, verify_tls13_signature, CryptoProvider},
+    pki_types::{CertificateDer, PrivateKeyDer, PrivatePkcs8KeyDer, ServerName, UnixTime},
+    ClientConfig, DigitallySignedStruct, RootCertStore, ServerConfig, SignatureScheme,
 };
 use std::{
     fs::File,
@@ -9,33 +12,59 @@ use std::{
     path::Path,
     sync::Arc,
 };
-use rustls_pemfile::{certs, pkcs8_private_keys};
+use rustls_pemfile::{certs, private_key};
 
 /// TLS 配置管理
 pub struct TlsConfig;
 
 impl TlsConfig {
     /// 创建 TLS 客户端配置
-    pub fn client_config() -> Result<ClientConfig> {
+    ///
+    /// 在系统根证书之外额外信任 `ca_cert` 中的证书；`insecure` 为 true 时跳过服务器证书校验
+    pub fn client_config(ca_cert: Option<&Path>, insecure: bool) -> Result<ClientConfig> {
+        if insecure {
+            let provider = Arc::new(rustls::crypto::aws_lc_rs::default_provider());
+            let config = ClientConfig::builder()
+                .dangerous()
+                .with_custom_certificate_verifier(Arc::new(NoCertificateVerification(provider)))
+                .with_no_client_auth();
+            return Ok(config);
+        }
+
         let mut root_cert_store = RootCertStore::empty();
-        
-        // 加载系统根证书
-        let certs = rustls_native_certs::load_native_certs()
-            .context("Failed to load native certificates")?;
-            
-        for cert in certs {
-            root_cert_store
-                .add(rustls::pki_types::CertificateDer::from(cert.0))
-                .context("Failed to add certificate to root store")?;
+
+        // 加载系统根证书 (忽略个别无法解析的证书)
+        let native_certs = rustls_native_certs::load_native_certs();
+        if native_certs.certs.is_empty() && ca_cert.is_none() {
+            if let Some(error) = native_certs.errors.into_iter().next() {
+                return Err(error).context("Failed to load native certificates");
+            }
+        }
+        let (_added, _ignored) = root_cert_store.add_parsable_certificates(native_certs.certs);
+
+        // 加载自定义 CA 证书
+        if let Some(ca_cert) = ca_cert {
+            let ca_file = File::open(ca_cert)
+                .with_context(|| format!("Failed to open CA certificate file {}", ca_cert.display()))?;
+            let mut reader = BufReader::new(ca_file);
+            let ca_certs = certs(&mut reader)
+                .collect::<Result<Vec<_>, _>>()
+                .context("Failed to parse CA certificates")?;
+            if ca_certs.is_empty() {
+                anyhow::bail!("No certificates found in {}", ca_cert.display());
+            }
+            for cert in ca_certs {
+                root_cert_store.add(cert).context("Invalid CA certificate")?;
+            }
         }
-        
+
         let config = ClientConfig::builder()
             .with_root_certificates(root_cert_store)
             .with_no_client_auth();
-            
+
         Ok(config)
     }
-    
+
     /// 从文件创建 TLS 服务器配置
     pub fn server_config_from_files<P: AsRef<Path>>(
         cert_path: P,
@@ -48,33 +77,81 @@ impl TlsConfig {
         let certs = certs(&mut reader)
             .collect::<Result<Vec<_>, _>>()
             .context("Failed to parse certificates")?;
-        
-        // 加载私钥
+
+        if certs.is_empty() {
+            anyhow::bail!("No certificates found");
+        }
+
+        // 加载私钥 (支持 PKCS#1、PKCS#8 和 SEC1 格式)
         let key_file = File::open(key_path)
             .context("Failed to open private key file")?;
         let mut reader = BufReader::new(key_file);
-        let keys = pkcs8_private_keys(&mut reader)
-            .collect::<Result<Vec<_>, _>>()
-            .context("Failed to parse private key")?;
-        
-        if keys.is_empty() {
-            anyhow::bail!("No private keys found");
-        }
-        
+        let key = private_key(&mut reader)
+            .context("Failed to parse private key")?
+            .ok_or_else(|| anyhow::anyhow!("No private keys found"))?;
+
         // 使用证书和私钥创建服务器配置
         let config = ServerConfig::builder()
             .with_no_client_auth()
-            .with_single_cert(
-                certs.into_iter().map(rustls::pki_types::CertificateDer::from).collect(),
-                rustls::pki_types::PrivateKeyDer::from(keys[0].clone())
-            )
+            .with_single_cert(certs, key)
             .context("Failed to create server config with certificate and key")?;
-            
+
         Ok(config)
     }
-    
-    /// 创建自签名证书的 TLS 服务器配置 (用于测试)
-    pub fn server_config_with_self_signed() -> Result<ServerConfig> {
-        todo!("Generate self-signed certificate for testing")
+
+    /// 创建自签名证书的 TLS 服务器配置 (用于测试)，同时返回证书的 PEM 文本
+    ///
+    /// 证书对 localhost、127.0.0.1 和 ::1 有效，每次调用重新生成
+    pub fn server_config_with_self_signed() -> Result<(ServerConfig, String)> {
+        let subject_alt_names = vec!["localhost".to_string(), "127.0.0.1".to_string(), "::1".to_string()];
+        let CertifiedKey { cert, key_pair } = generate_simple_self_signed(subject_alt_names)
+            .context("Failed to generate self-signed certificate")?;
+
+        let key = PrivateKeyDer::Pkcs8(PrivatePkcs8KeyDer::from(key_pair.serialize_der()));
+        let config = ServerConfig::builder()
+            .with_no_client_auth()
+            .with_single_cert(vec![cert.der().clone()], key)
+            .context("Failed to create server config with self-signed certificate")?;
+
+        Ok((config, cert.pem()))
+    }
+}
+
+/// 跳过服务器证书校验的验证器 (仍校验握手签名)，仅用于连接测试服务器
+#[derive(Debug)]
+struct NoCertificateVerification(Arc<CryptoProvider>);
+
+impl ServerCertVerifier for NoCertificateVerification {
+    fn verify_server_cert(
+        &self,
+        _end_entity: &CertificateDer<'_>,
+        _intermediates: &[CertificateDer<'_>],
+        _server_name: &ServerName<'_>,
+        _ocsp_response: &[u8],
+        _now: UnixTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        Ok(ServerCertVerified::assertion())
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        verify_tls12_signature(message, cert, dss, &self.0.signature_verification_algorithms)
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        verify_tls13_signature(message, cert, dss, &self.0.signature_verification_algorithms)
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.0.signature_verification_algorithms.supported_schemes()
     }
-}
\ No newline at end of file
+}
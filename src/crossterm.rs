@@ -2,9 +2,14 @@ use std::error::Error;
 use std::io;
 use std::time::{Duration, Instant};
 
-use crossterm::event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEventKind};
+use crossterm::event::{
+    self, DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste, EnableMouseCapture, Event, KeyEventKind,
+    KeyboardEnhancementFlags, PopKeyboardEnhancementFlags, PushKeyboardEnhancementFlags,
+};
 use crossterm::execute;
-use crossterm::terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen};
+use crossterm::terminal::{
+    disable_raw_mode, enable_raw_mode, supports_keyboard_enhancement, EnterAlternateScreen, LeaveAlternateScreen,
+};
 use ratatui::backend::{Backend, CrosstermBackend};
 use ratatui::Terminal;
 
@@ -12,29 +17,54 @@ use crate::app::App;
 use crate::cli::args::Args;
 use crate::ui::ui;
 
-pub async fn run(tick_rate: Duration, enhanced_graphics: bool, args: Args) -> Result<(), Box<dyn Error>> {
+pub async fn run(tick_rate: Duration, args: Args) -> Result<(), Box<dyn Error>> {
     // setup terminal
     enable_raw_mode()?;
     let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
+    // 启用括号粘贴，粘贴内容作为整体送达而不是逐个按键
+    execute!(stdout, EnterAlternateScreen, EnableMouseCapture, EnableBracketedPaste)?;
+    // 终端支持时区分 Shift+Enter 等组合键 (输入框换行)
+    let keyboard_enhancement = supports_keyboard_enhancement().unwrap_or(false);
+    if keyboard_enhancement {
+        execute!(stdout, PushKeyboardEnhancementFlags(KeyboardEnhancementFlags::DISAMBIGUATE_ESCAPE_CODES))?;
+    }
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
     // create app and run it
-    let mut app = App::new(args).await.unwrap();
-
-    let app_result = run_app(&mut terminal, &mut app, tick_rate).await;
+    let app_result = match App::new(args).await {
+        Ok(mut app) => run_app(&mut terminal, &mut app, tick_rate).await,
+        // 启动失败 (如端口被占用) 时显示错误界面，按任意键退出
+        Err(err) => show_startup_error(&mut terminal, &err).and(Err(format!("{:#}", err).into())),
+    };
 
     // restore terminal
+    if keyboard_enhancement {
+        execute!(terminal.backend_mut(), PopKeyboardEnhancementFlags)?;
+    }
     disable_raw_mode()?;
-    execute!(terminal.backend_mut(), LeaveAlternateScreen, DisableMouseCapture)?;
+    execute!(terminal.backend_mut(), LeaveAlternateScreen, DisableMouseCapture, DisableBracketedPaste)?;
     terminal.show_cursor()?;
 
-    if let Err(err) = app_result {
-        println!("{err:?}");
+    app_result
+}
+
+/// 显示启动错误并等待按键
+fn show_startup_error<B: Backend>(terminal: &mut Terminal<B>, err: &anyhow::Error) -> Result<(), Box<dyn Error>> {
+    // 逐层列出错误原因
+    let mut message = err.to_string();
+    for cause in err.chain().skip(1) {
+        message.push_str(&format!("\n  caused by: {}", cause));
     }
 
-    Ok(())
+    loop {
+        terminal.draw(|frame| ui::draw_startup_error(frame, &message))?;
+        if let Event::Key(key) = event::read()? {
+            if key.kind == KeyEventKind::Press {
+                return Ok(());
+            }
+        }
+    }
 }
 
 async fn run_app<B: Backend>(
@@ -46,7 +76,7 @@ async fn run_app<B: Backend>(
     loop {
         // 检查应用是否需要退出
         if app.should_quit {
-            return Ok(());
+            break;
         }
 
         // 接收服务端消息
@@ -61,20 +91,27 @@ async fn run_app<B: Backend>(
             .unwrap_or_else(|| Duration::from_secs(0));
 
         if crossterm::event::poll(timeout)? {
-            if let Event::Key(key) = event::read()? {
-                if key.kind == KeyEventKind::Press {
+            match event::read()? {
+                Event::Key(key) if key.kind == KeyEventKind::Press => {
                     app.handle_key_event(key.code, key.modifiers)?;
 
                     if app.should_quit {
-                        return Ok(());
+                        break;
                     }
                 }
+                Event::Paste(text) => app.handle_paste(&text),
+                _ => {}
             }
         }
 
         if last_tick.elapsed() >= tick_rate {
             // 处理定时任务
+            app.on_tick();
             last_tick = Instant::now();
         }
     }
+
+    // 退出前停止协议处理器
+    app.protocol_handler.stop().await?;
+    Ok(())
 }
@@ -23,6 +23,9 @@ async fn main() -> Result<()> {
     // app.run().await?;
 
     let tick_rate = Duration::from_millis(100);
-    crossterm::run(tick_rate, true,args).await.unwrap();
+    if let Err(err) = crossterm::run(tick_rate, args).await {
+        eprintln!("Error: {err}");
+        std::process::exit(1);
+    }
     Ok(())
 }
@@ -2,11 +2,16 @@ use anyhow::Result;
 use async_trait::async_trait;
 use bytes::Bytes;
 use chrono::{DateTime, Local};
-use h2::server;
-use std::net::SocketAddr;
-use tokio::sync::mpsc::{Receiver, Sender};
+use std::{
+    net::SocketAddr,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+use tokio::sync::{mpsc::Sender, oneshot, watch};
 
-use crate::protocols::tcp::TcpServerHandler;
 
 /// 传输消息类型
 #[derive(Debug, Clone)]
@@ -15,12 +20,83 @@ pub enum MessageType {
     Text(String),
     /// 二进制消息
     Binary(Bytes),
-    /// 十六进制消息
-    Hex(String),
+    /// WebSocket Ping 帧
+    Ping(Bytes),
+    /// WebSocket Pong 帧
+    Pong(Bytes),
+    /// WebSocket Close 帧
+    Close {
+        /// 关闭码
+        code: Option<u16>,
+        /// 关闭原因
+        reason: String,
+    },
     /// 客户端连接消息
     ClientConnected,
     /// 客户端断开连接消息
     ClientDisconnected,
+    /// 客户端空闲超时 (无连接协议的对端被标记为 stale)
+    ClientStale,
+    /// 连接事件 (重连、错误等提示信息)，发送方向时为已发出请求的描述
+    Event(String),
+    /// 请求耗时统计 (HTTP 类客户端)
+    Timing(RequestTiming),
+    /// 分帧接收时尚未组成完整帧的数据 (total 为 0 表示没有未完成的帧)
+    Partial {
+        /// 开头部分数据的预览
+        preview: Bytes,
+        /// 总字节数
+        total: usize,
+    },
+}
+
+/// 单次请求的耗时统计
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct RequestTiming {
+    /// DNS 解析耗时 (复用连接或无需解析时为 None)
+    pub dns: Option<Duration>,
+    /// 建立连接耗时 (复用连接时为 None)
+    pub connect: Option<Duration>,
+    /// 从发出请求到收到响应头的耗时
+    pub ttfb: Duration,
+    /// 从发出请求到读完响应体的总耗时
+    pub total: Duration,
+}
+
+impl RequestTiming {
+    /// 生成状态栏显示文本
+    pub fn describe(&self) -> String {
+        let format_duration = |duration: Option<Duration>| match duration {
+            Some(duration) => format!("{:.1} ms", duration.as_secs_f64() * 1000.0),
+            None => "-".to_string(),
+        };
+        format!(
+            "DNS: {} | Connect: {} | TTFB: {} | Total: {}",
+            format_duration(self.dns),
+            format_duration(self.connect),
+            format_duration(Some(self.ttfb)),
+            format_duration(Some(self.total))
+        )
+    }
+}
+
+impl MessageType {
+    /// 将消息内容转换为待发送的字节数据，非数据类消息返回 None
+    pub fn to_bytes(&self) -> Option<Bytes> {
+        match self {
+            MessageType::Text(text) => Some(Bytes::from(text.clone())),
+            MessageType::Binary(data) => Some(data.clone()),
+            MessageType::Ping(_)
+            | MessageType::Pong(_)
+            | MessageType::Close { .. }
+            | MessageType::ClientConnected
+            | MessageType::ClientDisconnected
+            | MessageType::ClientStale
+            | MessageType::Event(_)
+            | MessageType::Timing(_)
+            | MessageType::Partial { .. } => None,
+        }
+    }
 }
 
 /// 消息方向
@@ -52,6 +128,53 @@ pub struct Message {
     pub timestamp: DateTime<Local>,
     /// 连接信息
     pub connection_info: Option<ConnectionInfo>,
+    /// 写出确认 (None 表示发送方不需要确认)
+    pub ack: Option<WriteAck>,
+}
+
+/// 消息写出确认，协议处理器写出数据后标记，所有副本释放时把是否写出的结果通知发送方
+#[derive(Debug, Clone)]
+pub struct WriteAck(Arc<WriteAckInner>);
+
+#[derive(Debug)]
+struct WriteAckInner {
+    /// 是否已写出
+    written: AtomicBool,
+    /// 发送方的取消信号 (None 表示不可取消)
+    cancelled: Option<watch::Receiver<bool>>,
+    /// 结果通知通道
+    done: Option<oneshot::Sender<bool>>,
+}
+
+impl WriteAck {
+    /// 创建写出确认，返回的接收端在确认释放后收到是否写出
+    pub fn new(cancelled: Option<watch::Receiver<bool>>) -> (Self, oneshot::Receiver<bool>) {
+        let (done_tx, done_rx) = oneshot::channel();
+        let inner = WriteAckInner {
+            written: AtomicBool::new(false),
+            cancelled,
+            done: Some(done_tx),
+        };
+        (Self(Arc::new(inner)), done_rx)
+    }
+
+    /// 标记数据已写出
+    pub fn mark_written(&self) {
+        self.0.written.store(true, Ordering::Relaxed);
+    }
+
+    /// 发送方是否已取消
+    pub fn is_cancelled(&self) -> bool {
+        self.0.cancelled.as_ref().is_some_and(|cancelled| *cancelled.borrow())
+    }
+}
+
+impl Drop for WriteAckInner {
+    fn drop(&mut self) {
+        if let Some(done) = self.done.take() {
+            let _ = done.send(*self.written.get_mut());
+        }
+    }
 }
 
 impl Message {
@@ -62,9 +185,20 @@ impl Message {
             direction: MessageDirection::Received,
             timestamp: Local::now(),
             connection_info,
+            ack: None,
         }
     }
 
+    /// 创建新的连接事件消息
+    pub fn new_event(text: String, connection_info: Option<ConnectionInfo>) -> Self {
+        Self::new_received(MessageType::Event(text), connection_info)
+    }
+
+    /// 创建已发出请求的描述消息 (只在发送视图中显示，不计入发送统计)
+    pub fn new_request(text: String, connection_info: Option<ConnectionInfo>) -> Self {
+        Self::new_sent(MessageType::Event(text), connection_info)
+    }
+
     /// 创建新的发送消息
     pub fn new_sent(content: MessageType, connection_info: Option<ConnectionInfo>) -> Self {
         Self {
@@ -72,8 +206,27 @@ impl Message {
             direction: MessageDirection::Sent,
             timestamp: Local::now(),
             connection_info,
+            ack: None,
         }
     }
+
+    /// 附加写出确认
+    pub fn with_ack(mut self, ack: WriteAck) -> Self {
+        self.ack = Some(ack);
+        self
+    }
+
+    /// 标记消息已写出
+    pub fn mark_written(&self) {
+        if let Some(ref ack) = self.ack {
+            ack.mark_written();
+        }
+    }
+
+    /// 发送方是否已取消 (已取消的消息不再写出)
+    pub fn is_cancelled(&self) -> bool {
+        self.ack.as_ref().is_some_and(WriteAck::is_cancelled)
+    }
 }
 
 /// 通讯协议处理接口
@@ -85,73 +238,20 @@ pub trait ProtocolHandler {
     /// 停止协议处理器
     async fn stop(&mut self) -> Result<()>;
 
-    /// 发送消息
-    async fn send_message(&mut self, message: MessageType, target: Option<String>) -> Result<()>;
-
     /// 获取UI向服务端的发送通道
     fn get_ui_to_server_sender(&self) -> Option<Sender<Message>>;
 
     /// 设置服务端向UI的发送通道
     fn set_server_to_ui_sender(&mut self, sender: Sender<Message>);
 
-    /// 处理程序是否正在运行
-    fn is_running(&self) -> bool;
-
     /// 获取当前连接信息
     fn get_connections(&self) -> Vec<ConnectionInfo>;
 
+    /// 是否支持向所有连接广播 (UI发送的消息不带连接信息时视为广播)
+    fn supports_broadcast(&self) -> bool {
+        false
+    }
+
     /// 获取协议名称
     fn protocol_name(&self) -> &'static str;
 }
-
-/// 创建协议处理器工厂函数
-pub async fn create_protocol_handler(
-    protocol: &str,
-    is_server: bool,
-    server_to_ui_tx: Option<Sender<Message>>,
-    local_addr: SocketAddr,
-    remote_addr: Option<SocketAddr>,
-) -> Result<Box<dyn ProtocolHandler + Send + Sync>> {
-    match (protocol.to_lowercase().as_str(), is_server) {
-        ("tcp", true) => {
-            let mut handler = TcpServerHandler::new(local_addr);
-            handler.set_server_to_ui_sender(server_to_ui_tx.unwrap());
-            handler.start().await?;
-            Ok(Box::new(handler))
-        }
-        ("tcp", false) => {
-            todo!("Create TCP client handler")
-        }
-        ("udp", true) => {
-            todo!("Create UDP server handler")
-        }
-        ("udp", false) => {
-            todo!("Create UDP client handler")
-        }
-        ("websocket", true) => {
-            todo!("Create WebSocket server handler")
-        }
-        ("websocket", false) => {
-            todo!("Create WebSocket client handler")
-        }
-        ("http", true) => {
-            todo!("Create HTTP server handler")
-        }
-        ("http", false) => {
-            todo!("Create HTTP client handler")
-        }
-        ("http2", true) => {
-            todo!("Create HTTP/2 server handler")
-        }
-        ("http2", false) => {
-            todo!("Create HTTP/2 client handler")
-        }
-        ("http3", true) => {
-            todo!("Create HTTP/3 server handler")
-        }
-        ("http3", false) => {
-            todo!("Create HTTP/3 client handler")
-        }
-        _ => anyhow::bail!("Unsupported protocol: {}", protocol),
-    }
-}
@@ -0,0 +1,304 @@
+use anyhow::{Context, Result};
+use bytes::Bytes;
+use std::{
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+use tokio::{
+    fs::File,
+    io::AsyncReadExt,
+    sync::{mpsc::Sender, watch},
+};
+
+use crate::protocols::common::{ConnectionInfo, Message, MessageType, WriteAck};
+
+/// 分块发送配置
+#[derive(Debug, Clone, Copy)]
+pub struct ChunkConfig {
+    /// 每块字节数
+    pub chunk_size: usize,
+    /// 块之间的延迟
+    pub delay: Duration,
+}
+
+/// 文件发送状态
+#[derive(Debug, Clone, PartialEq)]
+pub enum TransferState {
+    /// 发送中
+    Running,
+    /// 发送完成
+    Completed,
+    /// 已取消
+    Cancelled,
+    /// 发送失败
+    Failed(String),
+}
+
+/// 文件发送进度
+#[derive(Debug, Clone)]
+pub struct TransferStatus {
+    /// 已写出的字节数
+    pub sent: u64,
+    /// 当前状态
+    pub state: TransferState,
+}
+
+/// 正在进行的文件发送
+pub struct FileTransfer {
+    /// 文件路径
+    pub path: PathBuf,
+    /// 文件总字节数
+    pub total: u64,
+    /// 目标连接 (None 表示当前连接或广播)
+    pub target: Option<String>,
+    /// 开始时间
+    pub started: Instant,
+    /// 已计入统计的字节数
+    reported: u64,
+    /// 发送进度
+    status_rx: watch::Receiver<TransferStatus>,
+    /// 取消信号
+    cancel_tx: watch::Sender<bool>,
+}
+
+impl FileTransfer {
+    /// 开始分块发送文件，数据块通过协议处理器的发送通道逐块发出，写出一块后再发下一块
+    pub fn start(
+        path: &Path,
+        config: ChunkConfig,
+        ui_to_server_tx: Sender<Message>,
+        connection_info: Option<ConnectionInfo>,
+    ) -> Result<Self> {
+        let total = std::fs::metadata(path)
+            .with_context(|| format!("Cannot read {}", path.display()))?
+            .len();
+        let (status_tx, status_rx) = watch::channel(TransferStatus {
+            sent: 0,
+            state: TransferState::Running,
+        });
+        let (cancel_tx, cancel_rx) = watch::channel(false);
+
+        let target = connection_info.as_ref().map(|info| info.connection_id.clone());
+        let task_path = path.to_path_buf();
+        tokio::spawn(async move {
+            let result = send_chunks(&task_path, config, ui_to_server_tx, connection_info, &status_tx, cancel_rx).await;
+            let state = match result {
+                Ok(true) => TransferState::Completed,
+                Ok(false) => TransferState::Cancelled,
+                Err(e) => TransferState::Failed(format!("{:#}", e)),
+            };
+            status_tx.send_modify(|status| status.state = state);
+        });
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            total,
+            target,
+            started: Instant::now(),
+            reported: 0,
+            status_rx,
+            cancel_tx,
+        })
+    }
+
+    /// 当前发送进度
+    pub fn status(&self) -> TransferStatus {
+        self.status_rx.borrow().clone()
+    }
+
+    /// 取消发送
+    pub fn cancel(&self) {
+        let _ = self.cancel_tx.send(true);
+    }
+
+    /// 取出上次调用以来新发送的字节数 (用于更新统计)
+    pub fn take_sent_delta(&mut self) -> u64 {
+        let sent = self.status().sent;
+        let delta = sent.saturating_sub(self.reported);
+        self.reported = sent;
+        delta
+    }
+
+    /// 平均发送速率 (字节/秒)
+    pub fn rate(&self) -> f64 {
+        let elapsed = self.started.elapsed().as_secs_f64();
+        if elapsed > 0.0 {
+            self.status().sent as f64 / elapsed
+        } else {
+            0.0
+        }
+    }
+
+    /// 文件名
+    pub fn file_name(&self) -> String {
+        self.path
+            .file_name()
+            .map_or_else(|| self.path.display().to_string(), |name| name.to_string_lossy().to_string())
+    }
+
+    /// 状态栏显示的进度文本
+    pub fn describe_progress(&self) -> String {
+        let sent = self.status().sent;
+        let percent = (sent * 100).checked_div(self.total).unwrap_or(100);
+        format!(
+            "{} {} {}% {}/{} {}/s",
+            self.file_name(),
+            progress_bar(sent, self.total, 20),
+            percent,
+            format_bytes(sent as f64),
+            format_bytes(self.total as f64),
+            format_bytes(self.rate())
+        )
+    }
+}
+
+/// 逐块读取文件并发送，每块等待协议处理器确认写出后计入进度，返回是否完整发送 (被取消时返回 false)
+async fn send_chunks(
+    path: &Path,
+    config: ChunkConfig,
+    ui_to_server_tx: Sender<Message>,
+    connection_info: Option<ConnectionInfo>,
+    status_tx: &watch::Sender<TransferStatus>,
+    mut cancel_rx: watch::Receiver<bool>,
+) -> Result<bool> {
+    let mut file = File::open(path)
+        .await
+        .with_context(|| format!("Cannot open {}", path.display()))?;
+    let mut buffer = vec![0u8; config.chunk_size.max(1)];
+    let mut sent = 0u64;
+
+    loop {
+        if *cancel_rx.borrow() {
+            return Ok(false);
+        }
+
+        // 读满一块或读到文件末尾
+        let mut filled = 0;
+        while filled < buffer.len() {
+            let n = file.read(&mut buffer[filled..]).await?;
+            if n == 0 {
+                break;
+            }
+            filled += n;
+        }
+        if filled == 0 {
+            return Ok(true);
+        }
+
+        // 等待协议处理器写出这一块，期间可以取消 (未写出的块会被协议处理器跳过)
+        let (ack, written_rx) = WriteAck::new(Some(cancel_rx.clone()));
+        let message = Message::new_sent(
+            MessageType::Binary(Bytes::copy_from_slice(&buffer[..filled])),
+            connection_info.clone(),
+        )
+        .with_ack(ack);
+        tokio::select! {
+            result = ui_to_server_tx.send(message) => {
+                result.map_err(|_| anyhow::anyhow!("Connection handler stopped"))?;
+            }
+            _ = cancel_rx.changed() => return Ok(false),
+        }
+        tokio::select! {
+            written = written_rx => {
+                if !written.unwrap_or(false) {
+                    anyhow::bail!("Chunk at offset {} was not written", sent);
+                }
+            }
+            _ = cancel_rx.changed() => return Ok(false),
+        }
+        sent += filled as u64;
+        status_tx.send_modify(|status| status.sent = sent);
+
+        if !config.delay.is_zero() {
+            tokio::select! {
+                _ = tokio::time::sleep(config.delay) => {}
+                _ = cancel_rx.changed() => return Ok(false),
+            }
+        }
+    }
+}
+
+/// 生成文本进度条
+pub fn progress_bar(done: u64, total: u64, width: usize) -> String {
+    let filled = if total > 0 {
+        ((done.min(total) as f64 / total as f64) * width as f64) as usize
+    } else {
+        width
+    };
+    format!("[{}{}]", "#".repeat(filled), "-".repeat(width - filled))
+}
+
+/// 格式化字节数 (B/KiB/MiB/GiB)
+pub fn format_bytes(bytes: f64) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+    let mut value = bytes;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{:.0} {}", value, UNITS[unit])
+    } else {
+        format!("{:.1} {}", value, UNITS[unit])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_progress_formatting() {
+        assert_eq!(progress_bar(0, 100, 10), "[----------]");
+        assert_eq!(progress_bar(55, 100, 10), "[#####-----]");
+        assert_eq!(progress_bar(0, 0, 4), "[####]");
+        assert_eq!(format_bytes(512.0), "512 B");
+        assert_eq!(format_bytes(1536.0), "1.5 KiB");
+        assert_eq!(format_bytes(3.0 * 1024.0 * 1024.0), "3.0 MiB");
+    }
+
+    #[tokio::test]
+    async fn test_send_chunks() {
+        let path = std::env::temp_dir().join(format!("nt-file-transfer-{}.bin", std::process::id()));
+        std::fs::write(&path, (0..10u8).collect::<Vec<_>>()).unwrap();
+
+        let (tx, mut rx) = tokio::sync::mpsc::channel(10);
+        let config = ChunkConfig {
+            chunk_size: 4,
+            delay: Duration::ZERO,
+        };
+        let mut transfer = FileTransfer::start(&path, config, tx, None).unwrap();
+
+        let mut chunks = Vec::new();
+        while let Some(message) = rx.recv().await {
+            // 上一块写出之前不会收到下一块
+            assert!(rx.is_empty());
+            assert_eq!(transfer.take_sent_delta(), 0);
+            chunks.push(message.content.to_bytes().unwrap().len());
+            message.mark_written();
+            drop(message);
+            tokio::task::yield_now().await;
+            assert_eq!(transfer.take_sent_delta(), *chunks.last().unwrap() as u64);
+        }
+
+        assert_eq!(chunks, vec![4, 4, 2]);
+        assert_eq!(transfer.status().state, TransferState::Completed);
+
+        // 未写出的块不计入进度
+        let (tx, mut rx) = tokio::sync::mpsc::channel(10);
+        let transfer = FileTransfer::start(&path, config, tx, None).unwrap();
+        drop(rx.recv().await.unwrap());
+        assert!(rx.recv().await.is_none());
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(transfer.status().sent, 0);
+        assert!(matches!(transfer.status().state, TransferState::Failed(_)));
+
+        // 取消后仍在通道中的块被跳过
+        let (ack, _written_rx) = WriteAck::new(Some(transfer.cancel_tx.subscribe()));
+        let message = Message::new_sent(MessageType::Binary(Bytes::new()), None).with_ack(ack);
+        assert!(!message.is_cancelled());
+        transfer.cancel();
+        assert!(message.is_cancelled());
+    }
+}
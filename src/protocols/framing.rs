@@ -0,0 +1,631 @@
+use bytes::{Buf, Bytes, BytesMut};
+use clap::Args;
+use std::{fmt, str::FromStr};
+
+use crate::utils::data_format::{bytes_to_display_string, unescape};
+
+/// 单帧最大长度，超过该值的帧 (或仍未出现结束标记的数据) 被丢弃
+pub const MAX_FRAME_LEN: usize = 16 * 1024 * 1024;
+
+/// 通知UI的未完成帧预览的最大字节数
+pub const PARTIAL_PREVIEW_LEN: usize = 256;
+
+/// 未完成的帧超过预览长度后，至少增长该字节数才再次通知UI
+const PARTIAL_REPORT_STEP: usize = 64 * 1024;
+
+/// STX/ETX 分帧的起始和结束字节
+const STX: u8 = 0x02;
+const ETX: u8 = 0x03;
+
+/// SLIP 特殊字节
+const SLIP_END: u8 = 0xC0;
+const SLIP_ESC: u8 = 0xDB;
+const SLIP_ESC_END: u8 = 0xDC;
+const SLIP_ESC_ESC: u8 = 0xDD;
+
+/// 流式数据的分帧方式
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum Framing {
+    /// 不分帧，每次读取的数据作为一条消息
+    #[default]
+    None,
+    /// 以分隔符结尾 (帧内容不包含分隔符)
+    Delimiter(Vec<u8>),
+    /// 长度前缀
+    LengthPrefix {
+        /// 长度字段字节数 (1/2/4)
+        width: usize,
+        /// 长度字段字节序
+        endian: Endian,
+        /// 长度是否包含长度字段本身
+        inclusive: bool,
+    },
+    /// 固定长度记录
+    Fixed(usize),
+    /// STX (0x02) 开始、ETX (0x03) 结束
+    StxEtx,
+    /// SLIP (RFC 1055)
+    Slip,
+    /// COBS，以 0x00 结尾
+    Cobs,
+}
+
+/// 长度字段字节序
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Endian {
+    Big,
+    Little,
+}
+
+/// 接收和发送的分帧方式
+#[derive(Debug, Clone, Default)]
+pub struct FramingConfig {
+    /// 接收数据的分帧方式
+    pub receive: Framing,
+    /// 发送数据的分帧方式
+    pub send: Framing,
+}
+
+/// 流式数据分帧参数
+#[derive(Args, Debug, Clone)]
+pub struct FramingArgs {
+    /// 收发数据的分帧方式: none, line, delim:<TEXT>, len:<u8|u16|u32>[:be|le][:incl],
+    /// fixed:<N>, stx-etx, slip, cobs
+    #[arg(long, default_value = "none")]
+    pub framing: Framing,
+
+    /// 接收数据的分帧方式，覆盖 --framing
+    #[arg(long)]
+    pub receive_framing: Option<Framing>,
+
+    /// 发送数据的分帧方式，覆盖 --framing
+    #[arg(long)]
+    pub send_framing: Option<Framing>,
+}
+
+impl FramingArgs {
+    /// 根据命令行参数生成分帧配置
+    pub fn framing_config(&self) -> FramingConfig {
+        FramingConfig {
+            receive: self.receive_framing.clone().unwrap_or_else(|| self.framing.clone()),
+            send: self.send_framing.clone().unwrap_or_else(|| self.framing.clone()),
+        }
+    }
+}
+
+impl Framing {
+    /// 按分帧方式封装待发送的数据
+    pub fn encode(&self, payload: &[u8]) -> Result<Bytes, String> {
+        let mut frame = BytesMut::with_capacity(payload.len() + 8);
+        match self {
+            Framing::None => frame.extend_from_slice(payload),
+            Framing::Delimiter(delimiter) => {
+                frame.extend_from_slice(payload);
+                frame.extend_from_slice(delimiter);
+            }
+            Framing::LengthPrefix { width, endian, inclusive } => {
+                let length = payload.len() + if *inclusive { *width } else { 0 };
+                let max = if *width >= 8 { u64::MAX } else { (1u64 << (width * 8)) - 1 };
+                if length as u64 > max {
+                    return Err(format!(
+                        "Payload of {} bytes does not fit a {}-byte length prefix",
+                        payload.len(),
+                        width
+                    ));
+                }
+                let bytes = match endian {
+                    Endian::Big => (length as u64).to_be_bytes()[8 - width..].to_vec(),
+                    Endian::Little => (length as u64).to_le_bytes()[..*width].to_vec(),
+                };
+                frame.extend_from_slice(&bytes);
+                frame.extend_from_slice(payload);
+            }
+            Framing::Fixed(size) => {
+                // 不足记录长度时补 0
+                if payload.len() > *size {
+                    return Err(format!("Payload of {} bytes exceeds the {}-byte record size", payload.len(), size));
+                }
+                frame.extend_from_slice(payload);
+                frame.resize(*size, 0);
+            }
+            Framing::StxEtx => {
+                if payload.contains(&ETX) {
+                    return Err("Payload contains ETX (0x03) and cannot be framed with STX/ETX".to_string());
+                }
+                frame.extend_from_slice(&[STX]);
+                frame.extend_from_slice(payload);
+                frame.extend_from_slice(&[ETX]);
+            }
+            Framing::Slip => {
+                frame.extend_from_slice(&[SLIP_END]);
+                for byte in payload {
+                    match *byte {
+                        SLIP_END => frame.extend_from_slice(&[SLIP_ESC, SLIP_ESC_END]),
+                        SLIP_ESC => frame.extend_from_slice(&[SLIP_ESC, SLIP_ESC_ESC]),
+                        byte => frame.extend_from_slice(&[byte]),
+                    }
+                }
+                frame.extend_from_slice(&[SLIP_END]);
+            }
+            Framing::Cobs => {
+                frame.extend_from_slice(&cobs_encode(payload));
+                frame.extend_from_slice(&[0]);
+            }
+        }
+        Ok(frame.freeze())
+    }
+}
+
+impl FromStr for Framing {
+    type Err = String;
+
+    /// 解析分帧方式: none, line, delim:<TEXT>, len:<u8|u16|u32>[:be|le][:incl], fixed:<N>, stx-etx, slip, cobs
+    fn from_str(spec: &str) -> Result<Self, Self::Err> {
+        let (kind, rest) = spec.split_once(':').unwrap_or((spec, ""));
+        match kind.to_ascii_lowercase().as_str() {
+            "none" => Ok(Framing::None),
+            "line" => Ok(Framing::Delimiter(b"\n".to_vec())),
+            "delim" => {
+                let delimiter = unescape(rest)?;
+                if delimiter.is_empty() {
+                    return Err("Delimiter must not be empty, e.g. delim:\\r\\n".to_string());
+                }
+                Ok(Framing::Delimiter(delimiter))
+            }
+            "len" => {
+                let mut parts = rest.split(':').filter(|part| !part.is_empty());
+                let width = match parts.next().map(str::to_ascii_lowercase).as_deref() {
+                    Some("u8") => 1,
+                    Some("u16") => 2,
+                    Some("u32") => 4,
+                    _ => return Err("Length prefix must be u8, u16 or u32, e.g. len:u16:be".to_string()),
+                };
+                let (mut endian, mut inclusive) = (Endian::Big, false);
+                for part in parts {
+                    match part.to_ascii_lowercase().as_str() {
+                        "be" => endian = Endian::Big,
+                        "le" => endian = Endian::Little,
+                        "incl" => inclusive = true,
+                        "excl" => inclusive = false,
+                        other => return Err(format!("Unknown length prefix option '{}', expected be, le, incl or excl", other)),
+                    }
+                }
+                Ok(Framing::LengthPrefix { width, endian, inclusive })
+            }
+            "fixed" => match rest.parse::<usize>() {
+                Ok(size) if size > 0 && size <= MAX_FRAME_LEN => Ok(Framing::Fixed(size)),
+                _ => Err(format!("Fixed record size must be 1-{}, e.g. fixed:16", MAX_FRAME_LEN)),
+            },
+            "stx-etx" | "stxetx" => Ok(Framing::StxEtx),
+            "slip" => Ok(Framing::Slip),
+            "cobs" => Ok(Framing::Cobs),
+            _ => Err(format!(
+                "Unknown framing '{}', expected none, line, delim:<TEXT>, len:<u8|u16|u32>[:be|le][:incl], fixed:<N>, stx-etx, slip or cobs",
+                spec
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Framing {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Framing::None => write!(f, "none"),
+            Framing::Delimiter(delimiter) if delimiter == b"\n" => write!(f, "line"),
+            Framing::Delimiter(delimiter) => {
+                write!(f, "delim:{}", bytes_to_display_string(delimiter).replace('\n', "\\n").replace('\t', "\\t"))
+            }
+            Framing::LengthPrefix { width, endian, inclusive } => {
+                let endian = match endian {
+                    Endian::Big => "be",
+                    Endian::Little => "le",
+                };
+                write!(f, "len:u{}:{}{}", width * 8, endian, if *inclusive { ":incl" } else { "" })
+            }
+            Framing::Fixed(size) => write!(f, "fixed:{}", size),
+            Framing::StxEtx => write!(f, "stx-etx"),
+            Framing::Slip => write!(f, "slip"),
+            Framing::Cobs => write!(f, "cobs"),
+        }
+    }
+}
+
+/// 流式数据分帧解码器 (每个连接一个)
+pub struct FrameDecoder {
+    /// 分帧方式
+    framing: Framing,
+    /// 尚未组成完整帧的数据
+    buffer: BytesMut,
+    /// 缓冲区中已查找过结束标记的字节数，下次读取只查找新数据
+    scanned: usize,
+    /// 正在丢弃超长帧的剩余部分，直到下一个结束标记
+    skipping: bool,
+    /// 单帧最大长度
+    max_frame_len: usize,
+    /// 上次通知UI时未完成帧的字节数
+    reported: usize,
+    /// 上次通知后是否取出了帧或丢弃了数据 (未完成帧的内容已变化)
+    advanced: bool,
+}
+
+impl FrameDecoder {
+    pub fn new(framing: Framing) -> Self {
+        Self {
+            framing,
+            buffer: BytesMut::new(),
+            scanned: 0,
+            skipping: false,
+            max_frame_len: MAX_FRAME_LEN,
+            reported: 0,
+            advanced: false,
+        }
+    }
+
+    /// 是否分帧 (不分帧时读取的数据直接作为消息)
+    pub fn is_framed(&self) -> bool {
+        self.framing != Framing::None
+    }
+
+    /// 尚未组成完整帧的数据
+    pub fn pending(&self) -> &[u8] {
+        &self.buffer
+    }
+
+    /// 追加读取到的数据，返回解出的完整帧 (无法解码的数据以错误原因返回)
+    pub fn decode(&mut self, data: &[u8]) -> Vec<Result<Bytes, String>> {
+        if !self.is_framed() {
+            return vec![Ok(Bytes::copy_from_slice(data))];
+        }
+        self.buffer.extend_from_slice(data);
+        let buffered = self.buffer.len();
+
+        let mut frames = Vec::new();
+        loop {
+            match self.next_frame() {
+                Some(Ok(Some(frame))) => frames.push(Ok(frame)),
+                // 空帧 (如 SLIP 连续的 END) 和被丢弃的超长帧忽略
+                Some(Ok(None)) => {}
+                Some(Err(e)) => frames.push(Err(e)),
+                None => break,
+            }
+        }
+        self.advanced |= self.buffer.len() != buffered;
+        frames
+    }
+
+    /// 返回需要通知UI的未完成帧 (前 PARTIAL_PREVIEW_LEN 字节的预览, 总字节数)
+    ///
+    /// 只在内容明显变化时返回: 取出了帧、预览部分变化或继续增长了 PARTIAL_REPORT_STEP 字节，
+    /// 避免超长的未完成帧在每次读取时都被完整复制
+    pub fn partial_update(&mut self) -> Option<(Bytes, usize)> {
+        let pending = self.buffer.len();
+        let changed = if std::mem::take(&mut self.advanced) {
+            pending > 0 || self.reported > 0
+        } else {
+            pending != self.reported
+                && (self.reported < PARTIAL_PREVIEW_LEN || pending - self.reported >= PARTIAL_REPORT_STEP)
+        };
+        if !changed {
+            return None;
+        }
+        self.reported = pending;
+        Some((Bytes::copy_from_slice(&self.buffer[..pending.min(PARTIAL_PREVIEW_LEN)]), pending))
+    }
+
+    /// 从缓冲区取出下一帧，数据不足时返回 None
+    fn next_frame(&mut self) -> Option<Result<Option<Bytes>, String>> {
+        match self.framing.clone() {
+            Framing::None => None,
+            Framing::Delimiter(delimiter) => {
+                let frame = self.take_terminated(&delimiter, 0, true)?;
+                Some(frame.map(|frame| frame.map(BytesMut::freeze)))
+            }
+            Framing::LengthPrefix { width, endian, inclusive } => {
+                if self.buffer.len() < width {
+                    return None;
+                }
+                let header = &self.buffer[..width];
+                let length = match endian {
+                    Endian::Big => header.iter().fold(0usize, |acc, byte| (acc << 8) | *byte as usize),
+                    Endian::Little => header.iter().rev().fold(0usize, |acc, byte| (acc << 8) | *byte as usize),
+                };
+                let payload_len = if inclusive {
+                    match length.checked_sub(width) {
+                        Some(payload_len) => payload_len,
+                        None => return Some(Err(self.discard(format!("Length {} is smaller than the header", length)))),
+                    }
+                } else {
+                    length
+                };
+                if payload_len > self.max_frame_len {
+                    return Some(Err(self.discard(format!(
+                        "Length {} exceeds the {} byte limit",
+                        length, self.max_frame_len
+                    ))));
+                }
+                if self.buffer.len() < width + payload_len {
+                    return None;
+                }
+                self.buffer.advance(width);
+                Some(Ok(Some(self.buffer.split_to(payload_len).freeze())))
+            }
+            Framing::Fixed(size) => (self.buffer.len() >= size).then(|| Ok(Some(self.buffer.split_to(size).freeze()))),
+            Framing::StxEtx => {
+                // 丢弃 STX 之前的数据
+                match self.buffer.iter().position(|byte| *byte == STX) {
+                    Some(0) => {}
+                    Some(start) => {
+                        self.buffer.advance(start);
+                        self.scanned = 0;
+                        return Some(Err(format!("Discarded {} bytes before STX", start)));
+                    }
+                    None if self.buffer.is_empty() => return None,
+                    None => {
+                        let discarded = self.buffer.len();
+                        self.buffer.clear();
+                        self.scanned = 0;
+                        return Some(Err(format!("Discarded {} bytes before STX", discarded)));
+                    }
+                }
+                // 超长帧被丢弃后，剩余部分会作为 STX 之前的数据丢弃，不需要跳过
+                let frame = self.take_terminated(&[ETX], 1, false)?;
+                Some(frame.map(|frame| frame.map(|frame| frame.freeze().slice(1..))))
+            }
+            Framing::Slip => {
+                let frame = self.take_terminated(&[SLIP_END], 0, true)?;
+                Some(frame.and_then(|frame| match frame {
+                    Some(raw) if !raw.is_empty() => slip_decode(&raw).map(|frame| Some(Bytes::from(frame))),
+                    _ => Ok(None),
+                }))
+            }
+            Framing::Cobs => {
+                let frame = self.take_terminated(&[0], 0, true)?;
+                Some(frame.and_then(|frame| match frame {
+                    Some(raw) if !raw.is_empty() => cobs_decode(&raw).map(|frame| Some(Bytes::from(frame))),
+                    _ => Ok(None),
+                }))
+            }
+        }
+    }
+
+    /// 取出以 `terminator` 结尾的帧 (不包含结束标记)，从 `from` 开始查找，数据不足时返回 None
+    ///
+    /// 超过最大长度仍未出现结束标记时丢弃缓冲区，`skip_rest` 为 true 时继续丢弃直到下一个结束标记；
+    /// 丢弃的超长帧返回 Ok(None)
+    fn take_terminated(&mut self, terminator: &[u8], from: usize, skip_rest: bool) -> Option<Result<Option<BytesMut>, String>> {
+        // 结束标记可能跨越上次读取的末尾，从已查找位置回退 terminator.len() - 1 字节
+        let start = self.scanned.saturating_sub(terminator.len() - 1).max(from);
+        let Some(end) = find(&self.buffer[start.min(self.buffer.len())..], terminator).map(|position| start + position)
+        else {
+            self.scanned = self.buffer.len();
+            if self.buffer.len() - from <= self.max_frame_len {
+                return None;
+            }
+            let error = (!self.skipping).then(|| {
+                self.discard(format!("Frame exceeds the {} byte limit without a terminator", self.max_frame_len))
+            });
+            self.buffer.clear();
+            self.scanned = 0;
+            self.skipping = skip_rest;
+            return error.map(Err);
+        };
+
+        let frame = self.buffer.split_to(end);
+        self.buffer.advance(terminator.len());
+        self.scanned = 0;
+        if std::mem::take(&mut self.skipping) {
+            return Some(Ok(None));
+        }
+        if frame.len() - from > self.max_frame_len {
+            return Some(Err(format!(
+                "Frame of {} bytes exceeds the {} byte limit, discarded",
+                frame.len() - from,
+                self.max_frame_len
+            )));
+        }
+        Some(Ok(Some(frame)))
+    }
+
+    /// 数据无法继续解析时丢弃缓冲区，返回错误原因
+    fn discard(&mut self, reason: String) -> String {
+        let discarded = self.buffer.len();
+        self.buffer.clear();
+        self.scanned = 0;
+        format!("{}, discarded {} bytes", reason, discarded)
+    }
+}
+
+/// 查找子序列的位置
+fn find(data: &[u8], pattern: &[u8]) -> Option<usize> {
+    match pattern {
+        [byte] => data.iter().position(|b| b == byte),
+        _ => data.windows(pattern.len()).position(|window| window == pattern),
+    }
+}
+
+/// SLIP 反转义
+fn slip_decode(raw: &[u8]) -> Result<Vec<u8>, String> {
+    let mut frame = Vec::with_capacity(raw.len());
+    let mut bytes = raw.iter();
+    while let Some(byte) = bytes.next() {
+        if *byte != SLIP_ESC {
+            frame.push(*byte);
+            continue;
+        }
+        match bytes.next() {
+            Some(&SLIP_ESC_END) => frame.push(SLIP_END),
+            Some(&SLIP_ESC_ESC) => frame.push(SLIP_ESC),
+            Some(other) => return Err(format!("Invalid SLIP escape 0xDB 0x{:02X}", other)),
+            None => return Err("SLIP frame ends with an escape byte".to_string()),
+        }
+    }
+    Ok(frame)
+}
+
+/// COBS 编码 (不包含结尾的 0x00)
+fn cobs_encode(payload: &[u8]) -> Vec<u8> {
+    let mut encoded = Vec::with_capacity(payload.len() + payload.len() / 254 + 2);
+    let mut code_index = 0;
+    encoded.push(0);
+    let mut code = 1u8;
+    for byte in payload {
+        if *byte == 0 {
+            encoded[code_index] = code;
+            code_index = encoded.len();
+            encoded.push(0);
+            code = 1;
+        } else {
+            encoded.push(*byte);
+            code += 1;
+            if code == 0xFF {
+                encoded[code_index] = code;
+                code_index = encoded.len();
+                encoded.push(0);
+                code = 1;
+            }
+        }
+    }
+    encoded[code_index] = code;
+    encoded
+}
+
+/// COBS 解码 (不包含结尾的 0x00)
+fn cobs_decode(raw: &[u8]) -> Result<Vec<u8>, String> {
+    let mut decoded = Vec::with_capacity(raw.len());
+    let mut index = 0;
+    while index < raw.len() {
+        let code = raw[index] as usize;
+        if index + code > raw.len() {
+            return Err(format!("Invalid COBS frame: code {} at offset {} runs past the end", code, index));
+        }
+        decoded.extend_from_slice(&raw[index + 1..index + code]);
+        index += code;
+        // 0xFF 块之后和最后一个块之后没有隐含的 0
+        if code < 0xFF && index < raw.len() {
+            decoded.push(0);
+        }
+    }
+    Ok(decoded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 分两次写入编码后的数据，验证能还原出原始帧
+    fn round_trip(framing: &str, payloads: &[&[u8]]) {
+        let framing: Framing = framing.parse().unwrap();
+        let stream: Vec<u8> = payloads.iter().flat_map(|payload| framing.encode(payload).unwrap()).collect();
+        let (first, second) = stream.split_at(stream.len() / 2);
+
+        let mut decoder = FrameDecoder::new(framing.clone());
+        let mut frames: Vec<Bytes> = decoder.decode(first).into_iter().map(Result::unwrap).collect();
+        frames.extend(decoder.decode(second).into_iter().map(Result::unwrap));
+        assert_eq!(frames, payloads.iter().map(|p| Bytes::copy_from_slice(p)).collect::<Vec<_>>(), "{}", framing);
+        assert!(decoder.pending().is_empty());
+    }
+
+    #[test]
+    fn test_round_trips() {
+        let payloads: &[&[u8]] = &[b"hello", b"\x00\x02\x03\xC0\xDB", b"world!"];
+        round_trip("len:u8", payloads);
+        round_trip("len:u16:le:incl", payloads);
+        round_trip("len:u32:be", payloads);
+        round_trip("slip", payloads);
+        round_trip("cobs", payloads);
+        round_trip("delim:\\r\\n", &[b"AT", b"OK"]);
+        round_trip("stx-etx", &[b"abc", b"de"]);
+        round_trip("fixed:4", &[b"abcd", b"\x00\x01\x02\x03"]);
+        round_trip("cobs", &[&[0x11; 300]]);
+    }
+
+    #[test]
+    fn test_partial_frames_and_errors() {
+        let mut decoder = FrameDecoder::new("line".parse().unwrap());
+        let frames = decoder.decode(b"one\ntwo\nthr");
+        assert_eq!(frames, vec![Ok(Bytes::from("one")), Ok(Bytes::from("two"))]);
+        assert_eq!(decoder.pending(), b"thr");
+        assert_eq!(decoder.decode(b"ee\n"), vec![Ok(Bytes::from("three"))]);
+
+        let mut decoder = FrameDecoder::new(Framing::StxEtx);
+        let frames = decoder.decode(b"xx\x02ok\x03\x02pa");
+        assert_eq!(frames, vec![Err("Discarded 2 bytes before STX".to_string()), Ok(Bytes::from("ok"))]);
+        assert_eq!(decoder.pending(), b"\x02pa");
+
+        let mut decoder = FrameDecoder::new("len:u8:incl".parse().unwrap());
+        assert!(decoder.decode(b"\x00abc")[0].is_err());
+        assert!(decoder.pending().is_empty());
+    }
+
+    #[test]
+    fn test_partial_updates() {
+        let mut decoder = FrameDecoder::new("line".parse().unwrap());
+        decoder.decode(b"abc");
+        assert_eq!(decoder.partial_update(), Some((Bytes::from("abc"), 3)));
+        assert_eq!(decoder.partial_update(), None);
+
+        // 内容不同但长度相同的新的未完成帧
+        decoder.decode(b"\nxyz");
+        assert_eq!(decoder.partial_update(), Some((Bytes::from("xyz"), 3)));
+
+        // 超过预览长度后只在明显增长时通知，且只复制预览部分
+        decoder.decode(&[b'a'; PARTIAL_PREVIEW_LEN]);
+        assert_eq!(decoder.partial_update().unwrap().1, PARTIAL_PREVIEW_LEN + 3);
+        decoder.decode(&[b'a'; 4096]);
+        assert_eq!(decoder.partial_update(), None);
+        decoder.decode(&[b'a'; PARTIAL_REPORT_STEP]);
+        let (preview, total) = decoder.partial_update().unwrap();
+        assert_eq!((preview.len(), total), (PARTIAL_PREVIEW_LEN, PARTIAL_PREVIEW_LEN + 3 + 4096 + PARTIAL_REPORT_STEP));
+
+        decoder.decode(b"\n");
+        assert_eq!(decoder.partial_update(), Some((Bytes::new(), 0)));
+        decoder.decode(b"done\n");
+        assert_eq!(decoder.partial_update(), None);
+    }
+
+    #[test]
+    fn test_oversize_frames() {
+        // 分多次读取时结束标记跨越读取边界
+        let mut decoder = FrameDecoder::new("delim:\\r\\n".parse().unwrap());
+        decoder.max_frame_len = 8;
+        assert!(decoder.decode(b"abc\r").is_empty());
+        assert_eq!(decoder.decode(b"\n"), vec![Ok(Bytes::from("abc"))]);
+
+        // 超长且没有结束标记时丢弃，剩余部分直到下一个分隔符也被丢弃
+        assert!(decoder.decode(b"0123").is_empty());
+        assert!(decoder.decode(b"456789")[0].is_err());
+        assert!(decoder.pending().is_empty());
+        assert_eq!(decoder.decode(b"tail\r\nok\r\n"), vec![Ok(Bytes::from("ok"))]);
+
+        // 同一次读取中包含结束标记的超长帧
+        assert!(decoder.decode(b"0123456789\r\nok\r\n")[0].is_err());
+
+        for framing in [Framing::Slip, Framing::Cobs, Framing::StxEtx] {
+            let mut decoder = FrameDecoder::new(framing.clone());
+            decoder.max_frame_len = 8;
+            let frames = decoder.decode(&[0x02; 12]);
+            assert_eq!(frames.len(), 1, "{}", framing);
+            assert!(frames[0].is_err(), "{}", framing);
+            assert!(decoder.pending().is_empty(), "{}", framing);
+        }
+    }
+
+    #[test]
+    fn test_parse_and_encode() {
+        assert_eq!(
+            "len:u16:le:incl".parse::<Framing>().unwrap(),
+            Framing::LengthPrefix { width: 2, endian: Endian::Little, inclusive: true }
+        );
+        assert_eq!("len:u16:le:incl".parse::<Framing>().unwrap().to_string(), "len:u16:le:incl");
+        assert!("len:u24".parse::<Framing>().is_err());
+        assert!("fixed:0".parse::<Framing>().is_err());
+        assert!("delim:".parse::<Framing>().is_err());
+
+        let framing: Framing = "len:u16:be:incl".parse().unwrap();
+        assert_eq!(framing.encode(b"ab").unwrap().as_ref(), b"\x00\x04ab");
+        assert!("len:u8".parse::<Framing>().unwrap().encode(&[0; 256]).is_err());
+        assert_eq!(Framing::Fixed(4).encode(b"ab").unwrap().as_ref(), b"ab\x00\x00");
+        assert_eq!(Framing::Cobs.encode(b"\x11\x00\x22").unwrap().as_ref(), b"\x02\x11\x02\x22\x00");
+        assert_eq!(Framing::Slip.encode(b"\xC0").unwrap().as_ref(), b"\xC0\xDB\xDC\xC0");
+    }
+}
@@ -1,174 +1,753 @@
 use anyhow::Result;
 use async_trait::async_trait;
 use bytes::Bytes;
+use clap::{Args, FromArgMatches};
+use http_body_util::{BodyExt, Full};
 use hyper::{
-    body::Bytes as HyperBytes,
+    body::Incoming,
     server::conn::http1,
     service::service_fn,
-    Body, Client, Method, Request, Response, Server, StatusCode,
+    Method, Request, Response, StatusCode, Uri,
+};
+use hyper_rustls::{HttpsConnector, HttpsConnectorBuilder};
+use hyper_util::{
+    client::legacy::{
+        connect::{
+            dns::{GaiAddrs, GaiResolver, Name},
+            HttpConnector, HttpInfo,
+        },
+        Client,
+    },
+    rt::{TokioExecutor, TokioIo},
+};
+use std::{
+    convert::Infallible,
+    future::Future,
+    io,
+    net::SocketAddr,
+    path::PathBuf,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+    time::Instant,
 };
-use hyper_util::{rt::TokioExecutor, server::conn::auto};
-use std::{collections::HashMap, net::SocketAddr, sync::Arc};
 use tokio::{
     net::TcpListener,
-    sync::{mpsc::{Receiver, Sender, channel}, RwLock},
+    sync::{mpsc::{Sender, channel}, RwLock},
 };
+use tower_service::Service;
 
+use crate::cli::args::{AppMode, ListenArgs, ProtocolType};
+use crate::config::tls::TlsConfig;
 use crate::protocols::common::{
-    ConnectionInfo, Message, MessageDirection, MessageType, ProtocolHandler,
+    ConnectionInfo, Message, MessageType, ProtocolHandler, RequestTiming,
 };
+use crate::protocols::registry::HandlerRegistry;
+
+/// HTTP 服务器参数
+#[derive(Args, Debug, Clone)]
+pub struct HttpServerArgs {
+    #[command(flatten)]
+    pub listen: ListenArgs,
+
+    /// 默认响应参数
+    #[command(flatten)]
+    pub response: HttpResponseArgs,
+}
+
+/// HTTP 服务器默认响应参数
+#[derive(Args, Debug, Clone)]
+pub struct HttpResponseArgs {
+    /// HTTP 默认响应状态码
+    #[arg(long, default_value_t = 200, value_parser = clap::value_parser!(u16).range(100..1000))]
+    pub response_status: u16,
+
+    /// HTTP 默认响应头，格式为 "Header-Name: Value"，可重复指定
+    #[arg(long, value_parser = parse_header)]
+    pub response_header: Vec<(String, String)>,
+
+    /// HTTP 默认响应体
+    #[arg(long, default_value = "")]
+    pub response_body: String,
+}
+
+impl HttpResponseArgs {
+    /// 根据命令行参数生成默认响应
+    pub fn default_response(&self) -> DefaultResponse {
+        DefaultResponse {
+            status: self.response_status,
+            headers: self.response_header.clone(),
+            body: self.response_body.clone().into(),
+        }
+    }
+}
+
+/// 解析 "Header-Name: Value" 格式的请求头/响应头
+fn parse_header(header: &str) -> Result<(String, String), String> {
+    let (name, value) = header
+        .split_once(':')
+        .ok_or_else(|| format!("expected \"Header-Name: Value\", got \"{}\"", header))?;
+    let name = name.trim();
+    let value = value.trim();
+    http::HeaderName::from_bytes(name.as_bytes()).map_err(|e| format!("invalid header name \"{}\": {}", name, e))?;
+    http::HeaderValue::from_str(value).map_err(|e| format!("invalid header value \"{}\": {}", value, e))?;
+    Ok((name.to_string(), value.to_string()))
+}
+
+/// HTTP 客户端参数
+#[derive(Args, Debug, Clone)]
+pub struct HttpClientArgs {
+    /// HTTP 请求方法 (GET, POST, PUT 等)
+    pub method: String,
+    
+    /// 目标 URL
+    pub url: String,
+    
+    /// 请求体，适用于 POST/PUT 等方法
+    #[arg(short, long)]
+    pub body: Option<String>,
+    
+    /// 请求头，格式为 "Header-Name: Value"
+    #[arg(short = 'H', long, value_parser = parse_header)]
+    pub headers: Vec<(String, String)>,
+
+    /// 额外信任的 CA 证书文件 (PEM)，用于连接使用自签名证书的测试服务器
+    #[arg(long)]
+    pub cacert: Option<PathBuf>,
+
+    /// 跳过服务器证书校验 (仅用于测试)
+    #[arg(short = 'k', long)]
+    pub insecure: bool,
+}
+
+/// HTTP 服务器默认响应
+#[derive(Debug, Clone)]
+pub struct DefaultResponse {
+    /// 状态码
+    pub status: u16,
+    /// 响应头
+    pub headers: Vec<(String, String)>,
+    /// 响应体
+    pub body: Bytes,
+}
+
+impl Default for DefaultResponse {
+    fn default() -> Self {
+        Self {
+            status: 200,
+            headers: Vec::new(),
+            body: Bytes::new(),
+        }
+    }
+}
+
+impl DefaultResponse {
+    /// 生成 hyper 响应
+    pub fn to_response(&self) -> Response<Full<Bytes>> {
+        self.head().map(|()| Full::new(self.body.clone()))
+    }
+
+    /// 生成不含响应体的响应头部 (状态码和响应头)
+    pub fn head(&self) -> Response<()> {
+        let mut response = Response::new(());
+        *response.status_mut() = StatusCode::from_u16(self.status).unwrap_or(StatusCode::OK);
+        for (name, value) in &self.headers {
+            // 响应头在解析命令行参数时已校验
+            if let (Ok(name), Ok(value)) = (
+                http::HeaderName::from_bytes(name.as_bytes()),
+                http::HeaderValue::from_str(value),
+            ) {
+                response.headers_mut().append(name, value);
+            }
+        }
+        response
+    }
+
+    /// 生成响应的显示文本
+    pub fn describe(&self, version: http::Version) -> String {
+        let status = StatusCode::from_u16(self.status).unwrap_or(StatusCode::OK);
+        let mut text = format!("{:?} {}", version, status);
+        for (name, value) in &self.headers {
+            text.push_str(&format!("\n{}: {}", name, value));
+        }
+        if !self.body.is_empty() {
+            text.push_str(&format!("\n\n{}", String::from_utf8_lossy(&self.body)));
+        }
+        text
+    }
+}
 
 /// HTTP 服务器处理器
 pub struct HttpServerHandler {
     /// 本地地址
     local_addr: SocketAddr,
+    /// 默认响应
+    default_response: Arc<RwLock<DefaultResponse>>,
     /// 客户端请求记录
     requests: Arc<RwLock<Vec<HttpRequest>>>,
     /// 控制通道 (用于停止服务器)
     control_tx: Option<Sender<()>>,
-    /// 消息接收通道
-    message_rx: Option<Receiver<Message>>,
     /// 消息发送通道
-    message_tx: Option<Sender<Message>>,
+    ui_to_server_tx: Option<Sender<Message>>,
     /// UI消息发送通道
-    ui_tx: Option<Sender<Message>>,
+    server_to_ui_tx: Option<Sender<Message>>,
     /// 运行状态
     running: bool,
 }
 
 /// HTTP 请求记录
-struct HttpRequest {
+pub(crate) struct HttpRequest {
     /// 客户端地址
-    client_addr: SocketAddr,
+    pub(crate) client_addr: SocketAddr,
     /// 请求方法
-    method: String,
+    pub(crate) method: String,
     /// 请求路径
-    path: String,
-    /// 请求头
-    headers: HashMap<String, String>,
+    pub(crate) path: String,
+    /// 协议版本
+    pub(crate) version: String,
+    /// 请求头 (保留顺序和重复项)
+    pub(crate) headers: Vec<(String, String)>,
     /// 请求体
-    body: Option<Vec<u8>>,
+    pub(crate) body: Option<Vec<u8>>,
     /// 时间戳
-    timestamp: chrono::DateTime<chrono::Local>,
+    pub(crate) timestamp: chrono::DateTime<chrono::Local>,
+}
+
+impl HttpRequest {
+    /// 生成请求的显示文本
+    fn describe(&self) -> String {
+        let mut text = format!(
+            "{} {} {} from {} at {}",
+            self.method,
+            self.path,
+            self.version,
+            self.client_addr,
+            self.timestamp.format("%H:%M:%S%.3f")
+        );
+        for (name, value) in &self.headers {
+            text.push_str(&format!("\n{}: {}", name, value));
+        }
+        if let Some(ref body) = self.body {
+            text.push_str(&format!("\n\n{}", String::from_utf8_lossy(body)));
+        }
+        text
+    }
+}
+
+/// 记录请求并在UI中显示 (按客户端 IP 分组)，返回应答使用的默认响应
+pub(crate) async fn record_request(
+    record: HttpRequest,
+    version: http::Version,
+    requests: &RwLock<Vec<HttpRequest>>,
+    default_response: &RwLock<DefaultResponse>,
+    server_to_ui_tx: &Option<Sender<Message>>,
+) -> DefaultResponse {
+    let description = record.describe();
+    let client_addr = record.client_addr;
+
+    // 按客户端 IP 分组，同一 IP 的首个请求创建连接标签页
+    let connection_info = ConnectionInfo {
+        remote_addr: client_addr,
+        connection_id: client_addr.ip().to_string(),
+    };
+    let first_seen = {
+        let mut requests = requests.write().await;
+        let first_seen = !requests.iter().any(|r| r.client_addr.ip() == client_addr.ip());
+        requests.push(record);
+        first_seen
+    };
+
+    let default_response = default_response.read().await.clone();
+
+    if let Some(ref server_to_ui_sender) = server_to_ui_tx {
+        if first_seen {
+            let _ = server_to_ui_sender
+                .send(Message::new_received(MessageType::ClientConnected, Some(connection_info.clone())))
+                .await;
+        }
+        let _ = server_to_ui_sender
+            .send(Message::new_received(MessageType::Text(description), Some(connection_info.clone())))
+            .await;
+
+        // 回复内容显示在发送视图
+        let reply = default_response.describe(version);
+        let _ = server_to_ui_sender
+            .send(Message::new_sent(MessageType::Text(reply), Some(connection_info)))
+            .await;
+    }
+
+    default_response
+}
+
+/// 按客户端 IP 汇总请求记录，每个 IP 对应一个连接，地址取最近一次请求的地址
+pub(crate) fn connections_by_ip(requests: &[HttpRequest]) -> Vec<ConnectionInfo> {
+    let mut connections: Vec<ConnectionInfo> = Vec::new();
+    for request in requests {
+        let connection_id = request.client_addr.ip().to_string();
+        match connections.iter_mut().find(|c| c.connection_id == connection_id) {
+            Some(connection) => connection.remote_addr = request.client_addr,
+            None => connections.push(ConnectionInfo {
+                remote_addr: request.client_addr,
+                connection_id,
+            }),
+        }
+    }
+    connections
 }
 
 impl HttpServerHandler {
     /// 创建新的HTTP服务器处理器
-    pub fn new(local_addr: SocketAddr) -> Self {
+    pub fn new(local_addr: SocketAddr, default_response: DefaultResponse) -> Self {
         Self {
             local_addr,
+            default_response: Arc::new(RwLock::new(default_response)),
             requests: Arc::new(RwLock::new(Vec::new())),
             control_tx: None,
-            message_rx: None,
-            message_tx: None,
-            ui_tx: None,
+            ui_to_server_tx: None,
+            server_to_ui_tx: None,
             running: false,
         }
     }
+
+    /// 处理单个请求：记录请求并返回默认响应
+    async fn handle_request(
+        request: Request<Incoming>,
+        client_addr: SocketAddr,
+        requests: Arc<RwLock<Vec<HttpRequest>>>,
+        default_response: Arc<RwLock<DefaultResponse>>,
+        server_to_ui_tx: Option<Sender<Message>>,
+    ) -> std::result::Result<Response<Full<Bytes>>, Infallible> {
+        let (parts, body) = request.into_parts();
+        let body = body.collect().await.map(|collected| collected.to_bytes()).unwrap_or_default();
+
+        let record = HttpRequest {
+            client_addr,
+            method: parts.method.to_string(),
+            path: parts.uri.path_and_query().map_or_else(|| parts.uri.to_string(), |p| p.to_string()),
+            version: format!("{:?}", parts.version),
+            headers: parts
+                .headers
+                .iter()
+                .map(|(name, value)| (name.to_string(), String::from_utf8_lossy(value.as_bytes()).into_owned()))
+                .collect(),
+            body: (!body.is_empty()).then(|| body.to_vec()),
+            timestamp: chrono::Local::now(),
+        };
+        let default_response = record_request(record, parts.version, &requests, &default_response, &server_to_ui_tx).await;
+
+        Ok(default_response.to_response())
+    }
 }
 
 #[async_trait]
 impl ProtocolHandler for HttpServerHandler {
     async fn start(&mut self) -> Result<()> {
-        todo!("Implement HTTP server start")
+        // 绑定监听地址
+        let listener = TcpListener::bind(self.local_addr).await?;
+
+        // 创建消息通道
+        let (ui_to_server_tx, mut ui_to_server_rx) = channel::<Message>(100);
+        let (control_tx, mut control_rx) = channel::<()>(1);
+
+        self.ui_to_server_tx = Some(ui_to_server_tx);
+        self.control_tx = Some(control_tx);
+        self.running = true;
+
+        let requests = Arc::clone(&self.requests);
+        let default_response = Arc::clone(&self.default_response);
+        let server_to_ui_tx = self.server_to_ui_tx.clone();
+
+        // 启动服务器任务
+        tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    // 接受新连接
+                    accepted = listener.accept() => {
+                        let Ok((stream, client_addr)) = accepted else {
+                            continue;
+                        };
+                        let requests = Arc::clone(&requests);
+                        let default_response = Arc::clone(&default_response);
+                        let server_to_ui_tx = server_to_ui_tx.clone();
+
+                        tokio::spawn(async move {
+                            let service = service_fn(move |request| {
+                                Self::handle_request(
+                                    request,
+                                    client_addr,
+                                    Arc::clone(&requests),
+                                    Arc::clone(&default_response),
+                                    server_to_ui_tx.clone(),
+                                )
+                            });
+                            let _ = http1::Builder::new()
+                                .serve_connection(TokioIo::new(stream), service)
+                                .await;
+                        });
+                    }
+
+                    // UI输入的内容作为后续请求的默认响应体
+                    message = ui_to_server_rx.recv() => {
+                        let Some(message) = message else {
+                            break;
+                        };
+                        if let Some(body) = message.content.to_bytes() {
+                            let length = body.len();
+                            message.mark_written();
+                            default_response.write().await.body = body;
+                            if let Some(ref server_to_ui_sender) = server_to_ui_tx {
+                                let _ = server_to_ui_sender
+                                    .send(Message::new_event(format!("Default response body set ({} bytes)", length), None))
+                                    .await;
+                            }
+                        }
+                    }
+
+                    // 处理停止信号
+                    _ = control_rx.recv() => break,
+                }
+            }
+        });
+
+        Ok(())
     }
-    
+
     async fn stop(&mut self) -> Result<()> {
-        todo!("Implement HTTP server stop")
-    }
-    
-    async fn send_message(&mut self, message: MessageType, target: Option<String>) -> Result<()> {
-        todo!("Implement HTTP server send message")
-    }
-    
-    fn get_receiver(&self) -> Option<Receiver<Message>> {
-        self.message_rx.clone()
+        if self.running {
+            // 发送停止信号
+            if let Some(ref control_tx) = self.control_tx {
+                let _ = control_tx.send(()).await;
+            }
+            self.running = false;
+            // 清理资源
+            self.control_tx = None;
+            self.ui_to_server_tx = None;
+        }
+        Ok(())
     }
-    
-    fn set_ui_sender(&mut self, sender: Sender<Message>) {
-        self.ui_tx = Some(sender);
+
+    fn get_ui_to_server_sender(&self) -> Option<Sender<Message>> {
+        self.ui_to_server_tx.clone()
     }
-    
-    fn is_running(&self) -> bool {
-        self.running
+
+    fn set_server_to_ui_sender(&mut self, sender: Sender<Message>) {
+        self.server_to_ui_tx = Some(sender);
     }
-    
+
     fn get_connections(&self) -> Vec<ConnectionInfo> {
-        todo!("Implement get_connections for HTTP server")
+        self.requests
+            .try_read()
+            .map(|requests| connections_by_ip(&requests))
+            .unwrap_or_default()
     }
-    
+
     fn protocol_name(&self) -> &'static str {
         "HTTP Server"
     }
 }
 
+/// 省略协议时默认为 http://
+pub(crate) fn normalize_http_url(url: &str) -> String {
+    if url.contains("://") {
+        url.to_string()
+    } else {
+        format!("http://{}", url)
+    }
+}
+
+/// 记录 DNS 解析耗时的解析器
+#[derive(Clone)]
+struct TimedResolver {
+    /// 系统解析器
+    inner: GaiResolver,
+    /// 当前请求的耗时统计
+    timing: Arc<Mutex<RequestTiming>>,
+}
+
+impl Service<Name> for TimedResolver {
+    type Response = GaiAddrs;
+    type Error = io::Error;
+    type Future = Pin<Box<dyn Future<Output = io::Result<GaiAddrs>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, name: Name) -> Self::Future {
+        let started = Instant::now();
+        let timing = Arc::clone(&self.timing);
+        let resolving = self.inner.call(name);
+        Box::pin(async move {
+            let result = resolving.await;
+            timing.lock().unwrap().dns = Some(started.elapsed());
+            result
+        })
+    }
+}
+
+/// 记录建立连接耗时的连接器 (包含 TLS 握手，不包含 DNS 解析)
+#[derive(Clone)]
+struct TimedConnector<S> {
+    /// 实际连接器
+    inner: S,
+    /// 当前请求的耗时统计
+    timing: Arc<Mutex<RequestTiming>>,
+}
+
+impl<S> Service<Uri> for TimedConnector<S>
+where
+    S: Service<Uri>,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = std::result::Result<S::Response, S::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<std::result::Result<(), S::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, uri: Uri) -> Self::Future {
+        let started = Instant::now();
+        let timing = Arc::clone(&self.timing);
+        let connecting = self.inner.call(uri);
+        Box::pin(async move {
+            let result = connecting.await;
+            let mut timing = timing.lock().unwrap();
+            timing.connect = Some(started.elapsed().saturating_sub(timing.dns.unwrap_or_default()));
+            result
+        })
+    }
+}
+
+/// 支持 http/https 并记录耗时的 HTTP 客户端
+type TimedClient = Client<TimedConnector<HttpsConnector<HttpConnector<TimedResolver>>>, Full<Bytes>>;
+
 /// HTTP 客户端处理器
 pub struct HttpClientHandler {
-    /// 本地地址
-    local_addr: SocketAddr,
-    /// HTTP 客户端
-    client: Option<Client<hyper::client::connect::HttpConnector>>,
+    /// HTTP请求参数
+    http_args: HttpClientArgs,
     /// 控制通道
     control_tx: Option<Sender<()>>,
-    /// 消息接收通道
-    message_rx: Option<Receiver<Message>>,
     /// 消息发送通道
-    message_tx: Option<Sender<Message>>,
+    ui_to_server_tx: Option<Sender<Message>>,
     /// UI消息发送通道
-    ui_tx: Option<Sender<Message>>,
+    server_to_ui_tx: Option<Sender<Message>>,
     /// 运行状态
     running: bool,
-    /// HTTP请求参数
-    http_args: Option<crate::cli::args::HttpClientArgs>,
 }
 
 impl HttpClientHandler {
     /// 创建新的HTTP客户端处理器
-    pub fn new(local_addr: SocketAddr, http_args: Option<crate::cli::args::HttpClientArgs>) -> Self {
+    pub fn new(mut http_args: HttpClientArgs) -> Self {
+        http_args.url = normalize_http_url(&http_args.url);
         Self {
-            local_addr,
-            client: None,
+            http_args,
             control_tx: None,
-            message_rx: None,
-            message_tx: None,
-            ui_tx: None,
+            ui_to_server_tx: None,
+            server_to_ui_tx: None,
             running: false,
-            http_args,
         }
     }
+
+    /// 创建 HTTP 客户端
+    fn build_client(http_args: &HttpClientArgs, timing: &Arc<Mutex<RequestTiming>>) -> Result<TimedClient> {
+        let resolver = TimedResolver {
+            inner: GaiResolver::new(),
+            timing: Arc::clone(timing),
+        };
+        let mut http = HttpConnector::new_with_resolver(resolver);
+        http.enforce_http(false);
+        let https = HttpsConnectorBuilder::new()
+            .with_tls_config(TlsConfig::client_config(http_args.cacert.as_deref(), http_args.insecure)?)
+            .https_or_http()
+            .enable_http1()
+            .wrap_connector(http);
+        let connector = TimedConnector {
+            inner: https,
+            timing: Arc::clone(timing),
+        };
+        Ok(Client::builder(TokioExecutor::new()).build(connector))
+    }
+
+    /// 根据命令行参数构造请求
+    fn build_request(http_args: &HttpClientArgs, body: Bytes) -> Result<Request<Full<Bytes>>> {
+        let method = Method::from_bytes(http_args.method.to_uppercase().as_bytes())?;
+        let mut builder = Request::builder().method(method).uri(http_args.url.as_str());
+        for (name, value) in &http_args.headers {
+            builder = builder.header(name.as_str(), value.as_str());
+        }
+        Ok(builder.body(Full::new(body))?)
+    }
+
+    /// 生成请求的显示文本
+    fn describe_request(request: &Request<Full<Bytes>>, body: &[u8]) -> String {
+        let mut text = format!("{} {} {:?}", request.method(), request.uri(), request.version());
+        for (name, value) in request.headers() {
+            text.push_str(&format!("\n{}: {}", name, String::from_utf8_lossy(value.as_bytes())));
+        }
+        if !body.is_empty() {
+            text.push_str(&format!("\n\n{}", String::from_utf8_lossy(body)));
+        }
+        text
+    }
+
+    /// 发送请求并读取完整响应，返回响应显示文本和耗时
+    async fn perform(
+        client: &TimedClient,
+        request: Request<Full<Bytes>>,
+        timing: &Arc<Mutex<RequestTiming>>,
+    ) -> Result<(String, RequestTiming)> {
+        // 复用连接时 DNS 和连接耗时保持为空
+        *timing.lock().unwrap() = RequestTiming::default();
+        let started = Instant::now();
+
+        let response = client.request(request).await?;
+        let ttfb = started.elapsed();
+        let (parts, body) = response.into_parts();
+        let body = body.collect().await?.to_bytes();
+        let total = started.elapsed();
+
+        let mut text = format!("{:?} {}", parts.version, parts.status);
+        if let Some(info) = parts.extensions.get::<HttpInfo>() {
+            text.push_str(&format!(" from {}", info.remote_addr()));
+        }
+        for (name, value) in &parts.headers {
+            text.push_str(&format!("\n{}: {}", name, String::from_utf8_lossy(value.as_bytes())));
+        }
+        if !body.is_empty() {
+            text.push_str(&format!("\n\n{}", String::from_utf8_lossy(&body)));
+        }
+
+        let mut result = *timing.lock().unwrap();
+        result.ttfb = ttfb;
+        result.total = total;
+        Ok((text, result))
+    }
 }
 
 #[async_trait]
 impl ProtocolHandler for HttpClientHandler {
     async fn start(&mut self) -> Result<()> {
-        todo!("Implement HTTP client start")
+        // 提前校验请求参数
+        Self::build_request(&self.http_args, Bytes::new())?;
+
+        let timing = Arc::new(Mutex::new(RequestTiming::default()));
+        let client = Self::build_client(&self.http_args, &timing)?;
+
+        // 创建消息通道
+        let (ui_to_server_tx, mut ui_to_server_rx) = channel::<Message>(100);
+        let (control_tx, mut control_rx) = channel::<()>(1);
+
+        self.ui_to_server_tx = Some(ui_to_server_tx);
+        self.control_tx = Some(control_tx);
+        self.running = true;
+
+        let http_args = self.http_args.clone();
+        let server_to_ui_tx = self.server_to_ui_tx.clone();
+
+        // 启动请求任务：先按命令行参数发送一次请求，之后每次UI发送都重新请求
+        tokio::spawn(async move {
+            let default_body = Bytes::from(http_args.body.clone().unwrap_or_default());
+            let mut body = default_body.clone();
+            loop {
+                if let Some(ref server_to_ui_sender) = server_to_ui_tx {
+                    let request = match Self::build_request(&http_args, body.clone()) {
+                        Ok(request) => request,
+                        Err(e) => {
+                            let _ = server_to_ui_sender
+                                .send(Message::new_event(format!("Invalid request: {:#}", e), None))
+                                .await;
+                            break;
+                        }
+                    };
+                    let _ = server_to_ui_sender
+                        .send(Message::new_request(Self::describe_request(&request, &body), None))
+                        .await;
+
+                    tokio::select! {
+                        result = Self::perform(&client, request, &timing) => {
+                            match result {
+                                Ok((response, timing)) => {
+                                    let _ = server_to_ui_sender
+                                        .send(Message::new_received(MessageType::Text(response), None))
+                                        .await;
+                                    let _ = server_to_ui_sender
+                                        .send(Message::new_received(MessageType::Timing(timing), None))
+                                        .await;
+                                }
+                                Err(e) => {
+                                    let _ = server_to_ui_sender
+                                        .send(Message::new_event(format!("Request failed: {:#}", e), None))
+                                        .await;
+                                }
+                            }
+                        }
+                        _ = control_rx.recv() => break,
+                    }
+                }
+
+                // 等待下一次发送，输入内容作为请求体 (为空时使用命令行参数中的请求体)
+                tokio::select! {
+                    message = ui_to_server_rx.recv() => {
+                        let Some(message) = message else {
+                            break;
+                        };
+                        body = message
+                            .content
+                            .to_bytes()
+                            .filter(|data| !data.is_empty())
+                            .unwrap_or_else(|| default_body.clone());
+                        message.mark_written();
+                    }
+                    _ = control_rx.recv() => break,
+                }
+            }
+        });
+
+        Ok(())
     }
-    
+
     async fn stop(&mut self) -> Result<()> {
-        todo!("Implement HTTP client stop")
-    }
-    
-    async fn send_message(&mut self, message: MessageType, target: Option<String>) -> Result<()> {
-        todo!("Implement HTTP client send message")
-    }
-    
-    fn get_receiver(&self) -> Option<Receiver<Message>> {
-        self.message_rx.clone()
+        if self.running {
+            // 发送停止信号
+            if let Some(ref control_tx) = self.control_tx {
+                let _ = control_tx.send(()).await;
+            }
+            self.running = false;
+            // 清理资源
+            self.control_tx = None;
+            self.ui_to_server_tx = None;
+        }
+        Ok(())
     }
-    
-    fn set_ui_sender(&mut self, sender: Sender<Message>) {
-        self.ui_tx = Some(sender);
+
+    fn get_ui_to_server_sender(&self) -> Option<Sender<Message>> {
+        self.ui_to_server_tx.clone()
     }
-    
-    fn is_running(&self) -> bool {
-        self.running
+
+    fn set_server_to_ui_sender(&mut self, sender: Sender<Message>) {
+        self.server_to_ui_tx = Some(sender);
     }
-    
+
     fn get_connections(&self) -> Vec<ConnectionInfo> {
-        todo!("Implement get_connections for HTTP client")
+        // HTTP 客户端按请求建立连接，不维护连接列表
+        Vec::new()
     }
-    
+
     fn protocol_name(&self) -> &'static str {
         "HTTP Client"
     }
-}
\ No newline at end of file
+}
+
+/// 注册 HTTP/1.1 服务器和客户端处理器
+pub fn register(registry: &mut HandlerRegistry) {
+    registry.register_protocol(ProtocolType::Http, "http", &[]);
+    registry.register(ProtocolType::Http, AppMode::Server, "HTTP Server", HttpServerArgs::augment_args, |matches| {
+        let server_args = HttpServerArgs::from_arg_matches(matches)?;
+        Ok(Box::new(HttpServerHandler::new(server_args.listen.local_addr(), server_args.response.default_response())))
+    });
+    registry.register(ProtocolType::Http, AppMode::Client, "HTTP Client", HttpClientArgs::augment_args, |matches| {
+        Ok(Box::new(HttpClientHandler::new(HttpClientArgs::from_arg_matches(matches)?)))
+    });
+}
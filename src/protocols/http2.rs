@@ -1,166 +1,921 @@
 use anyhow::Result;
 use async_trait::async_trait;
-use bytes::Bytes;
-use h2::{server, client};
-use hyper::{Method, Request, Response, StatusCode};
-use std::{collections::HashMap, net::SocketAddr, sync::Arc};
+use bytes::{Bytes, BytesMut};
+use clap::{Args, FromArgMatches};
+use h2::{
+    client::{ResponseFuture, SendRequest},
+    server::SendResponse,
+    RecvStream,
+};
+use hyper::{Method, Request, Uri, Version};
+use rustls::{pki_types::ServerName, ClientConfig, ServerConfig};
+use std::{
+    collections::HashMap,
+    net::SocketAddr,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Instant,
+};
 use tokio::{
-    net::TcpListener,
-    sync::{mpsc::{Receiver, Sender, channel}, RwLock},
+    io::{AsyncRead, AsyncWrite},
+    net::{lookup_host, TcpListener, TcpStream},
+    sync::{mpsc::{Sender, channel}, RwLock},
 };
+use tokio_rustls::{TlsAcceptor, TlsConnector};
 
+use crate::cli::args::{AppMode, ListenArgs, ProtocolType};
+use crate::config::tls::TlsConfig;
 use crate::protocols::common::{
-    ConnectionInfo, Message, MessageDirection, MessageType, ProtocolHandler,
+    ConnectionInfo, Message, MessageType, ProtocolHandler, RequestTiming,
 };
+use crate::protocols::http::{normalize_http_url, DefaultResponse, HttpClientArgs, HttpResponseArgs};
+use crate::protocols::reconnect::notify;
+use crate::protocols::registry::HandlerRegistry;
+
+/// TLS 记录层握手消息的首字节，用于区分 TLS 连接和 h2c 连接
+const TLS_HANDSHAKE_RECORD: u8 = 0x16;
+
+/// HTTP/2 服务器参数
+#[derive(Args, Debug, Clone)]
+pub struct Http2ServerArgs {
+    #[command(flatten)]
+    pub listen: ListenArgs,
+
+    /// 默认响应参数
+    #[command(flatten)]
+    pub response: HttpResponseArgs,
+
+    /// TLS 证书文件 (PEM)，指定后同时支持 TLS+ALPN 和 h2c，否则仅支持 h2c
+    #[arg(long, requires = "tls_key")]
+    pub tls_cert: Option<PathBuf>,
+
+    /// TLS 私钥文件 (PEM)
+    #[arg(long, requires = "tls_cert")]
+    pub tls_key: Option<PathBuf>,
+}
+
+impl Http2ServerArgs {
+    /// TLS 证书和私钥文件
+    pub fn tls_files(&self) -> Option<(&Path, &Path)> {
+        self.tls_cert.as_deref().zip(self.tls_key.as_deref())
+    }
+}
 
 /// HTTP/2 服务器处理器
 pub struct Http2ServerHandler {
     /// 本地地址
     local_addr: SocketAddr,
-    /// 客户端请求记录
-    requests: Arc<RwLock<Vec<Http2Request>>>,
+    /// TLS 配置 (None 表示仅支持 h2c)
+    tls_config: Option<Arc<ServerConfig>>,
+    /// 连接任务共享的状态
+    context: ServerContext,
     /// 控制通道 (用于停止服务器)
     control_tx: Option<Sender<()>>,
-    /// 消息接收通道
-    message_rx: Option<Receiver<Message>>,
     /// 消息发送通道
-    message_tx: Option<Sender<Message>>,
-    /// UI消息发送通道
-    ui_tx: Option<Sender<Message>>,
+    ui_to_server_tx: Option<Sender<Message>>,
     /// 运行状态
     running: bool,
 }
 
-/// HTTP/2 请求记录
+/// HTTP/2 连接任务共享的服务器状态
+#[derive(Clone)]
+struct ServerContext {
+    /// 默认响应
+    default_response: Arc<RwLock<DefaultResponse>>,
+    /// 客户端请求记录
+    requests: Arc<RwLock<Vec<Http2Request>>>,
+    /// 当前活动的连接
+    connections: Arc<RwLock<HashMap<String, ConnectionInfo>>>,
+    /// UI消息发送通道
+    server_to_ui_tx: Option<Sender<Message>>,
+}
+
+/// HTTP/2 请求记录 (对应一个流)
 struct Http2Request {
-    /// 客户端地址
-    client_addr: SocketAddr,
-    /// 请求方法
-    method: String,
-    /// 请求路径
-    path: String,
+    /// 流 ID
+    stream_id: u32,
+    /// 伪首部 (:method、:scheme、:authority、:path)
+    pseudo_headers: Vec<(&'static str, String)>,
     /// 请求头
-    headers: HashMap<String, String>,
+    headers: Vec<(String, String)>,
     /// 请求体
     body: Option<Vec<u8>>,
-    /// 时间戳
+    /// 尾部首部
+    trailers: Vec<(String, String)>,
+    /// 流开始时间
     timestamp: chrono::DateTime<chrono::Local>,
+    /// 从收到请求头到读完请求的耗时
+    elapsed: std::time::Duration,
+}
+
+impl Http2Request {
+    /// 生成请求的显示文本，整条流作为一个条目显示以免并发流交错
+    fn describe(&self) -> String {
+        let method = self.pseudo_header(":method");
+        let path = self.pseudo_header(":path");
+        let mut text = format!(
+            "[stream {}] {} {} at {} ({:.1} ms)",
+            self.stream_id,
+            method,
+            path,
+            self.timestamp.format("%H:%M:%S%.3f"),
+            self.elapsed.as_secs_f64() * 1000.0
+        );
+        for (name, value) in &self.pseudo_headers {
+            text.push_str(&format!("\n{}: {}", name, value));
+        }
+        for (name, value) in &self.headers {
+            text.push_str(&format!("\n{}: {}", name, value));
+        }
+        if let Some(ref body) = self.body {
+            text.push_str(&format!("\n\n{}", String::from_utf8_lossy(body)));
+        }
+        if !self.trailers.is_empty() {
+            text.push_str("\n\nTrailers:");
+            for (name, value) in &self.trailers {
+                text.push_str(&format!("\n{}: {}", name, value));
+            }
+        }
+        text
+    }
+
+    /// 获取伪首部的值
+    fn pseudo_header(&self, name: &str) -> &str {
+        self.pseudo_headers
+            .iter()
+            .find(|(n, _)| *n == name)
+            .map_or("", |(_, value)| value.as_str())
+    }
+}
+
+/// 将首部列表转换为可显示的名称/值对
+fn header_pairs(headers: &http::HeaderMap) -> Vec<(String, String)> {
+    headers
+        .iter()
+        .map(|(name, value)| (name.to_string(), String::from_utf8_lossy(value.as_bytes()).into_owned()))
+        .collect()
 }
 
 impl Http2ServerHandler {
     /// 创建新的HTTP/2服务器处理器
-    pub fn new(local_addr: SocketAddr) -> Self {
+    pub fn new(local_addr: SocketAddr, default_response: DefaultResponse, tls_config: Option<ServerConfig>) -> Self {
         Self {
             local_addr,
-            requests: Arc::new(RwLock::new(Vec::new())),
+            tls_config: tls_config.map(Arc::new),
+            context: ServerContext {
+                default_response: Arc::new(RwLock::new(default_response)),
+                requests: Arc::new(RwLock::new(Vec::new())),
+                connections: Arc::new(RwLock::new(HashMap::new())),
+                server_to_ui_tx: None,
+            },
             control_tx: None,
-            message_rx: None,
-            message_tx: None,
-            ui_tx: None,
+            ui_to_server_tx: None,
             running: false,
         }
     }
+
+    /// 处理新连接：配置了 TLS 时根据首字节区分 TLS 和 h2c
+    async fn handle_connection(
+        stream: TcpStream,
+        client_addr: SocketAddr,
+        tls_acceptor: Option<TlsAcceptor>,
+        context: ServerContext,
+    ) -> Result<()> {
+        let connection_info = ConnectionInfo {
+            remote_addr: client_addr,
+            connection_id: client_addr.to_string(),
+        };
+
+        let mut first_byte = [0u8; 1];
+        match tls_acceptor {
+            Some(acceptor) if stream.peek(&mut first_byte).await? == 1 && first_byte[0] == TLS_HANDSHAKE_RECORD => {
+                let stream = acceptor.accept(stream).await?;
+                if stream.get_ref().1.alpn_protocol() != Some(b"h2") {
+                    anyhow::bail!("client did not negotiate h2 via ALPN");
+                }
+                Self::serve(stream, "TLS, ALPN h2", connection_info, context).await
+            }
+            _ => Self::serve(stream, "h2c prior knowledge", connection_info, context).await,
+        }
+    }
+
+    /// 在已建立的传输层上运行 HTTP/2 连接
+    async fn serve<T>(io: T, transport: &str, connection_info: ConnectionInfo, context: ServerContext) -> Result<()>
+    where
+        T: AsyncRead + AsyncWrite + Unpin,
+    {
+        let mut connection = h2::server::handshake(io).await?;
+
+        // 记录连接并通知UI
+        context
+            .connections
+            .write()
+            .await
+            .insert(connection_info.connection_id.clone(), connection_info.clone());
+        if let Some(ref server_to_ui_sender) = context.server_to_ui_tx {
+            let _ = server_to_ui_sender
+                .send(Message::new_received(MessageType::ClientConnected, Some(connection_info.clone())))
+                .await;
+        }
+        let text = format!("HTTP/2 connection established ({})", transport);
+        notify(&context.server_to_ui_tx, &connection_info, text).await;
+
+        // 每个流在独立任务中处理，以支持并发流
+        let result = loop {
+            match connection.accept().await {
+                Some(Ok((request, respond))) => {
+                    let connection_info = connection_info.clone();
+                    let context = context.clone();
+                    tokio::spawn(async move {
+                        let stream_id = u32::from(respond.stream_id());
+                        if let Err(e) = Self::handle_stream(request, respond, &connection_info, &context).await {
+                            let text = format!("[stream {}] error: {}", stream_id, e);
+                            notify(&context.server_to_ui_tx, &connection_info, text).await;
+                        }
+                    });
+                }
+                Some(Err(e)) => break Err(e.into()),
+                None => break Ok(()),
+            }
+        };
+
+        // 清理连接并通知UI
+        context.connections.write().await.remove(&connection_info.connection_id);
+        if let Some(ref server_to_ui_sender) = context.server_to_ui_tx {
+            let _ = server_to_ui_sender
+                .send(Message::new_received(MessageType::ClientDisconnected, Some(connection_info)))
+                .await;
+        }
+        result
+    }
+
+    /// 处理单个流：读取完整请求 (含尾部首部) 后返回默认响应
+    async fn handle_stream(
+        request: Request<RecvStream>,
+        mut respond: SendResponse<Bytes>,
+        connection_info: &ConnectionInfo,
+        context: &ServerContext,
+    ) -> Result<()> {
+        let stream_id = u32::from(respond.stream_id());
+        let timestamp = chrono::Local::now();
+        let started = Instant::now();
+
+        let (parts, mut body) = request.into_parts();
+        let mut data = BytesMut::new();
+        while let Some(chunk) = body.data().await {
+            let chunk = chunk?;
+            let _ = body.flow_control().release_capacity(chunk.len());
+            data.extend_from_slice(&chunk);
+        }
+        let trailers = body.trailers().await?;
+
+        let mut pseudo_headers = vec![(":method", parts.method.to_string())];
+        if let Some(scheme) = parts.uri.scheme_str() {
+            pseudo_headers.push((":scheme", scheme.to_string()));
+        }
+        if let Some(authority) = parts.uri.authority() {
+            pseudo_headers.push((":authority", authority.to_string()));
+        }
+        pseudo_headers.push((":path", parts.uri.path_and_query().map_or("/", |p| p.as_str()).to_string()));
+
+        let record = Http2Request {
+            stream_id,
+            pseudo_headers,
+            headers: header_pairs(&parts.headers),
+            body: (!data.is_empty()).then(|| data.to_vec()),
+            trailers: trailers.as_ref().map(header_pairs).unwrap_or_default(),
+            timestamp,
+            elapsed: started.elapsed(),
+        };
+        let description = record.describe();
+        context.requests.write().await.push(record);
+
+        // 发送默认响应
+        let default_response = context.default_response.read().await.clone();
+        let end_of_stream = default_response.body.is_empty();
+        let mut send = respond.send_response(default_response.head(), end_of_stream)?;
+        if !end_of_stream {
+            send.send_data(default_response.body.clone(), true)?;
+        }
+
+        if let Some(ref server_to_ui_sender) = context.server_to_ui_tx {
+            let _ = server_to_ui_sender
+                .send(Message::new_received(MessageType::Text(description), Some(connection_info.clone())))
+                .await;
+            let reply = format!("[stream {}] {}", stream_id, default_response.describe(Version::HTTP_2));
+            let _ = server_to_ui_sender
+                .send(Message::new_sent(MessageType::Text(reply), Some(connection_info.clone())))
+                .await;
+        }
+        Ok(())
+    }
 }
 
 #[async_trait]
 impl ProtocolHandler for Http2ServerHandler {
     async fn start(&mut self) -> Result<()> {
-        todo!("Implement HTTP/2 server start")
+        // 绑定监听地址
+        let listener = TcpListener::bind(self.local_addr).await?;
+
+        // 创建消息通道
+        let (ui_to_server_tx, mut ui_to_server_rx) = channel::<Message>(100);
+        let (control_tx, mut control_rx) = channel::<()>(1);
+
+        self.ui_to_server_tx = Some(ui_to_server_tx);
+        self.control_tx = Some(control_tx);
+        self.running = true;
+
+        let tls_acceptor = self.tls_config.clone().map(TlsAcceptor::from);
+        let context = self.context.clone();
+
+        // 启动服务器任务
+        tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    // 接受新连接
+                    accepted = listener.accept() => {
+                        let Ok((stream, client_addr)) = accepted else {
+                            continue;
+                        };
+                        let tls_acceptor = tls_acceptor.clone();
+                        let context = context.clone();
+
+                        tokio::spawn(async move {
+                            if let Err(e) = Self::handle_connection(stream, client_addr, tls_acceptor, context.clone()).await {
+                                let connection_info = ConnectionInfo {
+                                    remote_addr: client_addr,
+                                    connection_id: client_addr.to_string(),
+                                };
+                                let text = format!("HTTP/2 connection error: {}", e);
+                                notify(&context.server_to_ui_tx, &connection_info, text).await;
+                            }
+                        });
+                    }
+
+                    // UI输入的内容作为后续请求的默认响应体
+                    message = ui_to_server_rx.recv() => {
+                        let Some(message) = message else {
+                            break;
+                        };
+                        if let Some(body) = message.content.to_bytes() {
+                            let length = body.len();
+                            message.mark_written();
+                            context.default_response.write().await.body = body;
+                            if let Some(ref server_to_ui_sender) = context.server_to_ui_tx {
+                                let _ = server_to_ui_sender
+                                    .send(Message::new_event(format!("Default response body set ({} bytes)", length), None))
+                                    .await;
+                            }
+                        }
+                    }
+
+                    // 处理停止信号
+                    _ = control_rx.recv() => break,
+                }
+            }
+        });
+
+        Ok(())
     }
-    
+
     async fn stop(&mut self) -> Result<()> {
-        todo!("Implement HTTP/2 server stop")
-    }
-    
-    async fn send_message(&mut self, message: MessageType, target: Option<String>) -> Result<()> {
-        todo!("Implement HTTP/2 server send message")
-    }
-    
-    fn get_receiver(&self) -> Option<Receiver<Message>> {
-        self.message_rx.clone()
+        if self.running {
+            // 发送停止信号
+            if let Some(ref control_tx) = self.control_tx {
+                let _ = control_tx.send(()).await;
+            }
+            self.running = false;
+            // 清理资源
+            self.control_tx = None;
+            self.ui_to_server_tx = None;
+        }
+        Ok(())
     }
-    
-    fn set_ui_sender(&mut self, sender: Sender<Message>) {
-        self.ui_tx = Some(sender);
+
+    fn get_ui_to_server_sender(&self) -> Option<Sender<Message>> {
+        self.ui_to_server_tx.clone()
     }
-    
-    fn is_running(&self) -> bool {
-        self.running
+
+    fn set_server_to_ui_sender(&mut self, sender: Sender<Message>) {
+        self.context.server_to_ui_tx = Some(sender);
     }
-    
+
     fn get_connections(&self) -> Vec<ConnectionInfo> {
-        todo!("Implement get_connections for HTTP/2 server")
+        self.context
+            .connections
+            .try_read()
+            .map(|connections| connections.values().cloned().collect())
+            .unwrap_or_default()
     }
-    
+
     fn protocol_name(&self) -> &'static str {
         "HTTP/2 Server"
     }
 }
 
+/// 批量请求中的一个请求
+#[derive(Debug, PartialEq)]
+struct BatchItem {
+    /// 请求方法
+    method: Method,
+    /// 完整请求 URI
+    uri: Uri,
+    /// 请求体
+    body: Bytes,
+}
+
+/// 解析发送对话框中的批量请求
+///
+/// 多个请求以 `;` 分隔，每个请求格式为 `[METHOD] <path|url> [body]`，
+/// 省略的方法和请求体取命令行参数中的值；输入为空时重复命令行参数中的请求。
+/// 批量请求复用同一个连接，因此完整 URL 必须与命令行 URL 的协议和主机一致。
+fn parse_batch(input: &str, base: &Uri, default_method: &Method, default_body: &Bytes) -> Result<Vec<BatchItem>> {
+    let default_item = || BatchItem {
+        method: default_method.clone(),
+        uri: base.clone(),
+        body: default_body.clone(),
+    };
+    if input.trim().is_empty() {
+        return Ok(vec![default_item()]);
+    }
+
+    let mut items = Vec::new();
+    for spec in input.split(';').map(str::trim).filter(|spec| !spec.is_empty()) {
+        let mut tokens = spec.splitn(2, ' ');
+        let first = tokens.next().unwrap_or_default();
+        let rest = tokens.next().map(str::trim_start);
+
+        // 首个单词为全大写字母且后面还有内容时视为请求方法
+        let (method, rest) = match rest {
+            Some(rest) if first.chars().all(|c| c.is_ascii_uppercase()) => (Method::from_bytes(first.as_bytes())?, rest),
+            _ => (default_method.clone(), spec),
+        };
+        let (target, body) = match rest.split_once(' ') {
+            Some((target, body)) => (target, Bytes::from(body.to_string())),
+            None => (rest, default_body.clone()),
+        };
+
+        let uri = if target.contains("://") {
+            let uri = target.parse::<Uri>()?;
+            if uri.scheme() != base.scheme() || uri.authority() != base.authority() {
+                anyhow::bail!(
+                    "{} is not on {}://{} (batch requests share one connection)",
+                    target,
+                    base.scheme_str().unwrap_or_default(),
+                    base.authority().map(|authority| authority.as_str()).unwrap_or_default()
+                );
+            }
+            uri
+        } else {
+            let path = if target.starts_with('/') { target.to_string() } else { format!("/{}", target) };
+            let mut parts = base.clone().into_parts();
+            parts.path_and_query = Some(path.parse()?);
+            Uri::from_parts(parts)?
+        };
+        items.push(BatchItem { method, uri, body });
+    }
+    Ok(items)
+}
+
 /// HTTP/2 客户端处理器
 pub struct Http2ClientHandler {
-    /// 本地地址
-    local_addr: SocketAddr,
+    /// 当前连接信息 (未连接时为 None)
+    connection: Arc<RwLock<Option<ConnectionInfo>>>,
     /// 控制通道
     control_tx: Option<Sender<()>>,
-    /// 消息接收通道
-    message_rx: Option<Receiver<Message>>,
     /// 消息发送通道
-    message_tx: Option<Sender<Message>>,
+    ui_to_server_tx: Option<Sender<Message>>,
     /// UI消息发送通道
-    ui_tx: Option<Sender<Message>>,
+    server_to_ui_tx: Option<Sender<Message>>,
     /// 运行状态
     running: bool,
     /// HTTP请求参数
-    http_args: Option<crate::cli::args::HttpClientArgs>,
+    http_args: HttpClientArgs,
 }
 
 impl Http2ClientHandler {
     /// 创建新的HTTP/2客户端处理器
-    pub fn new(local_addr: SocketAddr, http_args: Option<crate::cli::args::HttpClientArgs>) -> Self {
+    pub fn new(mut http_args: HttpClientArgs) -> Self {
+        http_args.url = normalize_http_url(&http_args.url);
         Self {
-            local_addr,
+            connection: Arc::new(RwLock::new(None)),
             control_tx: None,
-            message_rx: None,
-            message_tx: None,
-            ui_tx: None,
+            ui_to_server_tx: None,
+            server_to_ui_tx: None,
             running: false,
             http_args,
         }
     }
+
+    /// 建立 HTTP/2 连接 (https 使用 TLS+ALPN，http 使用 h2c prior knowledge)，
+    /// 返回请求句柄、建立连接的耗时和连接信息
+    async fn connect(
+        uri: &Uri,
+        tls_config: &Option<Arc<ClientConfig>>,
+        connection: &Arc<RwLock<Option<ConnectionInfo>>>,
+        server_to_ui_tx: &Option<Sender<Message>>,
+    ) -> Result<(SendRequest<Bytes>, RequestTiming, ConnectionInfo)> {
+        let host = uri
+            .host()
+            .ok_or_else(|| anyhow::anyhow!("URL has no host: {}", uri))?
+            .trim_start_matches('[')
+            .trim_end_matches(']')
+            .to_string();
+        let port = uri.port_u16().unwrap_or(if tls_config.is_some() { 443 } else { 80 });
+
+        // DNS 解析
+        let started = Instant::now();
+        let remote_addr = lookup_host((host.as_str(), port))
+            .await?
+            .next()
+            .ok_or_else(|| anyhow::anyhow!("Failed to resolve {}", host))?;
+        let dns = started.elapsed();
+
+        // 建立 TCP 连接 (https 包含 TLS 握手)
+        let started = Instant::now();
+        let stream = TcpStream::connect(remote_addr).await?;
+        let connection_info = ConnectionInfo {
+            remote_addr,
+            connection_id: uri.authority().map_or_else(|| host.clone(), |a| a.to_string()),
+        };
+
+        let (send_request, transport) = if let Some(tls_config) = tls_config {
+            let server_name = ServerName::try_from(host)?;
+            let stream = TlsConnector::from(Arc::clone(tls_config)).connect(server_name, stream).await?;
+            if stream.get_ref().1.alpn_protocol() != Some(b"h2") {
+                anyhow::bail!("server did not negotiate h2 via ALPN");
+            }
+            let (send_request, h2_connection) = h2::client::handshake(stream).await?;
+            Self::spawn_connection(h2_connection, connection_info.clone(), connection, server_to_ui_tx);
+            (send_request, "TLS, ALPN h2")
+        } else {
+            let (send_request, h2_connection) = h2::client::handshake(stream).await?;
+            Self::spawn_connection(h2_connection, connection_info.clone(), connection, server_to_ui_tx);
+            (send_request, "h2c prior knowledge")
+        };
+        let timing = RequestTiming {
+            dns: Some(dns),
+            connect: Some(started.elapsed()),
+            ..Default::default()
+        };
+
+        // 记录连接并通知UI
+        *connection.write().await = Some(connection_info.clone());
+        if let Some(ref server_to_ui_sender) = server_to_ui_tx {
+            let _ = server_to_ui_sender
+                .send(Message::new_received(MessageType::ClientConnected, Some(connection_info.clone())))
+                .await;
+        }
+        let text = format!("HTTP/2 connection established to {} ({})", remote_addr, transport);
+        notify(server_to_ui_tx, &connection_info, text).await;
+
+        Ok((send_request, timing, connection_info))
+    }
+
+    /// 驱动 HTTP/2 连接，连接结束时报告 GOAWAY 等连接级事件
+    fn spawn_connection<T>(
+        h2_connection: h2::client::Connection<T, Bytes>,
+        connection_info: ConnectionInfo,
+        connection: &Arc<RwLock<Option<ConnectionInfo>>>,
+        server_to_ui_tx: &Option<Sender<Message>>,
+    ) where
+        T: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+    {
+        let connection = Arc::clone(connection);
+        let server_to_ui_tx = server_to_ui_tx.clone();
+        tokio::spawn(async move {
+            let text = match h2_connection.await {
+                Ok(()) => "HTTP/2 connection closed (graceful GOAWAY or EOF)".to_string(),
+                Err(e) if e.is_go_away() => {
+                    let reason = e.reason().map_or_else(|| "unknown".to_string(), |reason| format!("{:?}", reason));
+                    let side = if e.is_remote() { "received from server" } else { "sent to server" };
+                    format!("GOAWAY {} ({})", side, reason)
+                }
+                Err(e) => format!("HTTP/2 connection error: {}", e),
+            };
+
+            // 清理连接状态并通知UI
+            *connection.write().await = None;
+            notify(&server_to_ui_tx, &connection_info, text).await;
+            if let Some(ref server_to_ui_sender) = server_to_ui_tx {
+                let _ = server_to_ui_sender
+                    .send(Message::new_received(MessageType::ClientDisconnected, Some(connection_info)))
+                    .await;
+            }
+        });
+    }
+
+    /// 在同一连接上并发发送一批请求，响应按完成顺序显示
+    async fn send_batch(
+        mut send_request: SendRequest<Bytes>,
+        items: Vec<BatchItem>,
+        headers: &[(String, String)],
+        batch: u32,
+        mut timing: Option<RequestTiming>,
+        connection_info: &ConnectionInfo,
+        server_to_ui_tx: &Option<Sender<Message>>,
+    ) -> Result<SendRequest<Bytes>> {
+        let total = items.len();
+        let completed = Arc::new(AtomicUsize::new(0));
+
+        for (index, item) in items.into_iter().enumerate() {
+            let mut builder = Request::builder().method(item.method.clone()).uri(item.uri.clone());
+            for (name, value) in headers {
+                builder = builder.header(name.as_str(), value.as_str());
+            }
+            let request = builder.body(())?;
+
+            // 等待连接允许新建流
+            send_request = send_request.ready().await?;
+            let started = Instant::now();
+            let (response, mut send_stream) = send_request.send_request(request, item.body.is_empty())?;
+            let stream_id = u32::from(response.stream_id());
+            if !item.body.is_empty() {
+                send_stream.send_data(item.body.clone(), true)?;
+            }
+
+            if let Some(ref server_to_ui_sender) = server_to_ui_tx {
+                let mut text = format!("[stream {}] batch {} ({}/{}) {} {}", stream_id, batch, index + 1, total, item.method, item.uri);
+                for (name, value) in headers {
+                    text.push_str(&format!("\n{}: {}", name, value));
+                }
+                if !item.body.is_empty() {
+                    text.push_str(&format!("\n\n{}", String::from_utf8_lossy(&item.body)));
+                }
+                let _ = server_to_ui_sender
+                    .send(Message::new_request(text, Some(connection_info.clone())))
+                    .await;
+            }
+
+            // 每个流在独立任务中等待响应，首个请求附带建立连接的耗时
+            let timing = timing.take().unwrap_or_default();
+            let completed = Arc::clone(&completed);
+            let connection_info = connection_info.clone();
+            let server_to_ui_tx = server_to_ui_tx.clone();
+            tokio::spawn(async move {
+                let result = Self::read_response(response, started, timing).await;
+                let order = completed.fetch_add(1, Ordering::SeqCst) + 1;
+                match result {
+                    Ok((text, timing)) => {
+                        let text = format!("[stream {}] batch {} completed #{} of {} · {}", stream_id, batch, order, total, text);
+                        if let Some(ref server_to_ui_sender) = server_to_ui_tx {
+                            let _ = server_to_ui_sender
+                                .send(Message::new_received(MessageType::Text(text), Some(connection_info.clone())))
+                                .await;
+                            let _ = server_to_ui_sender
+                                .send(Message::new_received(MessageType::Timing(timing), Some(connection_info)))
+                                .await;
+                        }
+                    }
+                    Err(e) => {
+                        let text = format!("[stream {}] batch {} failed #{} of {}: {}", stream_id, batch, order, total, e);
+                        notify(&server_to_ui_tx, &connection_info, text).await;
+                    }
+                }
+            });
+        }
+        Ok(send_request)
+    }
+
+    /// 读取完整响应 (含尾部首部)，返回显示文本和耗时
+    async fn read_response(
+        response: ResponseFuture,
+        started: Instant,
+        mut timing: RequestTiming,
+    ) -> Result<(String, RequestTiming)> {
+        let response = response.await?;
+        timing.ttfb = started.elapsed();
+
+        let (parts, mut body) = response.into_parts();
+        let mut data = BytesMut::new();
+        while let Some(chunk) = body.data().await {
+            let chunk = chunk?;
+            let _ = body.flow_control().release_capacity(chunk.len());
+            data.extend_from_slice(&chunk);
+        }
+        let trailers = body.trailers().await?;
+        timing.total = started.elapsed();
+
+        let mut text = format!("{:?} {} ({:.1} ms)", parts.version, parts.status, timing.total.as_secs_f64() * 1000.0);
+        for (name, value) in header_pairs(&parts.headers) {
+            text.push_str(&format!("\n{}: {}", name, value));
+        }
+        if !data.is_empty() {
+            text.push_str(&format!("\n\n{}", String::from_utf8_lossy(&data)));
+        }
+        if let Some(trailers) = trailers {
+            text.push_str("\n\nTrailers:");
+            for (name, value) in header_pairs(&trailers) {
+                text.push_str(&format!("\n{}: {}", name, value));
+            }
+        }
+        Ok((text, timing))
+    }
 }
 
 #[async_trait]
 impl ProtocolHandler for Http2ClientHandler {
     async fn start(&mut self) -> Result<()> {
-        todo!("Implement HTTP/2 client start")
+        // 提前校验请求参数并建立连接
+        let base = self.http_args.url.parse::<Uri>()?;
+        if base.host().is_none() {
+            anyhow::bail!("URL has no host: {}", base);
+        }
+        let method = Method::from_bytes(self.http_args.method.to_uppercase().as_bytes())?;
+        let default_body = Bytes::from(self.http_args.body.clone().unwrap_or_default());
+        let tls_config = if base.scheme_str() == Some("https") {
+            let mut config = TlsConfig::client_config(self.http_args.cacert.as_deref(), self.http_args.insecure)?;
+            config.alpn_protocols = vec![b"h2".to_vec()];
+            Some(Arc::new(config))
+        } else {
+            None
+        };
+        let (send_request, timing, connection_info) =
+            Self::connect(&base, &tls_config, &self.connection, &self.server_to_ui_tx).await?;
+
+        // 创建消息通道
+        let (ui_to_server_tx, mut ui_to_server_rx) = channel::<Message>(100);
+        let (control_tx, mut control_rx) = channel::<()>(1);
+
+        self.ui_to_server_tx = Some(ui_to_server_tx);
+        self.control_tx = Some(control_tx);
+        self.running = true;
+
+        let headers = self.http_args.headers.clone();
+        let connection = Arc::clone(&self.connection);
+        let server_to_ui_tx = self.server_to_ui_tx.clone();
+
+        // 启动请求任务：先发送命令行参数中的请求，之后每次UI发送作为一批并发请求
+        tokio::spawn(async move {
+            let mut current = Some((send_request, connection_info));
+            let mut timing = Some(timing);
+            let mut pending = Some(vec![BatchItem {
+                method: method.clone(),
+                uri: base.clone(),
+                body: default_body.clone(),
+            }]);
+            let mut batch = 0;
+            loop {
+                if let Some(items) = pending.take() {
+                    // 连接已断开时重新建立连接
+                    if connection.read().await.is_none() {
+                        current = None;
+                    }
+                    if current.is_none() {
+                        match Self::connect(&base, &tls_config, &connection, &server_to_ui_tx).await {
+                            Ok((send_request, new_timing, connection_info)) => {
+                                current = Some((send_request, connection_info));
+                                timing = Some(new_timing);
+                            }
+                            Err(e) => {
+                                if let Some(ref server_to_ui_sender) = server_to_ui_tx {
+                                    let _ = server_to_ui_sender
+                                        .send(Message::new_event(format!("Connect failed: {:#}", e), None))
+                                        .await;
+                                }
+                            }
+                        }
+                    }
+
+                    if let Some((send_request, connection_info)) = current.take() {
+                        batch += 1;
+                        let result = Self::send_batch(
+                            send_request,
+                            items,
+                            &headers,
+                            batch,
+                            timing.take(),
+                            &connection_info,
+                            &server_to_ui_tx,
+                        )
+                        .await;
+                        match result {
+                            Ok(send_request) => current = Some((send_request, connection_info)),
+                            Err(e) => {
+                                let text = format!("Batch {} failed: {}", batch, e);
+                                notify(&server_to_ui_tx, &connection_info, text).await;
+                            }
+                        }
+                    }
+                }
+
+                // 等待下一批请求
+                tokio::select! {
+                    message = ui_to_server_rx.recv() => {
+                        let Some(message) = message else {
+                            break;
+                        };
+                        let input = message
+                            .content
+                            .to_bytes()
+                            .map(|data| String::from_utf8_lossy(&data).into_owned())
+                            .unwrap_or_default();
+                        match parse_batch(&input, &base, &method, &default_body) {
+                            Ok(items) => {
+                                message.mark_written();
+                                pending = Some(items);
+                            }
+                            Err(e) => {
+                                if let Some(ref server_to_ui_sender) = server_to_ui_tx {
+                                    let _ = server_to_ui_sender
+                                        .send(Message::new_event(format!("Invalid batch: {}", e), None))
+                                        .await;
+                                }
+                            }
+                        }
+                    }
+                    _ = control_rx.recv() => break,
+                }
+            }
+        });
+
+        Ok(())
     }
-    
+
     async fn stop(&mut self) -> Result<()> {
-        todo!("Implement HTTP/2 client stop")
-    }
-    
-    async fn send_message(&mut self, message: MessageType, target: Option<String>) -> Result<()> {
-        todo!("Implement HTTP/2 client send message")
-    }
-    
-    fn get_receiver(&self) -> Option<Receiver<Message>> {
-        self.message_rx.clone()
+        if self.running {
+            // 发送停止信号
+            if let Some(ref control_tx) = self.control_tx {
+                let _ = control_tx.send(()).await;
+            }
+            self.running = false;
+            // 清理资源
+            self.control_tx = None;
+            self.ui_to_server_tx = None;
+        }
+        Ok(())
     }
-    
-    fn set_ui_sender(&mut self, sender: Sender<Message>) {
-        self.ui_tx = Some(sender);
+
+    fn get_ui_to_server_sender(&self) -> Option<Sender<Message>> {
+        self.ui_to_server_tx.clone()
     }
-    
-    fn is_running(&self) -> bool {
-        self.running
+
+    fn set_server_to_ui_sender(&mut self, sender: Sender<Message>) {
+        self.server_to_ui_tx = Some(sender);
     }
-    
+
     fn get_connections(&self) -> Vec<ConnectionInfo> {
-        todo!("Implement get_connections for HTTP/2 client")
+        self.connection
+            .try_read()
+            .map(|connection| connection.iter().cloned().collect())
+            .unwrap_or_default()
     }
-    
+
     fn protocol_name(&self) -> &'static str {
         "HTTP/2 Client"
     }
-}
\ No newline at end of file
+}
+
+/// 注册 HTTP/2 服务器和客户端处理器
+pub fn register(registry: &mut HandlerRegistry) {
+    registry.register_protocol(ProtocolType::Http2, "http2", &[]);
+    registry.register(ProtocolType::Http2, AppMode::Server, "HTTP/2 Server", Http2ServerArgs::augment_args, |matches| {
+        let server_args = Http2ServerArgs::from_arg_matches(matches)?;
+        // 指定证书时同时支持 TLS+ALPN 和 h2c，否则仅支持 h2c
+        let tls_config = match server_args.tls_files() {
+            Some((cert_path, key_path)) => {
+                let mut config = TlsConfig::server_config_from_files(cert_path, key_path)?;
+                config.alpn_protocols = vec![b"h2".to_vec()];
+                Some(config)
+            }
+            None => None,
+        };
+        let default_response = server_args.response.default_response();
+        Ok(Box::new(Http2ServerHandler::new(server_args.listen.local_addr(), default_response, tls_config)))
+    });
+    registry.register(ProtocolType::Http2, AppMode::Client, "HTTP/2 Client", HttpClientArgs::augment_args, |matches| {
+        Ok(Box::new(Http2ClientHandler::new(HttpClientArgs::from_arg_matches(matches)?)))
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_batch() {
+        let base: Uri = "http://127.0.0.1:8080/index".parse().unwrap();
+        let body = Bytes::from_static(b"default");
+
+        // 空输入重复命令行参数中的请求
+        let items = parse_batch("  ", &base, &Method::GET, &body).unwrap();
+        assert_eq!(items, vec![BatchItem { method: Method::GET, uri: base.clone(), body: body.clone() }]);
+
+        let items = parse_batch("/a; POST b hello world ;http://127.0.0.1:8080/c", &base, &Method::GET, &body).unwrap();
+        assert_eq!(items.len(), 3);
+        assert_eq!((&items[0].method, items[0].uri.to_string(), &items[0].body), (&Method::GET, "http://127.0.0.1:8080/a".to_string(), &body));
+        assert_eq!((&items[1].method, items[1].uri.to_string()), (&Method::POST, "http://127.0.0.1:8080/b".to_string()));
+        assert_eq!(items[1].body, Bytes::from_static(b"hello world"));
+        assert_eq!(items[2].uri.to_string(), "http://127.0.0.1:8080/c");
+
+        // 其他源的请求不能通过当前连接发送
+        for input in ["/a; https://example.com/c", "https://127.0.0.1:8080/c", "http://127.0.0.1:9090/c"] {
+            let error = parse_batch(input, &base, &Method::GET, &body).unwrap_err();
+            assert!(error.to_string().contains("is not on http://127.0.0.1:8080"));
+        }
+    }
+}
+
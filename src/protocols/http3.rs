@@ -1,161 +1,904 @@
 use anyhow::Result;
 use async_trait::async_trait;
-use bytes::Bytes;
-use std::{collections::HashMap, net::SocketAddr, sync::Arc};
-use tokio::sync::{mpsc::{Receiver, Sender, channel}, RwLock};
+use bytes::{BufMut, Bytes, BytesMut};
+use clap::{Args, FromArgMatches};
+use h3::{client::SendRequest, server::RequestResolver};
+use h3_quinn::OpenStreams;
+use http::{Method, Request, Uri, Version};
+use quinn::{
+    crypto::rustls::{HandshakeData, QuicClientConfig, QuicServerConfig},
+    ConnectionError, Endpoint, Incoming, VarInt, ZeroRttAccepted,
+};
+use rustls::ServerConfig;
+use std::{
+    io::Write,
+    net::{Ipv4Addr, Ipv6Addr, SocketAddr},
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Instant,
+};
+use tokio::{
+    net::lookup_host,
+    sync::{mpsc::{Sender, channel}, RwLock},
+    task::JoinHandle,
+};
 
+use crate::cli::args::{AppMode, ListenArgs, ProtocolType};
+use crate::config::tls::TlsConfig;
 use crate::protocols::common::{
-    ConnectionInfo, Message, MessageDirection, MessageType, ProtocolHandler,
+    ConnectionInfo, Message, MessageType, ProtocolHandler, RequestTiming,
 };
+use crate::protocols::http::{connections_by_ip, record_request, DefaultResponse, HttpClientArgs, HttpRequest, HttpResponseArgs};
+use crate::protocols::reconnect::notify;
+use crate::protocols::registry::HandlerRegistry;
+
+/// 自签名证书 PEM 保存文件名的前缀 (位于系统临时目录)
+const SELF_SIGNED_CERT_PREFIX: &str = "nt-http3-self-signed";
+
+/// 客户端使用的 QUIC 版本 (RFC 9000)
+const QUIC_VERSION: u32 = 1;
+
+/// HTTP/3 正常关闭连接的错误码 (H3_NO_ERROR)
+const H3_NO_ERROR: u32 = 0x100;
+
+/// HTTP/3 服务器参数
+#[derive(Args, Debug, Clone)]
+pub struct Http3ServerArgs {
+    #[command(flatten)]
+    pub listen: ListenArgs,
+
+    /// 默认响应参数
+    #[command(flatten)]
+    pub response: HttpResponseArgs,
+
+    /// TLS 证书文件 (PEM)，不指定时使用临时生成的自签名证书
+    #[arg(long, requires = "tls_key")]
+    pub tls_cert: Option<PathBuf>,
+
+    /// TLS 私钥文件 (PEM)
+    #[arg(long, requires = "tls_cert")]
+    pub tls_key: Option<PathBuf>,
+}
+
+impl Http3ServerArgs {
+    /// TLS 证书和私钥文件
+    pub fn tls_files(&self) -> Option<(&Path, &Path)> {
+        self.tls_cert.as_deref().zip(self.tls_key.as_deref())
+    }
+}
 
 /// HTTP/3 服务器处理器
 pub struct Http3ServerHandler {
     /// 本地地址
     local_addr: SocketAddr,
+    /// TLS 配置 (None 表示启动时生成自签名证书)
+    tls_config: Option<ServerConfig>,
+    /// 默认响应
+    default_response: Arc<RwLock<DefaultResponse>>,
     /// 客户端请求记录
-    requests: Arc<RwLock<Vec<Http3Request>>>,
+    requests: Arc<RwLock<Vec<HttpRequest>>>,
     /// 控制通道 (用于停止服务器)
     control_tx: Option<Sender<()>>,
-    /// 消息接收通道
-    message_rx: Option<Receiver<Message>>,
     /// 消息发送通道
-    message_tx: Option<Sender<Message>>,
+    ui_to_server_tx: Option<Sender<Message>>,
     /// UI消息发送通道
-    ui_tx: Option<Sender<Message>>,
+    server_to_ui_tx: Option<Sender<Message>>,
     /// 运行状态
     running: bool,
 }
 
-/// HTTP/3 请求记录
-struct Http3Request {
-    /// 客户端地址
-    client_addr: SocketAddr,
-    /// 请求方法
-    method: String,
-    /// 请求路径
-    path: String,
-    /// 请求头
-    headers: HashMap<String, String>,
-    /// 请求体
-    body: Option<Vec<u8>>,
-    /// 时间戳
-    timestamp: chrono::DateTime<chrono::Local>,
-}
-
 impl Http3ServerHandler {
     /// 创建新的HTTP/3服务器处理器
-    pub fn new(local_addr: SocketAddr) -> Self {
+    pub fn new(local_addr: SocketAddr, default_response: DefaultResponse, tls_config: Option<ServerConfig>) -> Self {
         Self {
             local_addr,
+            tls_config,
+            default_response: Arc::new(RwLock::new(default_response)),
             requests: Arc::new(RwLock::new(Vec::new())),
             control_tx: None,
-            message_rx: None,
-            message_tx: None,
-            ui_tx: None,
+            ui_to_server_tx: None,
+            server_to_ui_tx: None,
             running: false,
         }
     }
+
+    /// 处理一个 QUIC 连接上的所有 HTTP/3 请求
+    async fn handle_connection(
+        incoming: Incoming,
+        requests: Arc<RwLock<Vec<HttpRequest>>>,
+        default_response: Arc<RwLock<DefaultResponse>>,
+        server_to_ui_tx: Option<Sender<Message>>,
+    ) -> Result<()> {
+        let connection = incoming.await?;
+        let client_addr = connection.remote_address();
+        let mut h3_connection = h3::server::builder()
+            .build::<_, Bytes>(h3_quinn::Connection::new(connection.clone()))
+            .await?;
+
+        loop {
+            match h3_connection.accept().await {
+                Ok(Some(resolver)) => {
+                    let requests = Arc::clone(&requests);
+                    let default_response = Arc::clone(&default_response);
+                    let server_to_ui_tx = server_to_ui_tx.clone();
+                    tokio::spawn(async move {
+                        let result =
+                            Self::handle_request(resolver, client_addr, &requests, &default_response, &server_to_ui_tx).await;
+                        if let Err(e) = result {
+                            let connection_info = ConnectionInfo {
+                                remote_addr: client_addr,
+                                connection_id: client_addr.ip().to_string(),
+                            };
+                            notify(&server_to_ui_tx, &connection_info, format!("HTTP/3 request error: {}", e)).await;
+                        }
+                    });
+                }
+                Ok(None) => break,
+                Err(e) if e.is_h3_no_error() => break,
+                // 服务器停止时本地关闭的连接不视为错误
+                Err(_) if matches!(connection.close_reason(), Some(ConnectionError::LocallyClosed)) => break,
+                Err(e) => return Err(e.into()),
+            }
+        }
+        Ok(())
+    }
+
+    /// 处理单个请求：记录请求并返回默认响应
+    async fn handle_request(
+        resolver: RequestResolver<h3_quinn::Connection, Bytes>,
+        client_addr: SocketAddr,
+        requests: &RwLock<Vec<HttpRequest>>,
+        default_response: &RwLock<DefaultResponse>,
+        server_to_ui_tx: &Option<Sender<Message>>,
+    ) -> Result<()> {
+        let (request, mut stream) = resolver.resolve_request().await?;
+
+        // 读取完整请求体
+        let mut body = BytesMut::new();
+        while let Some(chunk) = stream.recv_data().await? {
+            body.put(chunk);
+        }
+
+        let record = HttpRequest {
+            client_addr,
+            method: request.method().to_string(),
+            path: request.uri().path_and_query().map_or("/", |p| p.as_str()).to_string(),
+            version: format!("{:?}", request.version()),
+            headers: request
+                .headers()
+                .iter()
+                .map(|(name, value)| (name.to_string(), String::from_utf8_lossy(value.as_bytes()).into_owned()))
+                .collect(),
+            body: (!body.is_empty()).then(|| body.to_vec()),
+            timestamp: chrono::Local::now(),
+        };
+        let default_response = record_request(record, http::Version::HTTP_3, requests, default_response, server_to_ui_tx).await;
+
+        // 发送默认响应
+        stream.send_response(default_response.head()).await?;
+        if !default_response.body.is_empty() {
+            stream.send_data(default_response.body.clone()).await?;
+        }
+        stream.finish().await?;
+        Ok(())
+    }
 }
 
 #[async_trait]
 impl ProtocolHandler for Http3ServerHandler {
     async fn start(&mut self) -> Result<()> {
-        todo!("Implement HTTP/3 server start")
+        // 未指定证书时生成自签名证书，并保存 PEM 以便客户端信任
+        let mut notes = Vec::new();
+        let mut tls_config = match self.tls_config.take() {
+            Some(tls_config) => tls_config,
+            None => {
+                let (tls_config, pem) = TlsConfig::server_config_with_self_signed()?;
+                let path = save_self_signed_pem(&pem)?;
+                notes.push(format!(
+                    "Using a self-signed certificate for localhost, 127.0.0.1 and ::1 (PEM saved to {})",
+                    path.display()
+                ));
+                tls_config
+            }
+        };
+        tls_config.alpn_protocols = vec![b"h3".to_vec()];
+        // 允许客户端使用 0-RTT：QUIC 要求该值为 0 或 0xffff_ffff (RFC 9001 第 4.6.1 节)，
+        // 早期数据的实际大小由 QUIC 的流量控制限制
+        tls_config.max_early_data_size = 0xffff_ffff;
+
+        // 绑定 QUIC 端点
+        let quic_config = QuicServerConfig::try_from(tls_config)?;
+        let endpoint = Endpoint::server(quinn::ServerConfig::with_crypto(Arc::new(quic_config)), self.local_addr)?;
+
+        // 创建消息通道
+        let (ui_to_server_tx, mut ui_to_server_rx) = channel::<Message>(100);
+        let (control_tx, mut control_rx) = channel::<()>(1);
+
+        self.ui_to_server_tx = Some(ui_to_server_tx);
+        self.control_tx = Some(control_tx);
+        self.running = true;
+
+        let requests = Arc::clone(&self.requests);
+        let default_response = Arc::clone(&self.default_response);
+        let server_to_ui_tx = self.server_to_ui_tx.clone();
+
+        if let Some(ref server_to_ui_sender) = server_to_ui_tx {
+            for note in notes {
+                let _ = server_to_ui_sender.send(Message::new_event(note, None)).await;
+            }
+        }
+
+        // 启动服务器任务
+        tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    // 接受新的 QUIC 连接
+                    incoming = endpoint.accept() => {
+                        let Some(incoming) = incoming else {
+                            break;
+                        };
+                        let client_addr = incoming.remote_address();
+                        let requests = Arc::clone(&requests);
+                        let default_response = Arc::clone(&default_response);
+                        let server_to_ui_tx = server_to_ui_tx.clone();
+
+                        tokio::spawn(async move {
+                            if let Err(e) = Self::handle_connection(incoming, requests, default_response, server_to_ui_tx.clone()).await {
+                                let connection_info = ConnectionInfo {
+                                    remote_addr: client_addr,
+                                    connection_id: client_addr.ip().to_string(),
+                                };
+                                let text = format!("HTTP/3 connection from {} error: {}", client_addr, e);
+                                notify(&server_to_ui_tx, &connection_info, text).await;
+                            }
+                        });
+                    }
+
+                    // UI输入的内容作为后续请求的默认响应体
+                    message = ui_to_server_rx.recv() => {
+                        let Some(message) = message else {
+                            break;
+                        };
+                        if let Some(body) = message.content.to_bytes() {
+                            let length = body.len();
+                            message.mark_written();
+                            default_response.write().await.body = body;
+                            if let Some(ref server_to_ui_sender) = server_to_ui_tx {
+                                let _ = server_to_ui_sender
+                                    .send(Message::new_event(format!("Default response body set ({} bytes)", length), None))
+                                    .await;
+                            }
+                        }
+                    }
+
+                    // 处理停止信号
+                    _ = control_rx.recv() => {
+                        endpoint.close(VarInt::from_u32(H3_NO_ERROR), b"server stopped");
+                        break;
+                    }
+                }
+            }
+        });
+
+        Ok(())
     }
-    
+
     async fn stop(&mut self) -> Result<()> {
-        todo!("Implement HTTP/3 server stop")
-    }
-    
-    async fn send_message(&mut self, message: MessageType, target: Option<String>) -> Result<()> {
-        todo!("Implement HTTP/3 server send message")
-    }
-    
-    fn get_receiver(&self) -> Option<Receiver<Message>> {
-        self.message_rx.clone()
+        if self.running {
+            // 发送停止信号
+            if let Some(ref control_tx) = self.control_tx {
+                let _ = control_tx.send(()).await;
+            }
+            self.running = false;
+            // 清理资源
+            self.control_tx = None;
+            self.ui_to_server_tx = None;
+        }
+        Ok(())
     }
-    
-    fn set_ui_sender(&mut self, sender: Sender<Message>) {
-        self.ui_tx = Some(sender);
+
+    fn get_ui_to_server_sender(&self) -> Option<Sender<Message>> {
+        self.ui_to_server_tx.clone()
     }
-    
-    fn is_running(&self) -> bool {
-        self.running
+
+    fn set_server_to_ui_sender(&mut self, sender: Sender<Message>) {
+        self.server_to_ui_tx = Some(sender);
     }
-    
+
     fn get_connections(&self) -> Vec<ConnectionInfo> {
-        todo!("Implement get_connections for HTTP/3 server")
+        self.requests
+            .try_read()
+            .map(|requests| connections_by_ip(&requests))
+            .unwrap_or_default()
     }
-    
+
     fn protocol_name(&self) -> &'static str {
         "HTTP/3 Server"
     }
 }
 
+/// 生成 QUIC 连接关闭原因的显示文本 (含错误码)
+fn describe_close(error: &ConnectionError) -> String {
+    match error {
+        ConnectionError::ApplicationClosed(close) => {
+            let code = close.error_code.into_inner();
+            let mut text = format!(
+                "closed by server with application error 0x{:x} ({})",
+                code,
+                h3_error_name(code).unwrap_or("unknown")
+            );
+            if !close.reason.is_empty() {
+                text.push_str(&format!(": {}", String::from_utf8_lossy(&close.reason)));
+            }
+            text
+        }
+        ConnectionError::ConnectionClosed(close) => format!("closed by server with transport error {}", close),
+        ConnectionError::LocallyClosed => "closed locally".to_string(),
+        ConnectionError::TimedOut => "closed after idle timeout".to_string(),
+        other => format!("closed: {}", other),
+    }
+}
+
+/// HTTP/3 应用层错误码名称 (RFC 9114 第 8.1 节)
+fn h3_error_name(code: u64) -> Option<&'static str> {
+    let name = match code {
+        0x100 => "H3_NO_ERROR",
+        0x101 => "H3_GENERAL_PROTOCOL_ERROR",
+        0x102 => "H3_INTERNAL_ERROR",
+        0x103 => "H3_STREAM_CREATION_ERROR",
+        0x104 => "H3_CLOSED_CRITICAL_STREAM",
+        0x105 => "H3_FRAME_UNEXPECTED",
+        0x106 => "H3_FRAME_ERROR",
+        0x107 => "H3_EXCESSIVE_LOAD",
+        0x108 => "H3_ID_ERROR",
+        0x109 => "H3_SETTINGS_ERROR",
+        0x10a => "H3_MISSING_SETTINGS",
+        0x10b => "H3_REQUEST_REJECTED",
+        0x10c => "H3_REQUEST_CANCELLED",
+        0x10d => "H3_REQUEST_INCOMPLETE",
+        0x10e => "H3_MESSAGE_ERROR",
+        0x10f => "H3_CONNECT_ERROR",
+        0x110 => "H3_VERSION_FALLBACK",
+        _ => return None,
+    };
+    Some(name)
+}
+
+/// 生成 QUIC 路径统计的显示文本
+fn describe_path(quic: &quinn::Connection) -> String {
+    let path = quic.stats().path;
+    format!(
+        "RTT {:.1} ms, cwnd {} bytes, {} of {} packets lost",
+        path.rtt.as_secs_f64() * 1000.0,
+        path.cwnd,
+        path.lost_packets,
+        path.sent_packets
+    )
+}
+
+/// 将自签名证书 PEM 保存到系统临时目录，返回文件路径
+///
+/// 每个服务器使用独立的文件名并以独占方式创建，不会跟随已存在的文件或符号链接
+fn save_self_signed_pem(pem: &str) -> Result<PathBuf> {
+    let dir = std::env::temp_dir();
+    let mut attempt = 0;
+    loop {
+        let path = dir.join(format!("{}-{}-{}.pem", SELF_SIGNED_CERT_PREFIX, std::process::id(), attempt));
+        match std::fs::OpenOptions::new().write(true).create_new(true).open(&path) {
+            Ok(mut file) => {
+                file.write_all(pem.as_bytes())?;
+                return Ok(path);
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists && attempt < 100 => attempt += 1,
+            Err(e) => return Err(anyhow::anyhow!("Failed to save certificate to {}: {}", path.display(), e)),
+        }
+    }
+}
+
+/// 省略协议时默认为 https:// (HTTP/3 只支持 https)
+fn normalize_https_url(url: &str) -> String {
+    if url.contains("://") {
+        url.to_string()
+    } else {
+        format!("https://{}", url)
+    }
+}
+
+/// 已建立的 HTTP/3 客户端连接
+struct ClientConnection {
+    /// 请求句柄
+    send_request: SendRequest<OpenStreams, Bytes>,
+    /// QUIC 连接
+    quic: quinn::Connection,
+    /// 连接信息
+    connection_info: ConnectionInfo,
+    /// 驱动连接的任务，结束时已清理连接状态
+    driver: JoinHandle<()>,
+    /// 以 0-RTT 建立时，握手完成后报告服务器是否接受了早期数据
+    zero_rtt: Option<ZeroRttAccepted>,
+}
+
 /// HTTP/3 客户端处理器
 pub struct Http3ClientHandler {
-    /// 本地地址
-    local_addr: SocketAddr,
+    /// 当前连接信息 (未连接时为 None)
+    connection: Arc<RwLock<Option<ConnectionInfo>>>,
     /// 控制通道
     control_tx: Option<Sender<()>>,
-    /// 消息接收通道
-    message_rx: Option<Receiver<Message>>,
     /// 消息发送通道
-    message_tx: Option<Sender<Message>>,
+    ui_to_server_tx: Option<Sender<Message>>,
     /// UI消息发送通道
-    ui_tx: Option<Sender<Message>>,
+    server_to_ui_tx: Option<Sender<Message>>,
     /// 运行状态
     running: bool,
     /// HTTP请求参数
-    http_args: Option<crate::cli::args::HttpClientArgs>,
+    http_args: HttpClientArgs,
 }
 
 impl Http3ClientHandler {
     /// 创建新的HTTP/3客户端处理器
-    pub fn new(local_addr: SocketAddr, http_args: Option<crate::cli::args::HttpClientArgs>) -> Self {
+    pub fn new(mut http_args: HttpClientArgs) -> Self {
+        http_args.url = normalize_https_url(&http_args.url);
         Self {
-            local_addr,
+            connection: Arc::new(RwLock::new(None)),
             control_tx: None,
-            message_rx: None,
-            message_tx: None,
-            ui_tx: None,
+            ui_to_server_tx: None,
+            server_to_ui_tx: None,
             running: false,
             http_args,
         }
     }
+
+    /// 根据命令行参数构造请求
+    fn build_request(http_args: &HttpClientArgs) -> Result<Request<()>> {
+        let method = Method::from_bytes(http_args.method.to_uppercase().as_bytes())?;
+        let mut builder = Request::builder()
+            .method(method)
+            .uri(http_args.url.as_str())
+            .version(Version::HTTP_3);
+        for (name, value) in &http_args.headers {
+            builder = builder.header(name.as_str(), value.as_str());
+        }
+        Ok(builder.body(())?)
+    }
+
+    /// 生成请求的显示文本
+    fn describe_request(request: &Request<()>, body: &[u8]) -> String {
+        let mut text = format!("{} {} {:?}", request.method(), request.uri(), request.version());
+        for (name, value) in request.headers() {
+            text.push_str(&format!("\n{}: {}", name, String::from_utf8_lossy(value.as_bytes())));
+        }
+        if !body.is_empty() {
+            text.push_str(&format!("\n\n{}", String::from_utf8_lossy(body)));
+        }
+        text
+    }
+
+    /// 建立 QUIC 连接并完成 HTTP/3 初始化，返回连接和建立连接的耗时
+    ///
+    /// 复用同一个端点且 `early_data` 为 true 时会利用之前的会话票据尝试 0-RTT，
+    /// 此时不等待握手完成即返回，之后的请求作为早期数据发送
+    async fn connect(
+        uri: &Uri,
+        endpoint: &mut Option<Endpoint>,
+        client_config: &quinn::ClientConfig,
+        early_data: bool,
+        connection: &Arc<RwLock<Option<ConnectionInfo>>>,
+        server_to_ui_tx: &Option<Sender<Message>>,
+    ) -> Result<(ClientConnection, RequestTiming)> {
+        let host = uri
+            .host()
+            .ok_or_else(|| anyhow::anyhow!("URL has no host: {}", uri))?
+            .trim_start_matches('[')
+            .trim_end_matches(']')
+            .to_string();
+        let port = uri.port_u16().unwrap_or(443);
+
+        // DNS 解析
+        let started = Instant::now();
+        let remote_addr = lookup_host((host.as_str(), port))
+            .await?
+            .next()
+            .ok_or_else(|| anyhow::anyhow!("Failed to resolve {}", host))?;
+        let dns = started.elapsed();
+
+        // 按目标地址族复用或绑定本地端点
+        let reusable = endpoint
+            .as_ref()
+            .filter(|endpoint| endpoint.local_addr().is_ok_and(|addr| addr.is_ipv4() == remote_addr.is_ipv4()))
+            .cloned();
+        let quic_endpoint = match reusable {
+            Some(quic_endpoint) => quic_endpoint,
+            None => {
+                let bind_addr: SocketAddr = if remote_addr.is_ipv4() {
+                    (Ipv4Addr::UNSPECIFIED, 0).into()
+                } else {
+                    (Ipv6Addr::UNSPECIFIED, 0).into()
+                };
+                let mut quic_endpoint = Endpoint::client(bind_addr)?;
+                quic_endpoint.set_default_client_config(client_config.clone());
+                *endpoint = Some(quic_endpoint.clone());
+                quic_endpoint
+            }
+        };
+
+        // QUIC 握手：有会话票据时使用 0-RTT，否则完成完整的 1-RTT 握手
+        let started = Instant::now();
+        let connecting = quic_endpoint.connect(remote_addr, &host)?;
+        let (quic, zero_rtt) = if early_data {
+            match connecting.into_0rtt() {
+                Ok((quic, accepted)) => (quic, Some(accepted)),
+                Err(connecting) => (connecting.await?, None),
+            }
+        } else {
+            (connecting.await?, None)
+        };
+        let handshake = if zero_rtt.is_some() { "0-RTT, sending early data" } else { "1-RTT" };
+        let alpn = quic
+            .handshake_data()
+            .and_then(|data| data.downcast::<HandshakeData>().ok())
+            .and_then(|data| data.protocol)
+            .map(|protocol| String::from_utf8_lossy(&protocol).into_owned());
+        // 0-RTT 时握手尚未完成，ALPN 沿用恢复的会话 (rustls 只对 ALPN 相同的会话发送早期数据)
+        let alpn = match alpn {
+            Some(alpn) => alpn,
+            None if zero_rtt.is_some() => "h3".to_string(),
+            None => "-".to_string(),
+        };
+        if alpn != "h3" {
+            quic.close(VarInt::from_u32(H3_NO_ERROR), b"");
+            anyhow::bail!("server did not negotiate h3 via ALPN (got {})", alpn);
+        }
+
+        // HTTP/3 初始化 (控制流和 SETTINGS)
+        let (driver, send_request) = h3::client::new(h3_quinn::Connection::new(quic.clone())).await?;
+        let timing = RequestTiming {
+            dns: Some(dns),
+            connect: Some(started.elapsed()),
+            ..Default::default()
+        };
+        let connection_info = ConnectionInfo {
+            remote_addr,
+            connection_id: uri.authority().map_or_else(|| host.clone(), |a| a.to_string()),
+        };
+        let driver = Self::spawn_connection(driver, quic.clone(), connection_info.clone(), connection, server_to_ui_tx);
+
+        // 记录连接并通知UI
+        *connection.write().await = Some(connection_info.clone());
+        if let Some(ref server_to_ui_sender) = server_to_ui_tx {
+            let _ = server_to_ui_sender
+                .send(Message::new_received(MessageType::ClientConnected, Some(connection_info.clone())))
+                .await;
+        }
+        let text = format!(
+            "QUIC connection established to {} (ALPN {}, {}, {})",
+            remote_addr,
+            alpn,
+            handshake,
+            describe_path(&quic)
+        );
+        notify(server_to_ui_tx, &connection_info, text).await;
+
+        let client = ClientConnection {
+            send_request,
+            quic,
+            connection_info,
+            driver,
+            zero_rtt,
+        };
+        Ok((client, timing))
+    }
+
+    /// 驱动 HTTP/3 连接，连接结束时报告关闭错误码
+    fn spawn_connection(
+        mut driver: h3::client::Connection<h3_quinn::Connection, Bytes>,
+        quic: quinn::Connection,
+        connection_info: ConnectionInfo,
+        connection: &Arc<RwLock<Option<ConnectionInfo>>>,
+        server_to_ui_tx: &Option<Sender<Message>>,
+    ) -> JoinHandle<()> {
+        let connection = Arc::clone(connection);
+        let server_to_ui_tx = server_to_ui_tx.clone();
+        tokio::spawn(async move {
+            let _ = std::future::poll_fn(|cx| driver.poll_close(cx)).await;
+            let text = format!("QUIC connection {}", describe_close(&quic.closed().await));
+
+            // 清理连接状态并通知UI
+            *connection.write().await = None;
+            notify(&server_to_ui_tx, &connection_info, text).await;
+            if let Some(ref server_to_ui_sender) = server_to_ui_tx {
+                let _ = server_to_ui_sender
+                    .send(Message::new_received(MessageType::ClientDisconnected, Some(connection_info)))
+                    .await;
+            }
+        })
+    }
+
+    /// 发送请求并读取完整响应 (含尾部首部)，返回显示文本和耗时
+    async fn perform(
+        send_request: &mut SendRequest<OpenStreams, Bytes>,
+        request: Request<()>,
+        body: Bytes,
+        quic: &quinn::Connection,
+        mut timing: RequestTiming,
+    ) -> Result<(String, RequestTiming)> {
+        let started = Instant::now();
+        let mut stream = send_request.send_request(request).await?;
+        if !body.is_empty() {
+            stream.send_data(body).await?;
+        }
+        stream.finish().await?;
+
+        let response = stream.recv_response().await?;
+        timing.ttfb = started.elapsed();
+        let mut data = BytesMut::new();
+        while let Some(chunk) = stream.recv_data().await? {
+            data.put(chunk);
+        }
+        let trailers = stream.recv_trailers().await?;
+        timing.total = started.elapsed();
+
+        let mut text = format!(
+            "{:?} {} ({:.1} ms) · {}",
+            response.version(),
+            response.status(),
+            timing.total.as_secs_f64() * 1000.0,
+            describe_path(quic)
+        );
+        for (name, value) in response.headers() {
+            text.push_str(&format!("\n{}: {}", name, String::from_utf8_lossy(value.as_bytes())));
+        }
+        if !data.is_empty() {
+            text.push_str(&format!("\n\n{}", String::from_utf8_lossy(&data)));
+        }
+        if let Some(trailers) = trailers {
+            text.push_str("\n\nTrailers:");
+            for (name, value) in &trailers {
+                text.push_str(&format!("\n{}: {}", name, String::from_utf8_lossy(value.as_bytes())));
+            }
+        }
+        Ok((text, timing))
+    }
 }
 
 #[async_trait]
 impl ProtocolHandler for Http3ClientHandler {
     async fn start(&mut self) -> Result<()> {
-        todo!("Implement HTTP/3 client start")
+        // 提前校验请求参数
+        let base = self.http_args.url.parse::<Uri>()?;
+        if base.scheme_str() != Some("https") {
+            anyhow::bail!("HTTP/3 requires an https:// URL: {}", base);
+        }
+        Self::build_request(&self.http_args)?;
+        let default_body = Bytes::from(self.http_args.body.clone().unwrap_or_default());
+
+        // QUIC 客户端配置：ALPN h3，允许 0-RTT
+        let mut tls_config = TlsConfig::client_config(self.http_args.cacert.as_deref(), self.http_args.insecure)?;
+        tls_config.alpn_protocols = vec![b"h3".to_vec()];
+        tls_config.enable_early_data = true;
+        let mut client_config = quinn::ClientConfig::new(Arc::new(QuicClientConfig::try_from(tls_config)?));
+        client_config.version(QUIC_VERSION);
+
+        let mut endpoint = None;
+        let (client, timing) =
+            Self::connect(&base, &mut endpoint, &client_config, true, &self.connection, &self.server_to_ui_tx).await?;
+
+        // 创建消息通道
+        let (ui_to_server_tx, mut ui_to_server_rx) = channel::<Message>(100);
+        let (control_tx, mut control_rx) = channel::<()>(1);
+
+        self.ui_to_server_tx = Some(ui_to_server_tx);
+        self.control_tx = Some(control_tx);
+        self.running = true;
+
+        let http_args = self.http_args.clone();
+        let connection = Arc::clone(&self.connection);
+        let server_to_ui_tx = self.server_to_ui_tx.clone();
+
+        // 启动请求任务：先按命令行参数发送一次请求，之后每次UI发送都重新请求
+        tokio::spawn(async move {
+            let mut current = Some(client);
+            let mut timing = Some(timing);
+            let mut body = default_body.clone();
+            // 0-RTT 被拒绝后，下一次连接使用完整握手重发请求
+            let mut early_data = true;
+            loop {
+                // 连接已断开时重新建立连接 (复用端点以尝试 0-RTT)
+                if connection.read().await.is_none() {
+                    current = None;
+                }
+                if current.is_none() {
+                    match Self::connect(&base, &mut endpoint, &client_config, early_data, &connection, &server_to_ui_tx).await {
+                        Ok((client, new_timing)) => {
+                            current = Some(client);
+                            timing = Some(new_timing);
+                            early_data = true;
+                        }
+                        Err(e) => {
+                            if let Some(ref server_to_ui_sender) = server_to_ui_tx {
+                                let _ = server_to_ui_sender
+                                    .send(Message::new_event(format!("Connect failed: {:#}", e), None))
+                                    .await;
+                            }
+                        }
+                    }
+                }
+
+                if let Some(ref mut client) = current {
+                    let connection_info = &client.connection_info;
+                    let request = match Self::build_request(&http_args) {
+                        Ok(request) => request,
+                        Err(e) => {
+                            notify(&server_to_ui_tx, connection_info, format!("Invalid request: {:#}", e)).await;
+                            break;
+                        }
+                    };
+                    if let Some(ref server_to_ui_sender) = server_to_ui_tx {
+                        let _ = server_to_ui_sender
+                            .send(Message::new_request(
+                                Self::describe_request(&request, &body),
+                                Some(connection_info.clone()),
+                            ))
+                            .await;
+                    }
+
+                    // 首个请求附带建立连接的耗时
+                    let request_timing = timing.take().unwrap_or_default();
+                    tokio::select! {
+                        result = Self::perform(&mut client.send_request, request, body.clone(), &client.quic, request_timing) => {
+                            // 请求已作为早期数据发出，握手完成后确认服务器是否接受
+                            if let Some(accepted) = client.zero_rtt.take() {
+                                if accepted.await {
+                                    notify(&server_to_ui_tx, &client.connection_info, "0-RTT accepted".to_string()).await;
+                                } else {
+                                    notify(
+                                        &server_to_ui_tx,
+                                        &client.connection_info,
+                                        "0-RTT rejected, retrying the request over 1-RTT".to_string(),
+                                    )
+                                    .await;
+                                    // 早期数据已丢失：关闭连接并等待清理完成，再以完整握手重发
+                                    if let Some(client) = current.take() {
+                                        client.quic.close(VarInt::from_u32(H3_NO_ERROR), b"");
+                                        let _ = client.driver.await;
+                                    }
+                                    early_data = false;
+                                    continue;
+                                }
+                            }
+                            let connection_info = &client.connection_info;
+                            match result {
+                                Ok((response, timing)) => {
+                                    if let Some(ref server_to_ui_sender) = server_to_ui_tx {
+                                        let _ = server_to_ui_sender
+                                            .send(Message::new_received(MessageType::Text(response), Some(connection_info.clone())))
+                                            .await;
+                                        let _ = server_to_ui_sender
+                                            .send(Message::new_received(MessageType::Timing(timing), Some(connection_info.clone())))
+                                            .await;
+                                    }
+                                }
+                                Err(e) => {
+                                    notify(&server_to_ui_tx, connection_info, format!("Request failed: {:#}", e)).await;
+                                }
+                            }
+                        }
+                        _ = control_rx.recv() => break,
+                    }
+                }
+
+                // 等待下一次发送，输入内容作为请求体 (为空时使用命令行参数中的请求体)
+                tokio::select! {
+                    message = ui_to_server_rx.recv() => {
+                        let Some(message) = message else {
+                            break;
+                        };
+                        body = message
+                            .content
+                            .to_bytes()
+                            .filter(|data| !data.is_empty())
+                            .unwrap_or_else(|| default_body.clone());
+                        message.mark_written();
+                    }
+                    _ = control_rx.recv() => break,
+                }
+            }
+
+            // 以 H3_NO_ERROR 关闭连接
+            if let Some(client) = current {
+                client.quic.close(VarInt::from_u32(H3_NO_ERROR), b"");
+            }
+        });
+
+        Ok(())
     }
-    
+
     async fn stop(&mut self) -> Result<()> {
-        todo!("Implement HTTP/3 client stop")
-    }
-    
-    async fn send_message(&mut self, message: MessageType, target: Option<String>) -> Result<()> {
-        todo!("Implement HTTP/3 client send message")
-    }
-    
-    fn get_receiver(&self) -> Option<Receiver<Message>> {
-        self.message_rx.clone()
+        if self.running {
+            // 发送停止信号
+            if let Some(ref control_tx) = self.control_tx {
+                let _ = control_tx.send(()).await;
+            }
+            self.running = false;
+            // 清理资源
+            self.control_tx = None;
+            self.ui_to_server_tx = None;
+        }
+        Ok(())
     }
-    
-    fn set_ui_sender(&mut self, sender: Sender<Message>) {
-        self.ui_tx = Some(sender);
+
+    fn get_ui_to_server_sender(&self) -> Option<Sender<Message>> {
+        self.ui_to_server_tx.clone()
     }
-    
-    fn is_running(&self) -> bool {
-        self.running
+
+    fn set_server_to_ui_sender(&mut self, sender: Sender<Message>) {
+        self.server_to_ui_tx = Some(sender);
     }
-    
+
     fn get_connections(&self) -> Vec<ConnectionInfo> {
-        todo!("Implement get_connections for HTTP/3 client")
+        self.connection
+            .try_read()
+            .ok()
+            .and_then(|connection| connection.clone())
+            .into_iter()
+            .collect()
     }
-    
+
     fn protocol_name(&self) -> &'static str {
         "HTTP/3 Client"
     }
-}
\ No newline at end of file
+}
+
+/// 注册 HTTP/3 服务器和客户端处理器
+pub fn register(registry: &mut HandlerRegistry) {
+    registry.register_protocol(ProtocolType::Http3, "http3", &[]);
+    registry.register(ProtocolType::Http3, AppMode::Server, "HTTP/3 Server", Http3ServerArgs::augment_args, |matches| {
+        let server_args = Http3ServerArgs::from_arg_matches(matches)?;
+        // 未指定证书时由处理器生成自签名证书
+        let tls_config = match server_args.tls_files() {
+            Some((cert_path, key_path)) => Some(TlsConfig::server_config_from_files(cert_path, key_path)?),
+            None => None,
+        };
+        let default_response = server_args.response.default_response();
+        Ok(Box::new(Http3ServerHandler::new(server_args.listen.local_addr(), default_response, tls_config)))
+    });
+    registry.register(ProtocolType::Http3, AppMode::Client, "HTTP/3 Client", HttpClientArgs::augment_args, |matches| {
+        Ok(Box::new(Http3ClientHandler::new(HttpClientArgs::from_arg_matches(matches)?)))
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use quinn::ApplicationClose;
+
+    #[test]
+    fn test_describe_close() {
+        let close = ConnectionError::ApplicationClosed(ApplicationClose {
+            error_code: VarInt::from_u32(0x10c),
+            reason: Bytes::from("cancelled"),
+        });
+        assert_eq!(
+            describe_close(&close),
+            "closed by server with application error 0x10c (H3_REQUEST_CANCELLED): cancelled"
+        );
+
+        let close = ConnectionError::ApplicationClosed(ApplicationClose {
+            error_code: VarInt::from_u32(0x42),
+            reason: Bytes::new(),
+        });
+        assert_eq!(describe_close(&close), "closed by server with application error 0x42 (unknown)");
+        assert_eq!(describe_close(&ConnectionError::LocallyClosed), "closed locally");
+    }
+
+    #[test]
+    fn test_save_self_signed_pem() {
+        // 每次保存都写入新文件，不覆盖其他服务器的证书
+        let first = save_self_signed_pem("first").unwrap();
+        let second = save_self_signed_pem("second").unwrap();
+        assert_ne!(first, second);
+        assert_eq!(std::fs::read_to_string(&first).unwrap(), "first");
+        assert_eq!(std::fs::read_to_string(&second).unwrap(), "second");
+        let _ = std::fs::remove_file(first);
+        let _ = std::fs::remove_file(second);
+    }
+}
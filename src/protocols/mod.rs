@@ -1,10 +1,14 @@
 pub mod common;
+pub mod file_transfer;
+pub mod framing;
+pub mod http;
+pub mod http2;
+pub mod http3;
+pub mod reconnect;
+pub mod registry;
 pub mod tcp;
-// pub mod udp;
-// pub mod websocket;
-// pub mod http;
-// pub mod http2;
-// pub mod http3;
+pub mod udp;
+pub mod websocket;
 
 // 重新导出常用的类型
-pub use common::{ProtocolHandler, Message, MessageDirection, MessageType, ConnectionInfo};
\ No newline at end of file
+pub use common::{ProtocolHandler, Message, MessageDirection, MessageType};
\ No newline at end of file
@@ -0,0 +1,287 @@
+use anyhow::Result;
+use clap::{Args, ValueEnum};
+use std::{collections::VecDeque, future::Future, time::Duration};
+use tokio::sync::mpsc::{Receiver, Sender};
+
+use crate::protocols::common::{ConnectionInfo, Message};
+
+/// 重连策略
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum ReconnectPolicy {
+    /// 不重连
+    #[default]
+    Off,
+    /// 固定间隔重连
+    Fixed {
+        /// 重连间隔
+        interval: Duration,
+        /// 最大重连次数 (None 表示不限制)
+        max_attempts: Option<u32>,
+    },
+    /// 指数退避重连
+    Backoff {
+        /// 初始间隔
+        initial: Duration,
+        /// 间隔上限
+        max: Duration,
+        /// 最大重连次数 (None 表示不限制)
+        max_attempts: Option<u32>,
+    },
+}
+
+impl ReconnectPolicy {
+    /// 计算第 attempt 次 (从 1 开始) 重连前的等待时间，不再重连时返回 None
+    pub fn delay(&self, attempt: u32) -> Option<Duration> {
+        let within = |max_attempts: &Option<u32>| max_attempts.is_none_or(|max| attempt <= max);
+
+        match self {
+            ReconnectPolicy::Off => None,
+            ReconnectPolicy::Fixed { interval, max_attempts } => within(max_attempts).then_some(*interval),
+            ReconnectPolicy::Backoff { initial, max, max_attempts } => {
+                if !within(max_attempts) {
+                    return None;
+                }
+                let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+                Some(initial.saturating_mul(factor).min(*max))
+            }
+        }
+    }
+}
+
+/// 客户端断线重连配置
+#[derive(Debug, Clone, Default)]
+pub struct ReconnectConfig {
+    /// 重连策略
+    pub policy: ReconnectPolicy,
+    /// 断线期间的发送处理方式
+    pub offline_send: OfflineSendMode,
+}
+
+/// 断线重连参数
+#[derive(Args, Debug, Clone)]
+pub struct ReconnectArgs {
+    /// 断线重连策略
+    #[arg(long, value_enum, default_value_t = ReconnectMode::Off)]
+    pub reconnect: ReconnectMode,
+
+    /// 重连间隔 (毫秒)，指数退避时为初始间隔
+    #[arg(long, default_value_t = 1000)]
+    pub reconnect_interval: u64,
+
+    /// 指数退避的最大重连间隔 (毫秒)
+    #[arg(long, default_value_t = 30000)]
+    pub reconnect_max_interval: u64,
+
+    /// 最大重连次数，不指定则一直重连
+    #[arg(long)]
+    pub reconnect_max_attempts: Option<u32>,
+
+    /// 断线期间发送数据的处理方式
+    #[arg(long, value_enum, default_value_t = OfflineSendMode::Reject)]
+    pub offline_send: OfflineSendMode,
+}
+
+impl ReconnectArgs {
+    /// 根据命令行参数生成重连配置
+    pub fn reconnect_config(&self) -> ReconnectConfig {
+        let policy = match self.reconnect {
+            ReconnectMode::Off => ReconnectPolicy::Off,
+            ReconnectMode::Fixed => ReconnectPolicy::Fixed {
+                interval: Duration::from_millis(self.reconnect_interval),
+                max_attempts: self.reconnect_max_attempts,
+            },
+            ReconnectMode::Backoff => ReconnectPolicy::Backoff {
+                initial: Duration::from_millis(self.reconnect_interval),
+                max: Duration::from_millis(self.reconnect_max_interval),
+                max_attempts: self.reconnect_max_attempts,
+            },
+        };
+
+        ReconnectConfig {
+            policy,
+            offline_send: self.offline_send,
+        }
+    }
+}
+
+/// 断线重连方式
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq)]
+pub enum ReconnectMode {
+    /// 不重连
+    Off,
+    /// 固定间隔重连
+    Fixed,
+    /// 指数退避重连
+    Backoff,
+}
+
+/// 断线期间发送数据的处理方式
+#[derive(ValueEnum, Debug, Clone, Copy, Default, PartialEq)]
+pub enum OfflineSendMode {
+    /// 缓存数据，重连成功后发送
+    Buffer,
+    /// 拒绝发送并提示错误
+    #[default]
+    Reject,
+}
+
+/// 一次连接会话的结束原因
+pub enum SessionEnd {
+    /// 用户主动停止
+    Stopped,
+    /// 连接丢失 (附带原因)
+    Lost(String),
+}
+
+/// 重连结果
+pub enum ReconnectOutcome<T> {
+    /// 重连成功
+    Connected(T),
+    /// 达到最大次数或未启用重连
+    GaveUp,
+    /// 等待期间收到停止信号
+    Stopped,
+}
+
+/// 断线重连执行器，供各客户端处理器复用
+pub struct Reconnector {
+    /// 重连配置
+    config: ReconnectConfig,
+    /// 断线期间缓存的待发送消息
+    pending: VecDeque<Message>,
+}
+
+impl Reconnector {
+    pub fn new(config: ReconnectConfig) -> Self {
+        Self {
+            config,
+            pending: VecDeque::new(),
+        }
+    }
+
+    /// 按策略反复尝试重连，期间继续处理UI发送和停止信号
+    pub async fn run<T, F, Fut>(
+        &mut self,
+        mut connect: F,
+        ui_to_server_rx: &mut Receiver<Message>,
+        control_rx: &mut Receiver<()>,
+        server_to_ui_tx: &Option<Sender<Message>>,
+        connection_info: &ConnectionInfo,
+    ) -> ReconnectOutcome<T>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        let mut attempt = 0;
+        loop {
+            attempt += 1;
+            let Some(delay) = self.config.policy.delay(attempt) else {
+                if attempt > 1 {
+                    let text = format!("Reconnect gave up after {} attempt(s)", attempt - 1);
+                    notify(server_to_ui_tx, connection_info, text).await;
+                }
+                return ReconnectOutcome::GaveUp;
+            };
+
+            let text = format!("Reconnect attempt {} in {} ms", attempt, delay.as_millis());
+            notify(server_to_ui_tx, connection_info, text).await;
+
+            // 等待重连间隔
+            let sleep = tokio::time::sleep(delay);
+            tokio::pin!(sleep);
+            loop {
+                tokio::select! {
+                    _ = &mut sleep => break,
+                    message = ui_to_server_rx.recv() => match message {
+                        Some(message) => self.hold(message, server_to_ui_tx, connection_info).await,
+                        None => return ReconnectOutcome::Stopped,
+                    },
+                    _ = control_rx.recv() => return ReconnectOutcome::Stopped,
+                }
+            }
+
+            // 发起连接
+            let connecting = connect();
+            tokio::pin!(connecting);
+            let result = loop {
+                tokio::select! {
+                    result = &mut connecting => break result,
+                    message = ui_to_server_rx.recv() => match message {
+                        Some(message) => self.hold(message, server_to_ui_tx, connection_info).await,
+                        None => return ReconnectOutcome::Stopped,
+                    },
+                    _ = control_rx.recv() => return ReconnectOutcome::Stopped,
+                }
+            };
+
+            match result {
+                Ok(connection) => {
+                    let text = format!("Reconnected after {} attempt(s)", attempt);
+                    notify(server_to_ui_tx, connection_info, text).await;
+                    return ReconnectOutcome::Connected(connection);
+                }
+                Err(e) => {
+                    let text = format!("Reconnect attempt {} failed: {}", attempt, e);
+                    notify(server_to_ui_tx, connection_info, text).await;
+                }
+            }
+        }
+    }
+
+    /// 处理断线期间UI发来的消息
+    async fn hold(&mut self, message: Message, server_to_ui_tx: &Option<Sender<Message>>, connection_info: &ConnectionInfo) {
+        let length = message.content.to_bytes().map(|data| data.len()).unwrap_or(0);
+        let text = match self.config.offline_send {
+            OfflineSendMode::Buffer => {
+                self.pending.push_back(message);
+                format!("Not connected, buffered {} bytes ({} queued)", length, self.pending.len())
+            }
+            OfflineSendMode::Reject => format!("Not connected, send rejected ({} bytes dropped)", length),
+        };
+        notify(server_to_ui_tx, connection_info, text).await;
+    }
+
+    /// 取出断线期间缓存的待发送消息
+    pub fn take_pending(&mut self) -> VecDeque<Message> {
+        std::mem::take(&mut self.pending)
+    }
+}
+
+/// 向UI发送连接事件
+pub async fn notify(server_to_ui_tx: &Option<Sender<Message>>, connection_info: &ConnectionInfo, text: String) {
+    if let Some(ref server_to_ui_sender) = server_to_ui_tx {
+        let _ = server_to_ui_sender
+            .send(Message::new_event(text, Some(connection_info.clone())))
+            .await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fixed_delay() {
+        let policy = ReconnectPolicy::Fixed {
+            interval: Duration::from_millis(500),
+            max_attempts: Some(2),
+        };
+        assert_eq!(policy.delay(1), Some(Duration::from_millis(500)));
+        assert_eq!(policy.delay(2), Some(Duration::from_millis(500)));
+        assert_eq!(policy.delay(3), None);
+    }
+
+    #[test]
+    fn test_backoff_delay_capped() {
+        let policy = ReconnectPolicy::Backoff {
+            initial: Duration::from_millis(100),
+            max: Duration::from_millis(1000),
+            max_attempts: None,
+        };
+        assert_eq!(policy.delay(1), Some(Duration::from_millis(100)));
+        assert_eq!(policy.delay(4), Some(Duration::from_millis(800)));
+        assert_eq!(policy.delay(5), Some(Duration::from_millis(1000)));
+        assert_eq!(policy.delay(64), Some(Duration::from_millis(1000)));
+        assert_eq!(ReconnectPolicy::Off.delay(1), None);
+    }
+}
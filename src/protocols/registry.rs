@@ -0,0 +1,275 @@
+use anyhow::{Context, Result};
+use clap::{error::ErrorKind, ArgMatches, Command, CommandFactory, FromArgMatches};
+use std::collections::HashMap;
+use std::ffi::OsString;
+use tokio::sync::mpsc::Sender;
+
+use crate::cli::args::{AppMode, Args, Cli, ProtocolType};
+use crate::protocols::common::{Message, ProtocolHandler};
+use crate::protocols::{http, http2, http3, tcp, udp, websocket};
+
+/// 协议处理器构造函数，根据子命令参数创建处理器 (不启动)
+pub type HandlerConstructor = fn(&ArgMatches) -> Result<Box<dyn ProtocolHandler + Send + Sync>>;
+
+/// 子命令参数定义函数，向子命令添加处理器的命令行参数 (通常为 clap::Args::augment_args)
+pub type ArgsAugmenter = fn(Command) -> Command;
+
+/// 协议处理器注册信息
+pub struct HandlerRegistration {
+    /// 显示名称 (如 "TCP Server")
+    pub name: &'static str,
+    /// 命令行参数定义
+    pub augment_args: ArgsAugmenter,
+    /// 构造函数
+    pub constructor: HandlerConstructor,
+}
+
+impl HandlerRegistration {
+    /// 发送区标题
+    pub fn send_title(&self) -> String {
+        format!("{} Send", self.name)
+    }
+
+    /// 接收区标题
+    pub fn receive_title(&self) -> String {
+        format!("{} Receive", self.name)
+    }
+}
+
+/// 协议子命令 (如 "nt tcp server")
+struct ProtocolCommand {
+    protocol: ProtocolType,
+    /// 子命令名称
+    name: &'static str,
+    /// 子命令别名
+    aliases: &'static [&'static str],
+}
+
+/// 协议模式的简写子命令 (如 "nt tcps")
+struct ShorthandCommand {
+    protocol: ProtocolType,
+    mode: AppMode,
+    /// 子命令名称
+    name: &'static str,
+    /// 子命令别名
+    alias: &'static str,
+}
+
+/// 协议处理器注册表，按协议类型和模式查找处理器，并由注册信息生成命令行子命令
+pub struct HandlerRegistry {
+    handlers: HashMap<(ProtocolType, AppMode), HandlerRegistration>,
+    protocols: Vec<ProtocolCommand>,
+    shorthands: Vec<ShorthandCommand>,
+}
+
+impl HandlerRegistry {
+    /// 创建空的注册表
+    pub fn new() -> Self {
+        Self {
+            handlers: HashMap::new(),
+            protocols: Vec::new(),
+            shorthands: Vec::new(),
+        }
+    }
+
+    /// 注册协议子命令，协议下的各模式作为它的子命令
+    pub fn register_protocol(&mut self, protocol: ProtocolType, name: &'static str, aliases: &'static [&'static str]) {
+        self.protocols.retain(|command| command.protocol != protocol);
+        self.protocols.push(ProtocolCommand { protocol, name, aliases });
+    }
+
+    /// 注册协议处理器及其命令行参数，同一协议和模式重复注册时覆盖之前的注册
+    pub fn register(
+        &mut self,
+        protocol: ProtocolType,
+        mode: AppMode,
+        name: &'static str,
+        augment_args: ArgsAugmenter,
+        constructor: HandlerConstructor,
+    ) {
+        self.handlers.insert((protocol, mode), HandlerRegistration { name, augment_args, constructor });
+    }
+
+    /// 注册协议模式的简写子命令
+    pub fn register_shorthand(&mut self, protocol: ProtocolType, mode: AppMode, name: &'static str, alias: &'static str) {
+        self.shorthands.push(ShorthandCommand { protocol, mode, name, alias });
+    }
+
+    /// 查找协议处理器的注册信息
+    pub fn get(&self, protocol: ProtocolType, mode: AppMode) -> Result<&HandlerRegistration> {
+        self.handlers
+            .get(&(protocol, mode))
+            .ok_or_else(|| anyhow::anyhow!("Unsupported protocol: {} {}", protocol, mode))
+    }
+
+    /// 生成完整的命令行定义，协议子命令及其参数来自各处理器的注册信息
+    pub fn command(&self) -> Command {
+        let mut command = Cli::command().subcommand_required(true).arg_required_else_help(true);
+        for protocol in &self.protocols {
+            let mut protocol_command = Command::new(protocol.name)
+                .aliases(protocol.aliases)
+                .about(format!("{} 协议", protocol.protocol))
+                .subcommand_required(true);
+            for mode in [AppMode::Server, AppMode::Client] {
+                if let Some(registration) = self.handlers.get(&(protocol.protocol, mode)) {
+                    let (name, alias) = mode_command(mode);
+                    // 参数结构体的文档会覆盖子命令说明，因此在添加参数之后设置
+                    let mode_command = (registration.augment_args)(Command::new(name).alias(alias))
+                        .about(format!("{} {}", protocol.protocol, mode_about(mode)));
+                    protocol_command = protocol_command.subcommand(mode_command);
+                }
+            }
+            command = command.subcommand(protocol_command);
+
+            let shorthands = self.shorthands.iter().filter(|shorthand| shorthand.protocol == protocol.protocol);
+            for shorthand in shorthands {
+                if let Some(registration) = self.handlers.get(&(shorthand.protocol, shorthand.mode)) {
+                    let shorthand_command = (registration.augment_args)(Command::new(shorthand.name).alias(shorthand.alias))
+                        .about(format!("{} {}简写", shorthand.protocol, mode_about(shorthand.mode)));
+                    command = command.subcommand(shorthand_command);
+                }
+            }
+        }
+        command
+    }
+
+    /// 解析命令行参数，参数错误时打印错误并退出
+    pub fn parse_args<I, T>(&self, itr: I) -> Args
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<OsString> + Clone,
+    {
+        self.try_parse_args(itr).unwrap_or_else(|e| e.exit())
+    }
+
+    /// 解析命令行参数
+    pub fn try_parse_args<I, T>(&self, itr: I) -> Result<Args, clap::Error>
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<OsString> + Clone,
+    {
+        let mut command = self.command();
+        let matches = command.try_get_matches_from_mut(itr)?;
+        let cli = Cli::from_arg_matches(&matches)?;
+        let Some((protocol, mode, protocol_matches)) = self.resolve(&matches) else {
+            return Err(command.error(ErrorKind::MissingSubcommand, "a protocol and mode are required"));
+        };
+        Ok(Args {
+            vertical_layout: cli.vertical_layout,
+            file_send: cli.file_send,
+            slots: cli.slots,
+            protocol,
+            mode,
+            protocol_matches: protocol_matches.clone(),
+        })
+    }
+
+    /// 根据子命令确定协议、模式和处理器参数
+    fn resolve<'a>(&self, matches: &'a ArgMatches) -> Option<(ProtocolType, AppMode, &'a ArgMatches)> {
+        let (name, sub_matches) = matches.subcommand()?;
+        if let Some(shorthand) = self.shorthands.iter().find(|shorthand| shorthand.name == name) {
+            return Some((shorthand.protocol, shorthand.mode, sub_matches));
+        }
+        let protocol = self.protocols.iter().find(|protocol| protocol.name == name)?;
+        let (mode_name, mode_matches) = sub_matches.subcommand()?;
+        let mode = [AppMode::Server, AppMode::Client]
+            .into_iter()
+            .find(|mode| mode_command(*mode).0 == mode_name)?;
+        Some((protocol.protocol, mode, mode_matches))
+    }
+
+    /// 按命令行参数创建并启动协议处理器
+    pub async fn create(
+        &self,
+        args: &Args,
+        server_to_ui_tx: Sender<Message>,
+    ) -> Result<Box<dyn ProtocolHandler + Send + Sync>> {
+        let registration = self.get(args.protocol, args.mode)?;
+        let mut handler = (registration.constructor)(&args.protocol_matches)
+            .with_context(|| format!("Failed to create {}", registration.name))?;
+        handler.set_server_to_ui_sender(server_to_ui_tx);
+        handler
+            .start()
+            .await
+            .with_context(|| format!("Failed to start {}", registration.name))?;
+        Ok(handler)
+    }
+}
+
+/// 模式子命令的名称和别名
+fn mode_command(mode: AppMode) -> (&'static str, &'static str) {
+    match mode {
+        AppMode::Server => ("server", "s"),
+        AppMode::Client => ("client", "c"),
+    }
+}
+
+/// 模式子命令的说明
+fn mode_about(mode: AppMode) -> &'static str {
+    match mode {
+        AppMode::Server => "服务器模式",
+        AppMode::Client => "客户端模式",
+    }
+}
+
+impl Default for HandlerRegistry {
+    /// 注册所有内置协议
+    fn default() -> Self {
+        let mut registry = Self::new();
+        tcp::register(&mut registry);
+        udp::register(&mut registry);
+        websocket::register(&mut registry);
+        http::register(&mut registry);
+        http2::register(&mut registry);
+        http3::register(&mut registry);
+        registry
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_registry_covers_all_protocols() {
+        let registry = HandlerRegistry::default();
+        let protocols = [
+            ProtocolType::Tcp,
+            ProtocolType::Udp,
+            ProtocolType::WebSocket,
+            ProtocolType::Http,
+            ProtocolType::Http2,
+            ProtocolType::Http3,
+        ];
+        for protocol in protocols {
+            for mode in [AppMode::Server, AppMode::Client] {
+                let registration = registry.get(protocol, mode).unwrap();
+                assert_eq!(registration.name, format!("{} {}", protocol, mode));
+            }
+        }
+        assert_eq!(registry.get(ProtocolType::Http2, AppMode::Client).unwrap().send_title(), "HTTP/2 Client Send");
+    }
+
+    #[test]
+    fn test_protocol_arguments() {
+        let registry = HandlerRegistry::default();
+        let parse = |argv: &[&str]| registry.try_parse_args(argv);
+
+        let args = parse(&["nt", "tcps", "8000", "--framing", "line"]).unwrap();
+        assert_eq!((args.protocol, args.mode), (ProtocolType::Tcp, AppMode::Server));
+        let args = parse(&["nt", "ws", "c", "127.0.0.1:8000", "--reconnect", "fixed"]).unwrap();
+        assert_eq!((args.protocol, args.mode), (ProtocolType::WebSocket, AppMode::Client));
+        let args = parse(&["nt", "http2", "server", "8000", "--tls-cert", "a.pem", "--tls-key", "b.pem"]).unwrap();
+        assert_eq!((args.protocol, args.mode), (ProtocolType::Http2, AppMode::Server));
+        assert!(parse(&["nt", "udp", "server", "8000", "--peer-timeout", "5", "--send-file", "f.bin"]).is_ok());
+
+        // 每个处理器只接受自己注册的参数
+        let error = parse(&["nt", "tcp", "server", "8000", "--peer-timeout", "5"]).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::UnknownArgument);
+        assert!(parse(&["nt", "udp", "client", "9000", "8000", "--reconnect", "fixed"]).is_err());
+        assert!(parse(&["nt", "udps", "8000", "--framing", "line"]).is_err());
+        assert!(parse(&["nt", "http", "server", "8000", "--tls-cert", "a.pem", "--tls-key", "b.pem"]).is_err());
+        assert!(parse(&["nt", "ws", "server", "8000", "--response-body", "ok"]).is_err());
+        assert!(parse(&["nt", "tcp"]).is_err());
+    }
+}
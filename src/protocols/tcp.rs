@@ -1,17 +1,52 @@
 use anyhow::Result;
 use async_trait::async_trait;
 use bytes::Bytes;
-use std::{collections::HashMap, net::SocketAddr, sync::Arc};
+use clap::{Args, FromArgMatches};
+use std::{
+    collections::{HashMap, VecDeque},
+    net::SocketAddr,
+    sync::Arc,
+};
 use tokio::{
     io::{AsyncReadExt, AsyncWriteExt},
-    net::{TcpListener, TcpStream},
+    net::{tcp::OwnedWriteHalf, TcpListener, TcpSocket, TcpStream},
     sync::{
-        mpsc::{channel, Receiver, Sender},
+        mpsc::{channel, error::TrySendError, Receiver, Sender},
         RwLock,
     },
 };
 
-use crate::protocols::common::{ConnectionInfo, Message, MessageDirection, MessageType, ProtocolHandler};
+use crate::cli::args::{AppMode, EndpointArgs, ListenArgs, ProtocolType};
+use crate::protocols::common::{ConnectionInfo, Message, MessageDirection, MessageType, ProtocolHandler, WriteAck};
+use crate::protocols::framing::{FrameDecoder, Framing, FramingArgs, FramingConfig};
+use crate::protocols::reconnect::{notify, ReconnectArgs, ReconnectConfig, ReconnectOutcome, Reconnector, SessionEnd};
+use crate::protocols::registry::HandlerRegistry;
+
+/// TCP 服务器参数
+#[derive(Args, Debug, Clone)]
+pub struct TcpServerArgs {
+    #[command(flatten)]
+    pub listen: ListenArgs,
+
+    /// 分帧参数
+    #[command(flatten)]
+    pub framing: FramingArgs,
+}
+
+/// TCP 客户端参数
+#[derive(Args, Debug, Clone)]
+pub struct TcpClientArgs {
+    #[command(flatten)]
+    pub endpoint: EndpointArgs,
+
+    /// 断线重连参数
+    #[command(flatten)]
+    pub reconnect: ReconnectArgs,
+
+    /// 分帧参数
+    #[command(flatten)]
+    pub framing: FramingArgs,
+}
 
 /// TCP 服务器处理器
 pub struct TcpServerHandler {
@@ -19,12 +54,12 @@ pub struct TcpServerHandler {
     local_addr: SocketAddr,
     /// 连接的客户端
     clients: Arc<RwLock<HashMap<String, TcpClientInfo>>>,
+    /// 分帧配置
+    framing: FramingConfig,
     /// 控制通道 (用于停止服务器)
     control_tx: Option<Sender<()>>,
     /// UI到服务器发送通道
     ui_to_server_tx: Option<Sender<Message>>,
-    /// UI到服务器接收通道
-    ui_to_server_rx: Option<Receiver<Message>>,
     /// 服务器到UI发送通道
     server_to_ui_tx: Option<Sender<Message>>,
     /// 运行状态
@@ -36,41 +71,171 @@ struct TcpClientInfo {
     /// 远程地址
     addr: SocketAddr,
     /// 发送通道
-    tx: Sender<Bytes>,
+    tx: ClientSender,
 }
 
+/// 客户端发送通道 (数据和写出确认)
+type ClientSender = Sender<(Bytes, Option<WriteAck>)>;
+
 impl TcpServerHandler {
     /// 创建新的TCP服务器处理器
     pub fn new(local_addr: SocketAddr) -> Self {
         Self {
             local_addr,
             clients: Arc::new(RwLock::new(HashMap::new())),
+            framing: FramingConfig::default(),
             control_tx: None,
             ui_to_server_tx: None,
-            ui_to_server_rx: None,
             server_to_ui_tx: None,
             running: false,
         }
     }
+
+    /// 设置分帧配置
+    pub fn set_framing(&mut self, framing: FramingConfig) {
+        self.framing = framing;
+    }
+}
+
+/// 按接收分帧方式解码读取到的数据，每个完整帧作为一条消息发送到UI，并更新未完成的帧
+async fn forward_frames(
+    decoder: &mut FrameDecoder,
+    data: &[u8],
+    server_to_ui_tx: &Option<Sender<Message>>,
+    connection_info: &ConnectionInfo,
+) {
+    let frames = decoder.decode(data);
+    let Some(ref server_to_ui_sender) = server_to_ui_tx else {
+        return;
+    };
+    for frame in frames {
+        let message = match frame {
+            Ok(frame) => Message::new_received(MessageType::Binary(frame), Some(connection_info.clone())),
+            Err(e) => Message::new_event(format!("Framing error: {}", e), Some(connection_info.clone())),
+        };
+        let _ = server_to_ui_sender.send(message).await;
+    }
+    if let Some((preview, total)) = decoder.partial_update() {
+        let partial = MessageType::Partial { preview, total };
+        let _ = server_to_ui_sender.send(Message::new_received(partial, Some(connection_info.clone()))).await;
+    }
+}
+
+/// 连接断开时丢弃未完成的帧并通知UI
+async fn discard_partial(
+    decoder: &FrameDecoder,
+    server_to_ui_tx: &Option<Sender<Message>>,
+    connection_info: &ConnectionInfo,
+) {
+    let pending = decoder.pending().len();
+    if pending == 0 {
+        return;
+    }
+    if let Some(ref server_to_ui_sender) = server_to_ui_tx {
+        let event = format!("Connection closed with {} bytes of incomplete frame", pending);
+        let _ = server_to_ui_sender.send(Message::new_event(event, Some(connection_info.clone()))).await;
+        let partial = MessageType::Partial {
+            preview: Bytes::new(),
+            total: 0,
+        };
+        let _ = server_to_ui_sender.send(Message::new_received(partial, Some(connection_info.clone()))).await;
+    }
+}
+
+/// 从客户端列表中移除并通知UI连接断开
+///
+/// 读写任务都可能发现连接断开，只有实际移除了客户端的一方发送通知
+async fn remove_client(
+    clients: &RwLock<HashMap<String, TcpClientInfo>>,
+    connection_info: &ConnectionInfo,
+    error: Option<String>,
+    server_to_ui_tx: &Option<Sender<Message>>,
+) {
+    if clients.write().await.remove(&connection_info.connection_id).is_none() {
+        return;
+    }
+    if let Some(ref server_to_ui_sender) = server_to_ui_tx {
+        if let Some(text) = error {
+            let _ = server_to_ui_sender
+                .send(Message::new_event(text, Some(connection_info.clone())))
+                .await;
+        }
+        let _ = server_to_ui_sender
+            .send(Message::new_received(MessageType::ClientDisconnected, Some(connection_info.clone())))
+            .await;
+    }
+}
+
+/// 按发送分帧方式封装消息内容，非数据类消息返回 None
+fn encode_content(framing: &Framing, content: &MessageType) -> Option<Result<Bytes>> {
+    let data = content.to_bytes()?;
+    Some(framing.encode(&data).map_err(|e| anyhow::anyhow!("Send failed: {}", e)))
+}
+
+/// 将数据发送给指定客户端，未指定客户端时广播给所有已连接的客户端
+async fn send_to_clients(
+    clients: &RwLock<HashMap<String, TcpClientInfo>>,
+    data: Bytes,
+    ack: Option<&WriteAck>,
+    target: Option<&str>,
+) -> Result<()> {
+    // 先复制发送通道再释放锁，避免写入阻塞时影响新连接
+    let targets: Vec<(String, ClientSender)> = {
+        let clients = clients.read().await;
+        match target {
+            Some(client_id) => {
+                let client = clients
+                    .get(client_id)
+                    .ok_or_else(|| anyhow::anyhow!("Send failed: client {} is not connected", client_id))?;
+                vec![(client_id.to_string(), client.tx.clone())]
+            }
+            None => clients.iter().map(|(client_id, client)| (client_id.clone(), client.tx.clone())).collect(),
+        }
+    };
+    if targets.is_empty() {
+        anyhow::bail!("Send failed: no clients connected");
+    }
+
+    // 不等待发送队列，避免写入缓慢的客户端阻塞接受新连接和其他客户端
+    let mut disconnected = Vec::new();
+    let mut full = Vec::new();
+    for (client_id, tx) in targets {
+        match tx.try_send((data.clone(), ack.cloned())) {
+            Ok(()) => {}
+            Err(TrySendError::Full(_)) => full.push(client_id),
+            Err(TrySendError::Closed(_)) => disconnected.push(client_id),
+        }
+    }
+    let mut errors = Vec::new();
+    if !disconnected.is_empty() {
+        errors.push(format!("{} disconnected", disconnected.join(", ")));
+    }
+    if !full.is_empty() {
+        errors.push(format!("send queue of {} is full, data dropped", full.join(", ")));
+    }
+    if !errors.is_empty() {
+        anyhow::bail!("Send failed: {}", errors.join("; "));
+    }
+    Ok(())
 }
 
 #[async_trait]
 impl ProtocolHandler for TcpServerHandler {
     async fn start(&mut self) -> Result<()> {
         // 创建消息通道
-        let (ui_to_server_tx, ui_to_server_rx) = channel::<Message>(100);
+        let (ui_to_server_tx, mut ui_to_server_rx) = channel::<Message>(100);
         let (control_tx, mut control_rx) = channel::<()>(1);
 
+        // 绑定监听地址
+        let listener = TcpListener::bind(self.local_addr).await?;
+
         self.ui_to_server_tx = Some(ui_to_server_tx);
-        self.ui_to_server_rx = Some(ui_to_server_rx);
         self.control_tx = Some(control_tx);
         self.running = true;
 
-        // 绑定监听地址
-        let listener = TcpListener::bind(self.local_addr).await?;
-
         let clients = Arc::clone(&self.clients);
         let server_to_ui_tx = self.server_to_ui_tx.clone();
+        let framing = self.framing.clone();
 
         // 启动服务器监听任务
         tokio::spawn(async move {
@@ -82,7 +247,7 @@ impl ProtocolHandler for TcpServerHandler {
                             Ok((stream, addr)) => {
                                 // 为每个客户端创建处理任务
                                 let client_id = addr.to_string();
-                                let (client_tx, mut client_rx) = channel::<Bytes>(100);
+                                let (client_tx, mut client_rx) = channel::<(Bytes, Option<WriteAck>)>(100);
 
                                 // 保存客户端信息
                                 {
@@ -103,6 +268,7 @@ impl ProtocolHandler for TcpServerHandler {
                                            remote_addr: addr,
                                            connection_id: client_id.clone(),
                                         }),
+                                        ack: None,
                                     }).await;
                                 }
 
@@ -112,92 +278,95 @@ impl ProtocolHandler for TcpServerHandler {
                                 let server_to_ui_tx_for_read = server_to_ui_tx.clone();
 
                                 // 处理客户端读取任务
-                                let read_client_id = client_id.clone();
-                                tokio::spawn(async move {
+                                let read_connection_info = ConnectionInfo {
+                                    remote_addr: addr,
+                                    connection_id: client_id.clone(),
+                                };
+                                let write_connection_info = read_connection_info.clone();
+                                let mut decoder = FrameDecoder::new(framing.receive.clone());
+                                let read_task = tokio::spawn(async move {
                                     let mut buffer = vec![0u8; 4096];
-                                    loop {
+                                    let error = loop {
                                         match read_half.read(&mut buffer).await {
-                                            Ok(0) => {
-                                                // 从客户端列表中移除
-                                                {
-                                                    let mut clients_lock = clients_for_read.write().await;
-                                                    clients_lock.remove(&read_client_id);
-                                                }
-
-                                                // 通知UI连接断开
-                                                if let Some(ref server_to_ui_sender) = server_to_ui_tx_for_read {
-                                                    let _ = server_to_ui_sender.send(Message {
-                                                        direction: MessageDirection::Received,
-                                                        content: MessageType::ClientDisconnected,
-                                                        timestamp: chrono::Local::now(),
-                                                        connection_info: Some(ConnectionInfo {
-                                                            remote_addr: addr,
-                                                            connection_id: read_client_id.clone(),
-                                                        }),
-                                                    }).await;
-                                                }
-                                                break;
-                                            }
+                                            Ok(0) => break None,
                                             Ok(n) => {
-                                                // 接收到数据
-                                                let data = &buffer[..n];
-                                                let message_content = String::from_utf8_lossy(data).to_string();
-
-                                                // 发送到UI
-                                                if let Some(ref server_to_ui_sender) = server_to_ui_tx_for_read {
-                                                    let _ = server_to_ui_sender.send(Message {
-                                                        direction: MessageDirection::Received,
-                                                        content: MessageType::Text(message_content.clone()),
-                                                        timestamp: chrono::Local::now(),
-                                                        connection_info: Some(ConnectionInfo {
-                                                            remote_addr: addr,
-                                                            connection_id: read_client_id.clone(),
-                                                        }),
-                                                    }).await;
-                                                }
-                                            }
-                                            Err(e) => {
-                                                println!("读取客户端 {} 数据时出错: {}", addr, e);
-                                                break;
+                                                // 接收到数据，按分帧方式解码后传给UI (不分帧时按原始字节)
+                                                forward_frames(
+                                                    &mut decoder,
+                                                    &buffer[..n],
+                                                    &server_to_ui_tx_for_read,
+                                                    &read_connection_info,
+                                                ).await;
                                             }
+                                            Err(e) => break Some(format!("Read from {} failed: {}", addr, e)),
                                         }
-                                    }
+                                    };
+
+                                    // 连接关闭或读取出错时丢弃未完成的帧
+                                    discard_partial(&decoder, &server_to_ui_tx_for_read, &read_connection_info).await;
+
+                                    // 从客户端列表中移除并通知UI连接断开
+                                    remove_client(&clients_for_read, &read_connection_info, error, &server_to_ui_tx_for_read).await;
 
                                     drop(read_half);
                                 });
 
                                 // 处理客户端写入任务
+                                let clients_for_write = Arc::clone(&clients);
+                                let server_to_ui_tx_for_write = server_to_ui_tx.clone();
                                 tokio::spawn(async move {
-                                    while let Some(data) = client_rx.recv().await {
+                                    while let Some((data, ack)) = client_rx.recv().await {
+                                        // 发送方已取消的数据不再写出
+                                        if ack.as_ref().is_some_and(WriteAck::is_cancelled) {
+                                            continue;
+                                        }
                                         if let Err(e) = write_half.write_all(&data).await {
-                                            println!("向客户端 {} 发送数据时出错: {}", addr, e);
+                                            // 写入出错时停止读取，移除客户端并通知UI
+                                            read_task.abort();
+                                            let error = format!("Write to {} failed: {}", addr, e);
+                                            remove_client(&clients_for_write, &write_connection_info, Some(error), &server_to_ui_tx_for_write).await;
                                             break;
                                         }
+                                        if let Some(ack) = ack {
+                                            ack.mark_written();
+                                        }
                                     }
 
                                     drop(write_half);
                                 });
                             }
                             Err(e) => {
-                                println!("接受客户端连接时出错: {}", e);
+                                if let Some(ref server_to_ui_sender) = server_to_ui_tx {
+                                    let _ = server_to_ui_sender
+                                        .send(Message::new_event(format!("Accept failed: {}", e), None))
+                                        .await;
+                                }
                             }
                         }
                     }
 
-                    // 处理停止信号 - 明确检查是否收到了信号
-                    result = control_rx.recv() => {
-                        match result {
-                            Some(_) => {
-                                println!("TCP 服务器收到停止信号，正在停止...");
-                                break;
-                            }
-                            None => {
-                                // 发送方已关闭，这通常意味着服务器应该停止
-                                println!("TCP 服务器控制通道已关闭，正在停止...");
-                                break;
+                    // 将UI发送的数据转发给指定客户端，未指定时广播给所有客户端
+                    message = ui_to_server_rx.recv() => {
+                        let Some(message) = message else {
+                            break;
+                        };
+                        let Some(data) = encode_content(&framing.send, &message.content) else {
+                            continue;
+                        };
+                        let target = message.connection_info.map(|info| info.connection_id);
+                        let result = match data {
+                            Ok(data) => send_to_clients(&clients, data, message.ack.as_ref(), target.as_deref()).await,
+                            Err(e) => Err(e),
+                        };
+                        if let Err(e) = result {
+                            if let Some(ref server_to_ui_sender) = server_to_ui_tx {
+                                let _ = server_to_ui_sender.send(Message::new_event(e.to_string(), None)).await;
                             }
                         }
                     }
+
+                    // 收到停止信号或控制通道关闭时停止服务器
+                    _ = control_rx.recv() => break,
                 }
             }
         });
@@ -218,10 +387,6 @@ impl ProtocolHandler for TcpServerHandler {
         Ok(())
     }
 
-    async fn send_message(&mut self, message: MessageType, target: Option<String>) -> Result<()> {
-        todo!("Implement TCP server send message")
-    }
-
     fn get_ui_to_server_sender(&self) -> Option<Sender<Message>> {
         self.ui_to_server_tx.clone()
     }
@@ -230,12 +395,23 @@ impl ProtocolHandler for TcpServerHandler {
         self.server_to_ui_tx = Some(sender);
     }
 
-    fn is_running(&self) -> bool {
-        self.running
+    fn get_connections(&self) -> Vec<ConnectionInfo> {
+        self.clients
+            .try_read()
+            .map(|clients| {
+                clients
+                    .iter()
+                    .map(|(client_id, info)| ConnectionInfo {
+                        remote_addr: info.addr,
+                        connection_id: client_id.clone(),
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
     }
 
-    fn get_connections(&self) -> Vec<ConnectionInfo> {
-        todo!("Implement get_connections for TCP server")
+    fn supports_broadcast(&self) -> bool {
+        true
     }
 
     fn protocol_name(&self) -> &'static str {
@@ -249,12 +425,14 @@ pub struct TcpClientHandler {
     local_addr: SocketAddr,
     /// 远程服务器地址
     remote_addr: SocketAddr,
-    /// TCP 流
-    stream: Option<TcpStream>,
+    /// 当前连接信息 (未连接时为 None)
+    connection: Arc<RwLock<Option<ConnectionInfo>>>,
+    /// 断线重连配置
+    reconnect: ReconnectConfig,
+    /// 分帧配置
+    framing: FramingConfig,
     /// 控制通道 (用于停止客户端)
     control_tx: Option<Sender<()>>,
-    /// 消息接收通道
-    ui_to_server_rx: Option<Receiver<Message>>,
     /// 消息发送通道
     ui_to_server_tx: Option<Sender<Message>>,
     /// UI消息发送通道
@@ -269,28 +447,218 @@ impl TcpClientHandler {
         Self {
             local_addr,
             remote_addr,
-            stream: None,
+            connection: Arc::new(RwLock::new(None)),
+            reconnect: ReconnectConfig::default(),
+            framing: FramingConfig::default(),
             control_tx: None,
-            ui_to_server_rx: None,
             ui_to_server_tx: None,
             server_to_ui_tx: None,
             running: false,
         }
     }
+
+    /// 设置断线重连配置
+    pub fn set_reconnect_config(&mut self, reconnect: ReconnectConfig) {
+        self.reconnect = reconnect;
+    }
+
+    /// 设置分帧配置
+    pub fn set_framing(&mut self, framing: FramingConfig) {
+        self.framing = framing;
+    }
+
+    /// 绑定本地地址并连接到远程服务器
+    async fn connect(local_addr: SocketAddr, remote_addr: SocketAddr) -> Result<TcpStream> {
+        let socket = if remote_addr.is_ipv4() {
+            TcpSocket::new_v4()?
+        } else {
+            TcpSocket::new_v6()?
+        };
+        socket.set_reuseaddr(true)?;
+        socket.bind(local_addr)?;
+        let stream = socket.connect(remote_addr).await?;
+        Ok(stream)
+    }
+
+    /// 运行一次连接会话，直到连接断开或收到停止信号
+    async fn run_session(
+        stream: TcpStream,
+        pending: VecDeque<Message>,
+        framing: &FramingConfig,
+        ui_to_server_rx: &mut Receiver<Message>,
+        control_rx: &mut Receiver<()>,
+        server_to_ui_tx: &Option<Sender<Message>>,
+        connection_info: &ConnectionInfo,
+    ) -> SessionEnd {
+        let (mut read_half, mut write_half) = stream.into_split();
+        let mut buffer = vec![0u8; 4096];
+        let mut decoder = FrameDecoder::new(framing.receive.clone());
+
+        // 先发送断线期间缓存的数据
+        for message in pending {
+            if let Err(e) = Self::write_message(&mut write_half, &framing.send, &message, server_to_ui_tx).await {
+                return SessionEnd::Lost(e.to_string());
+            }
+        }
+
+        loop {
+            tokio::select! {
+                // 读取服务端数据
+                result = read_half.read(&mut buffer) => {
+                    match result {
+                        Ok(0) => {
+                            discard_partial(&decoder, server_to_ui_tx, connection_info).await;
+                            return SessionEnd::Lost("connection closed by peer".to_string());
+                        }
+                        Ok(n) => forward_frames(&mut decoder, &buffer[..n], server_to_ui_tx, connection_info).await,
+                        Err(e) => {
+                            discard_partial(&decoder, server_to_ui_tx, connection_info).await;
+                            return SessionEnd::Lost(e.to_string());
+                        }
+                    }
+                }
+
+                // 将UI发送的数据写入套接字
+                message = ui_to_server_rx.recv() => {
+                    match message {
+                        Some(message) => {
+                            if let Err(e) = Self::write_message(&mut write_half, &framing.send, &message, server_to_ui_tx).await {
+                                return SessionEnd::Lost(e.to_string());
+                            }
+                        }
+                        None => return SessionEnd::Stopped,
+                    }
+                }
+
+                // 处理停止信号
+                _ = control_rx.recv() => {
+                    let _ = write_half.shutdown().await;
+                    return SessionEnd::Stopped;
+                }
+            }
+        }
+    }
+
+    /// 按发送分帧方式封装并写入消息，无法封装时通知UI并跳过 (只返回写入错误)
+    async fn write_message(
+        write_half: &mut OwnedWriteHalf,
+        framing: &Framing,
+        message: &Message,
+        server_to_ui_tx: &Option<Sender<Message>>,
+    ) -> std::io::Result<()> {
+        // 发送方已取消的消息不再写出
+        if message.is_cancelled() {
+            return Ok(());
+        }
+        match encode_content(framing, &message.content) {
+            Some(Ok(data)) => {
+                write_half.write_all(&data).await?;
+                message.mark_written();
+                Ok(())
+            }
+            Some(Err(e)) => {
+                if let Some(ref server_to_ui_sender) = server_to_ui_tx {
+                    let _ = server_to_ui_sender.send(Message::new_event(e.to_string(), None)).await;
+                }
+                Ok(())
+            }
+            None => Ok(()),
+        }
+    }
 }
 
 #[async_trait]
 impl ProtocolHandler for TcpClientHandler {
     async fn start(&mut self) -> Result<()> {
-        todo!("Implement TCP client start")
-    }
+        // 先建立连接，连接失败时直接返回错误
+        let stream = Self::connect(self.local_addr, self.remote_addr).await?;
 
-    async fn stop(&mut self) -> Result<()> {
-        todo!("Implement TCP client stop")
-    }
+        // 创建消息通道
+        let (ui_to_server_tx, mut ui_to_server_rx) = channel::<Message>(100);
+        let (control_tx, mut control_rx) = channel::<()>(1);
+
+        self.ui_to_server_tx = Some(ui_to_server_tx);
+        self.control_tx = Some(control_tx);
+        self.running = true;
+
+        let connection_info = ConnectionInfo {
+            remote_addr: self.remote_addr,
+            connection_id: self.remote_addr.to_string(),
+        };
+
+        let (local_addr, remote_addr) = (self.local_addr, self.remote_addr);
+        let connection = Arc::clone(&self.connection);
+        let server_to_ui_tx = self.server_to_ui_tx.clone();
+        let framing = self.framing.clone();
+        let mut reconnector = Reconnector::new(self.reconnect.clone());
+
+        // 启动客户端读写任务
+        tokio::spawn(async move {
+            let mut stream = stream;
+            loop {
+                // 记录连接状态并通知UI已连接
+                *connection.write().await = Some(connection_info.clone());
+                if let Some(ref server_to_ui_sender) = server_to_ui_tx {
+                    let _ = server_to_ui_sender
+                        .send(Message::new_received(MessageType::ClientConnected, Some(connection_info.clone())))
+                        .await;
+                }
+
+                let end = Self::run_session(
+                    stream,
+                    reconnector.take_pending(),
+                    &framing,
+                    &mut ui_to_server_rx,
+                    &mut control_rx,
+                    &server_to_ui_tx,
+                    &connection_info,
+                )
+                .await;
+
+                // 清理连接状态并通知UI连接断开
+                *connection.write().await = None;
+                if let Some(ref server_to_ui_sender) = server_to_ui_tx {
+                    let _ = server_to_ui_sender
+                        .send(Message::new_received(MessageType::ClientDisconnected, Some(connection_info.clone())))
+                        .await;
+                }
+
+                let SessionEnd::Lost(reason) = end else {
+                    break;
+                };
+                notify(&server_to_ui_tx, &connection_info, format!("Connection lost: {}", reason)).await;
+
+                // 按重连策略尝试重新连接
+                let outcome = reconnector
+                    .run(
+                        || Self::connect(local_addr, remote_addr),
+                        &mut ui_to_server_rx,
+                        &mut control_rx,
+                        &server_to_ui_tx,
+                        &connection_info,
+                    )
+                    .await;
+                match outcome {
+                    ReconnectOutcome::Connected(new_stream) => stream = new_stream,
+                    ReconnectOutcome::GaveUp | ReconnectOutcome::Stopped => break,
+                }
+            }
+        });
 
-    async fn send_message(&mut self, message: MessageType, target: Option<String>) -> Result<()> {
-        todo!("Implement TCP client send message")
+        Ok(())
+    }
+    async fn stop(&mut self) -> Result<()> {
+        if self.running {
+            // 发送停止信号
+            if let Some(ref control_tx) = self.control_tx {
+                let _ = control_tx.send(()).await;
+            }
+            self.running = false;
+            // 清理资源
+            self.control_tx = None;
+            self.ui_to_server_tx = None;
+        }
+        Ok(())
     }
 
     fn get_ui_to_server_sender(&self) -> Option<Sender<Message>> {
@@ -301,15 +669,35 @@ impl ProtocolHandler for TcpClientHandler {
         self.server_to_ui_tx = Some(sender);
     }
 
-    fn is_running(&self) -> bool {
-        self.running
-    }
-
     fn get_connections(&self) -> Vec<ConnectionInfo> {
-        todo!("Implement get_connections for TCP client")
+        self.connection
+            .try_read()
+            .map(|connection| connection.iter().cloned().collect())
+            .unwrap_or_default()
     }
 
     fn protocol_name(&self) -> &'static str {
         "TCP Client"
     }
 }
+
+/// 注册 TCP 服务器和客户端处理器
+pub fn register(registry: &mut HandlerRegistry) {
+    registry.register_protocol(ProtocolType::Tcp, "tcp", &[]);
+    registry.register(ProtocolType::Tcp, AppMode::Server, "TCP Server", TcpServerArgs::augment_args, |matches| {
+        let server_args = TcpServerArgs::from_arg_matches(matches)?;
+        let mut handler = TcpServerHandler::new(server_args.listen.local_addr());
+        handler.set_framing(server_args.framing.framing_config());
+        Ok(Box::new(handler))
+    });
+    registry.register(ProtocolType::Tcp, AppMode::Client, "TCP Client", TcpClientArgs::augment_args, |matches| {
+        let client_args = TcpClientArgs::from_arg_matches(matches)?;
+        let endpoint = &client_args.endpoint;
+        let mut handler = TcpClientHandler::new(endpoint.local_addr(), endpoint.remote_addr());
+        handler.set_reconnect_config(client_args.reconnect.reconnect_config());
+        handler.set_framing(client_args.framing.framing_config());
+        Ok(Box::new(handler))
+    });
+    registry.register_shorthand(ProtocolType::Tcp, AppMode::Server, "tcp-server", "tcps");
+    registry.register_shorthand(ProtocolType::Tcp, AppMode::Client, "tcp-client", "tcpc");
+}
@@ -1,15 +1,47 @@
 use anyhow::Result;
 use async_trait::async_trait;
 use bytes::Bytes;
-use std::{collections::HashMap, net::SocketAddr, sync::Arc};
+use clap::{Args, FromArgMatches};
+use std::{collections::HashMap, io::ErrorKind, net::SocketAddr, sync::Arc, time::Duration};
 use tokio::{
     net::UdpSocket,
-    sync::{mpsc::{Receiver, Sender, channel}, RwLock},
+    sync::{mpsc::{Sender, channel}, RwLock},
+    time::Instant,
 };
 
+use crate::cli::args::{AppMode, EndpointArgs, ListenArgs, ProtocolType};
 use crate::protocols::common::{
-    ConnectionInfo, Message, MessageDirection, MessageType, ProtocolHandler,
+    ConnectionInfo, Message, MessageType, ProtocolHandler,
 };
+use crate::protocols::registry::HandlerRegistry;
+
+/// UDP 对端默认空闲超时 (秒)
+pub const DEFAULT_PEER_TIMEOUT_SECS: u64 = 60;
+
+/// UDP 服务器参数
+#[derive(Args, Debug, Clone)]
+pub struct UdpServerArgs {
+    #[command(flatten)]
+    pub listen: ListenArgs,
+
+    /// 对端空闲超时 (秒)，超时后对端被标记为 stale
+    #[arg(long, default_value_t = DEFAULT_PEER_TIMEOUT_SECS, value_parser = clap::value_parser!(u64).range(1..))]
+    pub peer_timeout: u64,
+}
+
+impl UdpServerArgs {
+    /// 对端空闲超时
+    pub fn peer_timeout(&self) -> Duration {
+        Duration::from_secs(self.peer_timeout)
+    }
+}
+
+/// UDP 客户端参数
+#[derive(Args, Debug, Clone)]
+pub struct UdpClientArgs {
+    #[command(flatten)]
+    pub endpoint: EndpointArgs,
+}
 
 /// UDP 服务器处理器
 pub struct UdpServerHandler {
@@ -17,31 +49,41 @@ pub struct UdpServerHandler {
     local_addr: SocketAddr,
     /// UDP 套接字
     socket: Option<Arc<UdpSocket>>,
-    /// 已知客户端
-    clients: Arc<RwLock<HashMap<SocketAddr, String>>>,
+    /// 已知对端 (按源地址视为伪连接)
+    clients: Arc<RwLock<HashMap<SocketAddr, UdpPeerInfo>>>,
+    /// 对端空闲超时时间，超时后标记为 stale
+    idle_timeout: Duration,
     /// 控制通道 (用于停止服务器)
     control_tx: Option<Sender<()>>,
-    /// 消息接收通道
-    message_rx: Option<Receiver<Message>>,
-    /// 消息发送通道
-    message_tx: Option<Sender<Message>>,
-    /// UI消息发送通道
-    ui_tx: Option<Sender<Message>>,
+    /// UI到服务器发送通道
+    ui_to_server_tx: Option<Sender<Message>>,
+    /// 服务器到UI发送通道
+    server_to_ui_tx: Option<Sender<Message>>,
     /// 运行状态
     running: bool,
 }
 
+/// UDP 对端信息
+struct UdpPeerInfo {
+    /// 连接 ID
+    connection_id: String,
+    /// 最后一次收到数据的时间
+    last_seen: Instant,
+    /// 是否已空闲超时
+    stale: bool,
+}
+
 impl UdpServerHandler {
     /// 创建新的UDP服务器处理器
-    pub fn new(local_addr: SocketAddr) -> Self {
+    pub fn new(local_addr: SocketAddr, idle_timeout: Duration) -> Self {
         Self {
             local_addr,
             socket: None,
             clients: Arc::new(RwLock::new(HashMap::new())),
+            idle_timeout,
             control_tx: None,
-            message_rx: None,
-            message_tx: None,
-            ui_tx: None,
+            ui_to_server_tx: None,
+            server_to_ui_tx: None,
             running: false,
         }
     }
@@ -50,33 +92,186 @@ impl UdpServerHandler {
 #[async_trait]
 impl ProtocolHandler for UdpServerHandler {
     async fn start(&mut self) -> Result<()> {
-        todo!("Implement UDP server start")
+        // 绑定监听地址
+        let socket = Arc::new(UdpSocket::bind(self.local_addr).await?);
+
+        // 创建消息通道
+        let (ui_to_server_tx, mut ui_to_server_rx) = channel::<Message>(100);
+        let (control_tx, mut control_rx) = channel::<()>(1);
+
+        self.socket = Some(Arc::clone(&socket));
+        self.ui_to_server_tx = Some(ui_to_server_tx);
+        self.control_tx = Some(control_tx);
+        self.running = true;
+
+        let clients = Arc::clone(&self.clients);
+        let server_to_ui_tx = self.server_to_ui_tx.clone();
+        let idle_timeout = self.idle_timeout;
+
+        // 启动服务器收发任务
+        tokio::spawn(async move {
+            let mut buffer = vec![0u8; 65536];
+            let mut idle_check = tokio::time::interval(idle_timeout.min(Duration::from_secs(1)));
+
+            loop {
+                tokio::select! {
+                    // 接收数据报
+                    result = socket.recv_from(&mut buffer) => {
+                        match result {
+                            Ok((n, addr)) => {
+                                let connection_info = ConnectionInfo {
+                                    remote_addr: addr,
+                                    connection_id: addr.to_string(),
+                                };
+
+                                // 记录对端，首次出现或从 stale 恢复时需要通知UI
+                                let activated = {
+                                    let mut clients_lock = clients.write().await;
+                                    match clients_lock.get_mut(&addr) {
+                                        Some(peer) => {
+                                            peer.last_seen = Instant::now();
+                                            std::mem::replace(&mut peer.stale, false)
+                                        }
+                                        None => {
+                                            clients_lock.insert(addr, UdpPeerInfo {
+                                                connection_id: connection_info.connection_id.clone(),
+                                                last_seen: Instant::now(),
+                                                stale: false,
+                                            });
+                                            true
+                                        }
+                                    }
+                                };
+
+                                if let Some(ref server_to_ui_sender) = server_to_ui_tx {
+                                    if activated {
+                                        let _ = server_to_ui_sender
+                                            .send(Message::new_received(MessageType::ClientConnected, Some(connection_info.clone())))
+                                            .await;
+                                    }
+
+                                    let data = Bytes::copy_from_slice(&buffer[..n]);
+                                    let _ = server_to_ui_sender
+                                        .send(Message::new_received(MessageType::Binary(data), Some(connection_info)))
+                                        .await;
+                                }
+                            }
+                            Err(e) => {
+                                if let Some(ref server_to_ui_sender) = server_to_ui_tx {
+                                    let _ = server_to_ui_sender
+                                        .send(Message::new_event(format!("Receive error: {}", e), None))
+                                        .await;
+                                }
+                            }
+                        }
+                    }
+
+                    // 将UI发送的数据回复给指定对端
+                    message = ui_to_server_rx.recv() => {
+                        let Some(message) = message else {
+                            break;
+                        };
+                        // 发送方已取消的消息不再写出
+                        if message.is_cancelled() {
+                            continue;
+                        }
+                        let result = match (&message.connection_info, message.content.to_bytes()) {
+                            (Some(target), Some(data)) => socket
+                                .send_to(&data, target.remote_addr)
+                                .await
+                                .map(|_| message.mark_written())
+                                .map_err(|e| format!("Send to {} failed: {}", target.connection_id, e)),
+                            (None, _) => Err("Send failed: no target peer selected".to_string()),
+                            (_, None) => Ok(()),
+                        };
+                        if let (Err(text), Some(server_to_ui_sender)) = (result, server_to_ui_tx.as_ref()) {
+                            let _ = server_to_ui_sender.send(Message::new_event(text, None)).await;
+                        }
+                    }
+
+                    // 检查空闲超时的对端
+                    _ = idle_check.tick() => {
+                        let expired: Vec<ConnectionInfo> = {
+                            let mut clients_lock = clients.write().await;
+                            clients_lock
+                                .iter_mut()
+                                .filter(|(_, peer)| !peer.stale && peer.last_seen.elapsed() >= idle_timeout)
+                                .map(|(addr, peer)| {
+                                    peer.stale = true;
+                                    ConnectionInfo {
+                                        remote_addr: *addr,
+                                        connection_id: peer.connection_id.clone(),
+                                    }
+                                })
+                                .collect()
+                        };
+
+                        if let Some(ref server_to_ui_sender) = server_to_ui_tx {
+                            for connection_info in expired {
+                                let text = format!(
+                                    "Peer {} is stale (idle for {} s)",
+                                    connection_info.connection_id,
+                                    idle_timeout.as_secs()
+                                );
+                                let _ = server_to_ui_sender
+                                    .send(Message::new_event(text, Some(connection_info.clone())))
+                                    .await;
+                                let _ = server_to_ui_sender
+                                    .send(Message::new_received(MessageType::ClientStale, Some(connection_info)))
+                                    .await;
+                            }
+                        }
+                    }
+
+                    // 处理停止信号
+                    _ = control_rx.recv() => {
+                        break;
+                    }
+                }
+            }
+        });
+
+        Ok(())
     }
-    
+
     async fn stop(&mut self) -> Result<()> {
-        todo!("Implement UDP server stop")
-    }
-    
-    async fn send_message(&mut self, message: MessageType, target: Option<String>) -> Result<()> {
-        todo!("Implement UDP server send message")
-    }
-    
-    fn get_receiver(&self) -> Option<Receiver<Message>> {
-        self.message_rx.clone()
+        if self.running {
+            // 发送停止信号
+            if let Some(ref control_tx) = self.control_tx {
+                let _ = control_tx.send(()).await;
+            }
+            self.running = false;
+            // 清理资源
+            self.control_tx = None;
+            self.ui_to_server_tx = None;
+            self.socket = None;
+        }
+        Ok(())
     }
-    
-    fn set_ui_sender(&mut self, sender: Sender<Message>) {
-        self.ui_tx = Some(sender);
+
+    fn get_ui_to_server_sender(&self) -> Option<Sender<Message>> {
+        self.ui_to_server_tx.clone()
     }
-    
-    fn is_running(&self) -> bool {
-        self.running
+
+    fn set_server_to_ui_sender(&mut self, sender: Sender<Message>) {
+        self.server_to_ui_tx = Some(sender);
     }
-    
+
     fn get_connections(&self) -> Vec<ConnectionInfo> {
-        todo!("Implement get_connections for UDP server")
+        self.clients
+            .try_read()
+            .map(|clients| {
+                clients
+                    .iter()
+                    .map(|(addr, peer)| ConnectionInfo {
+                        remote_addr: *addr,
+                        connection_id: peer.connection_id.clone(),
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
     }
-    
+
     fn protocol_name(&self) -> &'static str {
         "UDP Server"
     }
@@ -92,12 +287,10 @@ pub struct UdpClientHandler {
     socket: Option<Arc<UdpSocket>>,
     /// 控制通道 (用于停止客户端)
     control_tx: Option<Sender<()>>,
-    /// 消息接收通道
-    message_rx: Option<Receiver<Message>>,
     /// 消息发送通道
-    message_tx: Option<Sender<Message>>,
+    ui_to_server_tx: Option<Sender<Message>>,
     /// UI消息发送通道
-    ui_tx: Option<Sender<Message>>,
+    server_to_ui_tx: Option<Sender<Message>>,
     /// 运行状态
     running: bool,
 }
@@ -110,45 +303,169 @@ impl UdpClientHandler {
             remote_addr,
             socket: None,
             control_tx: None,
-            message_rx: None,
-            message_tx: None,
-            ui_tx: None,
+            ui_to_server_tx: None,
+            server_to_ui_tx: None,
             running: false,
         }
     }
+
+    /// 将收发错误转换为UI事件文本
+    ///
+    /// 已连接的 UDP 套接字会在下一次收发时以 ECONNREFUSED 报告 ICMP 端口不可达
+    fn describe_error(remote_addr: SocketAddr, action: &str, e: &std::io::Error) -> String {
+        if e.kind() == ErrorKind::ConnectionRefused {
+            format!("Peer {} unreachable (ICMP port unreachable)", remote_addr)
+        } else {
+            format!("{} error: {}", action, e)
+        }
+    }
 }
 
 #[async_trait]
 impl ProtocolHandler for UdpClientHandler {
     async fn start(&mut self) -> Result<()> {
-        todo!("Implement UDP client start")
+        // 绑定本地地址并关联远程地址
+        let socket = UdpSocket::bind(self.local_addr).await?;
+        socket.connect(self.remote_addr).await?;
+        let socket = Arc::new(socket);
+
+        // 创建消息通道
+        let (ui_to_server_tx, mut ui_to_server_rx) = channel::<Message>(100);
+        let (control_tx, mut control_rx) = channel::<()>(1);
+
+        self.socket = Some(Arc::clone(&socket));
+        self.ui_to_server_tx = Some(ui_to_server_tx);
+        self.control_tx = Some(control_tx);
+        self.running = true;
+
+        let remote_addr = self.remote_addr;
+        let connection_info = ConnectionInfo {
+            remote_addr,
+            connection_id: remote_addr.to_string(),
+        };
+        let server_to_ui_tx = self.server_to_ui_tx.clone();
+
+        // 通知UI已关联远程地址
+        if let Some(ref server_to_ui_sender) = server_to_ui_tx {
+            let _ = server_to_ui_sender
+                .send(Message::new_received(MessageType::ClientConnected, Some(connection_info.clone())))
+                .await;
+        }
+
+        // 启动客户端收发任务
+        tokio::spawn(async move {
+            let mut buffer = vec![0u8; 65536];
+
+            loop {
+                let event = tokio::select! {
+                    // 接收数据报
+                    result = socket.recv(&mut buffer) => {
+                        match result {
+                            Ok(n) => {
+                                if let Some(ref server_to_ui_sender) = server_to_ui_tx {
+                                    let data = Bytes::copy_from_slice(&buffer[..n]);
+                                    let _ = server_to_ui_sender
+                                        .send(Message::new_received(MessageType::Binary(data), Some(connection_info.clone())))
+                                        .await;
+                                }
+                                None
+                            }
+                            Err(e) => Some(Self::describe_error(remote_addr, "Receive", &e)),
+                        }
+                    }
+
+                    // 发送UI输入的数据
+                    message = ui_to_server_rx.recv() => {
+                        let Some(message) = message else {
+                            break;
+                        };
+                        match message.content.to_bytes().filter(|_| !message.is_cancelled()) {
+                            Some(data) => match socket.send(&data).await {
+                                Ok(_) => {
+                                    message.mark_written();
+                                    None
+                                }
+                                Err(e) => Some(Self::describe_error(remote_addr, "Send", &e)),
+                            },
+                            None => None,
+                        }
+                    }
+
+                    // 处理停止信号
+                    _ = control_rx.recv() => {
+                        break;
+                    }
+                };
+
+                // 收发出错时仅提示，不中断任务
+                if let (Some(text), Some(server_to_ui_sender)) = (event, server_to_ui_tx.as_ref()) {
+                    let _ = server_to_ui_sender
+                        .send(Message::new_event(text, Some(connection_info.clone())))
+                        .await;
+                }
+            }
+
+            // 通知UI已断开
+            if let Some(ref server_to_ui_sender) = server_to_ui_tx {
+                let _ = server_to_ui_sender
+                    .send(Message::new_received(MessageType::ClientDisconnected, Some(connection_info)))
+                    .await;
+            }
+        });
+
+        Ok(())
     }
-    
+
     async fn stop(&mut self) -> Result<()> {
-        todo!("Implement UDP client stop")
-    }
-    
-    async fn send_message(&mut self, message: MessageType, target: Option<String>) -> Result<()> {
-        todo!("Implement UDP client send message")
-    }
-    
-    fn get_receiver(&self) -> Option<Receiver<Message>> {
-        self.message_rx.clone()
+        if self.running {
+            // 发送停止信号
+            if let Some(ref control_tx) = self.control_tx {
+                let _ = control_tx.send(()).await;
+            }
+            self.running = false;
+            // 清理资源
+            self.control_tx = None;
+            self.ui_to_server_tx = None;
+            self.socket = None;
+        }
+        Ok(())
     }
-    
-    fn set_ui_sender(&mut self, sender: Sender<Message>) {
-        self.ui_tx = Some(sender);
+
+    fn get_ui_to_server_sender(&self) -> Option<Sender<Message>> {
+        self.ui_to_server_tx.clone()
     }
-    
-    fn is_running(&self) -> bool {
-        self.running
+
+    fn set_server_to_ui_sender(&mut self, sender: Sender<Message>) {
+        self.server_to_ui_tx = Some(sender);
     }
-    
+
     fn get_connections(&self) -> Vec<ConnectionInfo> {
-        todo!("Implement get_connections for UDP client")
+        if self.running {
+            vec![ConnectionInfo {
+                remote_addr: self.remote_addr,
+                connection_id: self.remote_addr.to_string(),
+            }]
+        } else {
+            Vec::new()
+        }
     }
-    
+
     fn protocol_name(&self) -> &'static str {
         "UDP Client"
     }
-}
\ No newline at end of file
+}
+
+/// 注册 UDP 服务器和客户端处理器
+pub fn register(registry: &mut HandlerRegistry) {
+    registry.register_protocol(ProtocolType::Udp, "udp", &[]);
+    registry.register(ProtocolType::Udp, AppMode::Server, "UDP Server", UdpServerArgs::augment_args, |matches| {
+        let server_args = UdpServerArgs::from_arg_matches(matches)?;
+        Ok(Box::new(UdpServerHandler::new(server_args.listen.local_addr(), server_args.peer_timeout())))
+    });
+    registry.register(ProtocolType::Udp, AppMode::Client, "UDP Client", UdpClientArgs::augment_args, |matches| {
+        let endpoint = UdpClientArgs::from_arg_matches(matches)?.endpoint;
+        Ok(Box::new(UdpClientHandler::new(endpoint.local_addr(), endpoint.remote_addr())))
+    });
+    registry.register_shorthand(ProtocolType::Udp, AppMode::Server, "udp-server", "udps");
+    registry.register_shorthand(ProtocolType::Udp, AppMode::Client, "udp-client", "udpc");
+}
@@ -1,22 +1,79 @@
 use anyhow::Result;
 use async_trait::async_trait;
-use bytes::Bytes;
+use clap::{Args, FromArgMatches};
 use futures_util::{SinkExt, StreamExt};
-use std::{collections::HashMap, net::SocketAddr, sync::Arc};
+use std::{
+    collections::{HashMap, VecDeque},
+    net::SocketAddr,
+    sync::Arc,
+};
 use tokio::{
-    net::{TcpListener, TcpStream},
+    net::{lookup_host, TcpListener, TcpSocket, TcpStream},
     sync::{mpsc::{Receiver, Sender, channel}, RwLock},
 };
 use tokio_tungstenite::{
     accept_async,
+    client_async_tls,
     connect_async,
-    tungstenite::Message as WsMessage,
+    tungstenite::{
+        client::IntoClientRequest,
+        protocol::{frame::coding::CloseCode, CloseFrame},
+        Message as WsMessage,
+    },
+    MaybeTlsStream,
     WebSocketStream,
 };
 
+use crate::cli::args::{parse_address, AppMode, ListenArgs, ProtocolType};
 use crate::protocols::common::{
-    ConnectionInfo, Message, MessageDirection, MessageType, ProtocolHandler,
+    ConnectionInfo, Message, MessageType, ProtocolHandler, WriteAck,
 };
+use crate::protocols::reconnect::{notify, ReconnectArgs, ReconnectConfig, ReconnectOutcome, Reconnector, SessionEnd};
+use crate::protocols::registry::HandlerRegistry;
+
+/// WebSocket 服务器参数
+#[derive(Args, Debug, Clone)]
+pub struct WebSocketServerArgs {
+    #[command(flatten)]
+    pub listen: ListenArgs,
+}
+
+/// WebSocket 客户端参数
+#[derive(Args, Debug, Clone)]
+pub struct WebSocketClientArgs {
+    /// 服务器 URL (如 ws://127.0.0.1:8000/path?query 或 wss://example.com/ws)
+    /// 省略协议时默认为 ws://
+    pub url: String,
+
+    /// 本地地址 (如 127.0.0.1:9000)，不指定则由系统分配
+    #[arg(short, long)]
+    pub local: Option<String>,
+
+    /// 断线重连参数
+    #[command(flatten)]
+    pub reconnect: ReconnectArgs,
+}
+
+impl WebSocketClientArgs {
+    /// 补全协议部分后的服务器 URL
+    pub fn url(&self) -> String {
+        normalize_ws_url(&self.url)
+    }
+
+    /// 本地地址，未指定时由系统分配
+    pub fn local_addr(&self) -> Option<SocketAddr> {
+        self.local.as_deref().map(parse_address)
+    }
+}
+
+/// 补全 WebSocket URL 的协议部分
+fn normalize_ws_url(url: &str) -> String {
+    if url.contains("://") {
+        url.to_string()
+    } else {
+        format!("ws://{}", url)
+    }
+}
 
 /// WebSocket 服务器处理器
 pub struct WebSocketServerHandler {
@@ -26,12 +83,10 @@ pub struct WebSocketServerHandler {
     clients: Arc<RwLock<HashMap<String, WebSocketClientInfo>>>,
     /// 控制通道 (用于停止服务器)
     control_tx: Option<Sender<()>>,
-    /// 消息接收通道
-    message_rx: Option<Receiver<Message>>,
-    /// 消息发送通道
-    message_tx: Option<Sender<Message>>,
-    /// UI消息发送通道
-    ui_tx: Option<Sender<Message>>,
+    /// UI到服务器发送通道
+    ui_to_server_tx: Option<Sender<Message>>,
+    /// 服务器到UI发送通道
+    server_to_ui_tx: Option<Sender<Message>>,
     /// 运行状态
     running: bool,
 }
@@ -41,7 +96,81 @@ struct WebSocketClientInfo {
     /// 远程地址
     addr: SocketAddr,
     /// 发送通道
-    tx: Sender<WsMessage>,
+    tx: ClientSender,
+}
+
+/// 客户端发送通道 (帧和写出确认)
+type ClientSender = Sender<(WsMessage, Option<WriteAck>)>;
+
+/// 将帧发送给指定客户端，未指定客户端时广播给所有已连接的客户端
+async fn send_to_clients(
+    clients: &RwLock<HashMap<String, WebSocketClientInfo>>,
+    frame: WsMessage,
+    ack: Option<&WriteAck>,
+    target: Option<&str>,
+) -> Result<()> {
+    // 先复制发送通道再释放锁，避免通道已满时阻塞客户端断开处理
+    let targets: Vec<(String, ClientSender)> = {
+        let clients = clients.read().await;
+        match target {
+            Some(client_id) => {
+                let client = clients
+                    .get(client_id)
+                    .ok_or_else(|| anyhow::anyhow!("Send failed: client {} is not connected", client_id))?;
+                vec![(client_id.to_string(), client.tx.clone())]
+            }
+            None => clients.iter().map(|(client_id, client)| (client_id.clone(), client.tx.clone())).collect(),
+        }
+    };
+    if targets.is_empty() {
+        anyhow::bail!("Send failed: no clients connected");
+    }
+
+    let mut failed = Vec::new();
+    for (client_id, tx) in targets {
+        if tx.send((frame.clone(), ack.cloned())).await.is_err() {
+            failed.push(client_id);
+        }
+    }
+    if !failed.is_empty() {
+        anyhow::bail!("Send failed: {} disconnected", failed.join(", "));
+    }
+    Ok(())
+}
+
+/// 将收到的 WebSocket 帧转换为消息内容，保留帧类型
+pub(crate) fn from_ws_message(message: WsMessage) -> Option<MessageType> {
+    match message {
+        WsMessage::Text(text) => Some(MessageType::Text(text.to_string())),
+        WsMessage::Binary(data) => Some(MessageType::Binary(data)),
+        WsMessage::Ping(data) => Some(MessageType::Ping(data)),
+        WsMessage::Pong(data) => Some(MessageType::Pong(data)),
+        WsMessage::Close(frame) => Some(match frame {
+            Some(frame) => MessageType::Close {
+                code: Some(u16::from(frame.code)),
+                reason: frame.reason.to_string(),
+            },
+            None => MessageType::Close {
+                code: None,
+                reason: String::new(),
+            },
+        }),
+        WsMessage::Frame(_) => None,
+    }
+}
+
+/// 将待发送的消息内容转换为 WebSocket 帧
+pub(crate) fn to_ws_message(message: &MessageType) -> Option<WsMessage> {
+    match message {
+        MessageType::Text(text) => Some(WsMessage::text(text.clone())),
+        MessageType::Ping(data) => Some(WsMessage::Ping(data.clone())),
+        MessageType::Pong(data) => Some(WsMessage::Pong(data.clone())),
+        MessageType::Close { code, reason } => Some(WsMessage::Close(code.map(|code| CloseFrame {
+            code: CloseCode::from(code),
+            reason: reason.clone().into(),
+        }))),
+        other => other.to_bytes().map(WsMessage::Binary),
+    }
 }
 
 impl WebSocketServerHandler {
@@ -51,116 +180,498 @@ impl WebSocketServerHandler {
             local_addr,
             clients: Arc::new(RwLock::new(HashMap::new())),
             control_tx: None,
-            message_rx: None,
-            message_tx: None,
-            ui_tx: None,
+            ui_to_server_tx: None,
+            server_to_ui_tx: None,
             running: false,
         }
     }
+
+    /// 处理单个客户端连接: 完成握手后转发收到的帧，并写出UI发送的帧
+    async fn handle_client(
+        stream: TcpStream,
+        addr: SocketAddr,
+        clients: Arc<RwLock<HashMap<String, WebSocketClientInfo>>>,
+        server_to_ui_tx: Option<Sender<Message>>,
+    ) {
+        let connection_info = ConnectionInfo {
+            remote_addr: addr,
+            connection_id: addr.to_string(),
+        };
+
+        // 完成 WebSocket 握手
+        let ws_stream = match accept_async(stream).await {
+            Ok(ws_stream) => ws_stream,
+            Err(e) => {
+                if let Some(ref server_to_ui_sender) = server_to_ui_tx {
+                    let _ = server_to_ui_sender
+                        .send(Message::new_event(format!("Handshake with {} failed: {}", addr, e), None))
+                        .await;
+                }
+                return;
+            }
+        };
+
+        // 保存客户端信息
+        let (client_tx, mut client_rx) = channel::<(WsMessage, Option<WriteAck>)>(100);
+        clients.write().await.insert(connection_info.connection_id.clone(), WebSocketClientInfo {
+            addr,
+            tx: client_tx,
+        });
+
+        // 通知UI有新连接
+        if let Some(ref server_to_ui_sender) = server_to_ui_tx {
+            let _ = server_to_ui_sender
+                .send(Message::new_received(MessageType::ClientConnected, Some(connection_info.clone())))
+                .await;
+        }
+
+        let (mut sink, mut stream) = ws_stream.split();
+        let error = loop {
+            tokio::select! {
+                // 读取客户端帧
+                frame = stream.next() => {
+                    match frame {
+                        Some(Ok(frame)) => {
+                            if let (Some(content), Some(server_to_ui_sender)) = (from_ws_message(frame), server_to_ui_tx.as_ref()) {
+                                let _ = server_to_ui_sender
+                                    .send(Message::new_received(content, Some(connection_info.clone())))
+                                    .await;
+                            }
+                        }
+                        Some(Err(e)) => break Some(format!("Read from {} failed: {}", addr, e)),
+                        None => break None,
+                    }
+                }
+
+                // 写出UI发送的帧
+                Some((frame, ack)) = client_rx.recv() => {
+                    // 发送方已取消的帧不再写出
+                    if ack.as_ref().is_some_and(WriteAck::is_cancelled) {
+                        continue;
+                    }
+                    if let Err(e) = sink.send(frame).await {
+                        break Some(format!("Send to {} failed: {}", addr, e));
+                    }
+                    if let Some(ack) = ack {
+                        ack.mark_written();
+                    }
+                }
+            }
+        };
+
+        // 从客户端列表中移除并通知UI连接断开
+        clients.write().await.remove(&connection_info.connection_id);
+        if let Some(ref server_to_ui_sender) = server_to_ui_tx {
+            if let Some(text) = error {
+                let _ = server_to_ui_sender
+                    .send(Message::new_event(text, Some(connection_info.clone())))
+                    .await;
+            }
+            let _ = server_to_ui_sender
+                .send(Message::new_received(MessageType::ClientDisconnected, Some(connection_info)))
+                .await;
+        }
+    }
 }
 
 #[async_trait]
 impl ProtocolHandler for WebSocketServerHandler {
     async fn start(&mut self) -> Result<()> {
-        todo!("Implement WebSocket server start")
+        // 绑定监听地址
+        let listener = TcpListener::bind(self.local_addr).await?;
+
+        // 创建消息通道
+        let (ui_to_server_tx, mut ui_to_server_rx) = channel::<Message>(100);
+        let (control_tx, mut control_rx) = channel::<()>(1);
+
+        self.ui_to_server_tx = Some(ui_to_server_tx);
+        self.control_tx = Some(control_tx);
+        self.running = true;
+
+        let clients = Arc::clone(&self.clients);
+        let server_to_ui_tx = self.server_to_ui_tx.clone();
+
+        // 启动服务器监听任务
+        tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    // 处理新的客户端连接
+                    result = listener.accept() => {
+                        match result {
+                            Ok((stream, addr)) => {
+                                tokio::spawn(Self::handle_client(
+                                    stream,
+                                    addr,
+                                    Arc::clone(&clients),
+                                    server_to_ui_tx.clone(),
+                                ));
+                            }
+                            Err(e) => {
+                                if let Some(ref server_to_ui_sender) = server_to_ui_tx {
+                                    let _ = server_to_ui_sender
+                                        .send(Message::new_event(format!("Accept failed: {}", e), None))
+                                        .await;
+                                }
+                            }
+                        }
+                    }
+
+                    // 将UI发送的帧转发给目标客户端，未指定目标时广播
+                    message = ui_to_server_rx.recv() => {
+                        let Some(message) = message else {
+                            break;
+                        };
+                        let Some(frame) = to_ws_message(&message.content) else {
+                            continue;
+                        };
+                        let target = message.connection_info.map(|info| info.connection_id);
+                        if let Err(e) = send_to_clients(&clients, frame, message.ack.as_ref(), target.as_deref()).await {
+                            if let Some(ref server_to_ui_sender) = server_to_ui_tx {
+                                let _ = server_to_ui_sender.send(Message::new_event(e.to_string(), None)).await;
+                            }
+                        }
+                    }
+
+                    // 处理停止信号
+                    _ = control_rx.recv() => {
+                        break;
+                    }
+                }
+            }
+        });
+
+        Ok(())
     }
-    
+
     async fn stop(&mut self) -> Result<()> {
-        todo!("Implement WebSocket server stop")
-    }
-    
-    async fn send_message(&mut self, message: MessageType, target: Option<String>) -> Result<()> {
-        todo!("Implement WebSocket server send message")
-    }
-    
-    fn get_receiver(&self) -> Option<Receiver<Message>> {
-        self.message_rx.clone()
+        if self.running {
+            // 发送停止信号
+            if let Some(ref control_tx) = self.control_tx {
+                let _ = control_tx.send(()).await;
+            }
+            self.running = false;
+            // 清理资源
+            self.control_tx = None;
+            self.ui_to_server_tx = None;
+        }
+        Ok(())
     }
-    
-    fn set_ui_sender(&mut self, sender: Sender<Message>) {
-        self.ui_tx = Some(sender);
+
+    fn get_ui_to_server_sender(&self) -> Option<Sender<Message>> {
+        self.ui_to_server_tx.clone()
     }
-    
-    fn is_running(&self) -> bool {
-        self.running
+
+    fn set_server_to_ui_sender(&mut self, sender: Sender<Message>) {
+        self.server_to_ui_tx = Some(sender);
     }
-    
+
     fn get_connections(&self) -> Vec<ConnectionInfo> {
-        todo!("Implement get_connections for WebSocket server")
+        self.clients
+            .try_read()
+            .map(|clients| {
+                clients
+                    .iter()
+                    .map(|(client_id, info)| ConnectionInfo {
+                        remote_addr: info.addr,
+                        connection_id: client_id.clone(),
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    fn supports_broadcast(&self) -> bool {
+        true
     }
-    
+
     fn protocol_name(&self) -> &'static str {
         "WebSocket Server"
     }
 }
 
+/// WebSocket 客户端连接流
+type ClientStream = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
 /// WebSocket 客户端处理器
 pub struct WebSocketClientHandler {
-    /// 本地地址
-    local_addr: SocketAddr,
-    /// 远程服务器地址
-    remote_addr: SocketAddr,
-    /// WebSocket 流
-    ws_stream: Option<WebSocketStream<TcpStream>>,
+    /// 本地地址 (None 表示由系统分配)
+    local_addr: Option<SocketAddr>,
+    /// 服务器 URL
+    url: String,
+    /// 当前连接信息 (未连接时为 None)
+    connection: Arc<RwLock<Option<ConnectionInfo>>>,
+    /// 断线重连配置
+    reconnect: ReconnectConfig,
     /// 控制通道 (用于停止客户端)
     control_tx: Option<Sender<()>>,
-    /// 消息接收通道
-    message_rx: Option<Receiver<Message>>,
     /// 消息发送通道
-    message_tx: Option<Sender<Message>>,
+    ui_to_server_tx: Option<Sender<Message>>,
     /// UI消息发送通道
-    ui_tx: Option<Sender<Message>>,
+    server_to_ui_tx: Option<Sender<Message>>,
     /// 运行状态
     running: bool,
 }
 
 impl WebSocketClientHandler {
     /// 创建新的WebSocket客户端处理器
-    pub fn new(local_addr: SocketAddr, remote_addr: SocketAddr) -> Self {
+    pub fn new(local_addr: Option<SocketAddr>, url: String) -> Self {
         Self {
             local_addr,
-            remote_addr,
-            ws_stream: None,
+            url,
+            connection: Arc::new(RwLock::new(None)),
+            reconnect: ReconnectConfig::default(),
             control_tx: None,
-            message_rx: None,
-            message_tx: None,
-            ui_tx: None,
+            ui_to_server_tx: None,
+            server_to_ui_tx: None,
             running: false,
         }
     }
+
+    /// 设置断线重连配置
+    pub fn set_reconnect_config(&mut self, reconnect: ReconnectConfig) {
+        self.reconnect = reconnect;
+    }
+
+    /// 建立 WebSocket 连接，返回连接流、握手响应描述和对端地址
+    async fn connect(local_addr: Option<SocketAddr>, url: String) -> Result<(ClientStream, String, SocketAddr)> {
+        let (ws_stream, response) = match local_addr {
+            None => connect_async(url.as_str()).await?,
+            Some(local_addr) => {
+                // 需要绑定本地地址时自行解析主机名并建立 TCP 连接
+                let request = url.as_str().into_client_request()?;
+                let uri = request.uri();
+                let host = uri
+                    .host()
+                    .ok_or_else(|| anyhow::anyhow!("URL has no host: {}", url))?
+                    .trim_start_matches('[')
+                    .trim_end_matches(']')
+                    .to_string();
+                let port = uri
+                    .port_u16()
+                    .unwrap_or(if uri.scheme_str() == Some("wss") { 443 } else { 80 });
+                let remote_addr = lookup_host((host.as_str(), port))
+                    .await?
+                    .find(|addr| addr.is_ipv4() == local_addr.is_ipv4())
+                    .ok_or_else(|| anyhow::anyhow!("Failed to resolve {}", host))?;
+
+                let socket = if remote_addr.is_ipv4() {
+                    TcpSocket::new_v4()?
+                } else {
+                    TcpSocket::new_v6()?
+                };
+                socket.set_reuseaddr(true)?;
+                socket.bind(local_addr)?;
+                let stream = socket.connect(remote_addr).await?;
+                client_async_tls(request, stream).await?
+            }
+        };
+
+        let peer_addr = match ws_stream.get_ref() {
+            MaybeTlsStream::Plain(stream) => stream.peer_addr()?,
+            MaybeTlsStream::Rustls(stream) => stream.get_ref().0.peer_addr()?,
+            _ => anyhow::bail!("Unsupported WebSocket stream"),
+        };
+
+        // 握手响应: 状态行和响应头
+        let mut description = format!("Handshake response from {} ({})\n{:?} {}", url, peer_addr, response.version(), response.status());
+        for (name, value) in response.headers() {
+            description.push_str(&format!("\n{}: {}", name, String::from_utf8_lossy(value.as_bytes())));
+        }
+
+        Ok((ws_stream, description, peer_addr))
+    }
+
+    /// 运行一次连接会话，直到连接断开或收到停止信号
+    async fn run_session(
+        ws_stream: ClientStream,
+        pending: VecDeque<Message>,
+        ui_to_server_rx: &mut Receiver<Message>,
+        control_rx: &mut Receiver<()>,
+        server_to_ui_tx: &Option<Sender<Message>>,
+        connection_info: &ConnectionInfo,
+    ) -> SessionEnd {
+        let (mut sink, mut stream) = ws_stream.split();
+
+        // 先发送断线期间缓存的帧
+        for message in pending {
+            if let Some(frame) = to_ws_message(&message.content).filter(|_| !message.is_cancelled()) {
+                if let Err(e) = sink.send(frame).await {
+                    return SessionEnd::Lost(e.to_string());
+                }
+                message.mark_written();
+            }
+        }
+
+        loop {
+            tokio::select! {
+                // 读取服务端帧
+                frame = stream.next() => {
+                    match frame {
+                        Some(Ok(frame)) => {
+                            if let (Some(content), Some(server_to_ui_sender)) = (from_ws_message(frame), server_to_ui_tx.as_ref()) {
+                                let _ = server_to_ui_sender
+                                    .send(Message::new_received(content, Some(connection_info.clone())))
+                                    .await;
+                            }
+                        }
+                        Some(Err(e)) => return SessionEnd::Lost(e.to_string()),
+                        None => return SessionEnd::Lost("connection closed by peer".to_string()),
+                    }
+                }
+
+                // 发送UI输入的帧
+                message = ui_to_server_rx.recv() => {
+                    match message {
+                        Some(message) => {
+                            if let Some(frame) = to_ws_message(&message.content).filter(|_| !message.is_cancelled()) {
+                                if let Err(e) = sink.send(frame).await {
+                                    return SessionEnd::Lost(e.to_string());
+                                }
+                                message.mark_written();
+                            }
+                        }
+                        None => return SessionEnd::Stopped,
+                    }
+                }
+
+                // 处理停止信号
+                _ = control_rx.recv() => {
+                    let _ = sink.send(WsMessage::Close(None)).await;
+                    return SessionEnd::Stopped;
+                }
+            }
+        }
+    }
 }
 
 #[async_trait]
 impl ProtocolHandler for WebSocketClientHandler {
     async fn start(&mut self) -> Result<()> {
-        todo!("Implement WebSocket client start")
+        // 先完成握手，连接失败时直接返回错误
+        let (ws_stream, handshake, remote_addr) = Self::connect(self.local_addr, self.url.clone()).await?;
+
+        // 创建消息通道
+        let (ui_to_server_tx, mut ui_to_server_rx) = channel::<Message>(100);
+        let (control_tx, mut control_rx) = channel::<()>(1);
+
+        self.ui_to_server_tx = Some(ui_to_server_tx);
+        self.control_tx = Some(control_tx);
+        self.running = true;
+
+        let connection_info = ConnectionInfo {
+            remote_addr,
+            connection_id: self.url.clone(),
+        };
+
+        let (local_addr, url) = (self.local_addr, self.url.clone());
+        let connection = Arc::clone(&self.connection);
+        let server_to_ui_tx = self.server_to_ui_tx.clone();
+        let mut reconnector = Reconnector::new(self.reconnect.clone());
+
+        // 启动客户端读写任务
+        tokio::spawn(async move {
+            let (mut ws_stream, mut handshake) = (ws_stream, handshake);
+            loop {
+                // 先显示握手响应，再记录连接状态并通知UI已连接
+                notify(&server_to_ui_tx, &connection_info, handshake).await;
+                *connection.write().await = Some(connection_info.clone());
+                if let Some(ref server_to_ui_sender) = server_to_ui_tx {
+                    let _ = server_to_ui_sender
+                        .send(Message::new_received(MessageType::ClientConnected, Some(connection_info.clone())))
+                        .await;
+                }
+
+                let end = Self::run_session(
+                    ws_stream,
+                    reconnector.take_pending(),
+                    &mut ui_to_server_rx,
+                    &mut control_rx,
+                    &server_to_ui_tx,
+                    &connection_info,
+                )
+                .await;
+
+                // 清理连接状态并通知UI连接断开
+                *connection.write().await = None;
+                if let Some(ref server_to_ui_sender) = server_to_ui_tx {
+                    let _ = server_to_ui_sender
+                        .send(Message::new_received(MessageType::ClientDisconnected, Some(connection_info.clone())))
+                        .await;
+                }
+
+                let SessionEnd::Lost(reason) = end else {
+                    break;
+                };
+                notify(&server_to_ui_tx, &connection_info, format!("Connection lost: {}", reason)).await;
+
+                // 按重连策略尝试重新连接
+                let outcome = reconnector
+                    .run(
+                        || Self::connect(local_addr, url.clone()),
+                        &mut ui_to_server_rx,
+                        &mut control_rx,
+                        &server_to_ui_tx,
+                        &connection_info,
+                    )
+                    .await;
+                match outcome {
+                    ReconnectOutcome::Connected((new_stream, new_handshake, _)) => {
+                        ws_stream = new_stream;
+                        handshake = new_handshake;
+                    }
+                    ReconnectOutcome::GaveUp | ReconnectOutcome::Stopped => break,
+                }
+            }
+        });
+
+        Ok(())
     }
-    
+
     async fn stop(&mut self) -> Result<()> {
-        todo!("Implement WebSocket client stop")
-    }
-    
-    async fn send_message(&mut self, message: MessageType, target: Option<String>) -> Result<()> {
-        todo!("Implement WebSocket client send message")
-    }
-    
-    fn get_receiver(&self) -> Option<Receiver<Message>> {
-        self.message_rx.clone()
+        if self.running {
+            // 发送停止信号
+            if let Some(ref control_tx) = self.control_tx {
+                let _ = control_tx.send(()).await;
+            }
+            self.running = false;
+            // 清理资源
+            self.control_tx = None;
+            self.ui_to_server_tx = None;
+        }
+        Ok(())
     }
-    
-    fn set_ui_sender(&mut self, sender: Sender<Message>) {
-        self.ui_tx = Some(sender);
+
+    fn get_ui_to_server_sender(&self) -> Option<Sender<Message>> {
+        self.ui_to_server_tx.clone()
     }
-    
-    fn is_running(&self) -> bool {
-        self.running
+
+    fn set_server_to_ui_sender(&mut self, sender: Sender<Message>) {
+        self.server_to_ui_tx = Some(sender);
     }
-    
+
     fn get_connections(&self) -> Vec<ConnectionInfo> {
-        todo!("Implement get_connections for WebSocket client")
+        self.connection
+            .try_read()
+            .map(|connection| connection.iter().cloned().collect())
+            .unwrap_or_default()
     }
-    
+
     fn protocol_name(&self) -> &'static str {
         "WebSocket Client"
     }
-}
\ No newline at end of file
+}
+
+/// 注册 WebSocket 服务器和客户端处理器
+pub fn register(registry: &mut HandlerRegistry) {
+    registry.register_protocol(ProtocolType::WebSocket, "websocket", &["ws"]);
+    registry.register(ProtocolType::WebSocket, AppMode::Server, "WebSocket Server", WebSocketServerArgs::augment_args, |matches| {
+        let server_args = WebSocketServerArgs::from_arg_matches(matches)?;
+        Ok(Box::new(WebSocketServerHandler::new(server_args.listen.local_addr())))
+    });
+    registry.register(ProtocolType::WebSocket, AppMode::Client, "WebSocket Client", WebSocketClientArgs::augment_args, |matches| {
+        let client_args = WebSocketClientArgs::from_arg_matches(matches)?;
+        let mut handler = WebSocketClientHandler::new(client_args.local_addr(), client_args.url());
+        handler.set_reconnect_config(client_args.reconnect.reconnect_config());
+        Ok(Box::new(handler))
+    });
+}
@@ -1,20 +1,30 @@
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    widgets::{Block, Borders, Paragraph, Wrap},
     Frame,
 };
 
 use crate::app::App;
+use crate::ui::widgets::repeater_panel::RepeaterPanel;
 
 use super::layout::LayoutType;
 
 pub fn draw(frame: &mut Frame, app: &mut App) {
-    // 首先将屏幕分为上、中、下三个部分
+    // 首先将屏幕分为上、中、下三个部分，有重复发送任务时在底部状态栏上方显示任务面板
+    // 面板最多占屏幕高度的三分之一，避免挤占消息区
+    let panel_height = if app.repeaters.is_empty() {
+        0
+    } else {
+        RepeaterPanel::height(&app.repeaters).min((frame.area().height / 3).max(3))
+    };
     let vertical_chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
-            Constraint::Length(1), // 顶部状态栏
-            Constraint::Min(1),    // 中间内容区域
-            Constraint::Length(1), // 底部状态栏
+            Constraint::Length(1),            // 顶部状态栏
+            Constraint::Min(1),               // 中间内容区域
+            Constraint::Length(2),            // 底部状态栏 (快捷键提示和快捷发送槽位)
+            Constraint::Length(panel_height), // 重复发送任务面板
         ])
         .split(frame.area());
 
@@ -28,10 +38,16 @@ pub fn draw(frame: &mut Frame, app: &mut App) {
     };
 
     // 绘制底部状态栏 (快捷键提示)
-    app.status_bar.draw_bottom_bar(frame, vertical_chunks[2]);
+    app.status_bar.draw_bottom_bar(frame, vertical_chunks[2], app);
+
+    // 绘制重复发送任务面板
+    if !app.repeaters.is_empty() {
+        let broadcast = app.protocol_handler.supports_broadcast();
+        app.repeater_panel.draw(frame, vertical_chunks[3], &app.repeaters, broadcast);
+    }
 
     // 如果有输入对话框, 绘制在最顶层
-    if let Some(dialog) = &app.input_dialog {
+    if let Some(dialog) = &mut app.input_dialog {
         dialog.draw(frame);
     }
 }
@@ -69,3 +85,15 @@ fn draw_vertical(frame: &mut Frame, app: &mut App, area: Rect) {
     // 绘制接收区
     app.receive_view.draw(frame, chunks[1]);
 }
+
+/// 启动失败时的错误界面
+pub fn draw_startup_error(frame: &mut Frame, message: &str) {
+    let block = Block::default()
+        .title(" Startup failed ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Red))
+        .title_style(Style::default().fg(Color::Red).add_modifier(Modifier::BOLD));
+    let text = format!("{}\n\nPress any key to exit", message);
+    let paragraph = Paragraph::new(text).block(block).wrap(Wrap { trim: false });
+    frame.render_widget(paragraph, frame.area());
+}
@@ -1,36 +1,222 @@
 use ratatui::{
-    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Style},
-    text::{Line, Span, Text},
+    text::{Line, Span},
     widgets::{Block, Borders, Clear, Paragraph, Tabs},
     Frame,
 };
 
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fmt;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use crate::config::history::HistoryEntry;
+use crate::config::slots::QuickSlot;
+use crate::protocols::file_transfer::format_bytes;
+use crate::ui::widgets::text_editor::TextEditor;
+use crate::utils::data_format::{hex_to_bytes, invalid_hex_chars, looks_like_hex, unescape};
+use crate::utils::repeater::{RepeatSpec, MIN_REPEAT_INTERVAL};
+
+/// 广播到所有客户端的选项名称
+pub const ALL_CLIENTS: &str = "All clients";
+
+/// 重复发送设置输入框的宽度
+const REPEAT_FIELD_WIDTH: u16 = 13;
+
 /// 输入对话框组件
 pub struct InputDialog {
-    /// 用户输入的文本
-    pub input: String,
-    /// 数据发送格式 (String/Hex)
+    /// 输入编辑器
+    pub editor: TextEditor,
+    /// 数据发送格式 (String/Hex/File)
     pub format_type: FormatType,
+    /// 字符串格式下追加的行结束符
+    pub line_ending: LineEnding,
+    /// 字符串格式下是否处理转义序列
+    pub escapes: bool,
+    /// 重复发送间隔 (毫秒，为空表示只发送一次)
+    pub repeat_interval: TextEditor,
+    /// 重复发送次数 (为空表示不限次数)
+    pub repeat_count: TextEditor,
+    /// 正在编辑的重复发送设置输入框
+    repeat_focus: Option<RepeatField>,
     /// 当前选择的客户端索引
     pub selected_client: Option<usize>,
     /// 可用的客户端列表
     pub clients: Vec<String>,
+    /// WebSocket 帧类型 (仅 WebSocket 协议)
+    pub frame_type: Option<FrameType>,
+    /// 提交失败时的错误提示
+    pub error: Option<String>,
+    /// 粘贴的内容像十六进制数据，提示切换格式
+    pub hex_suggestion: bool,
+    /// 发送历史 (从旧到新)
+    pub history: Vec<HistoryEntry>,
+    /// 当前浏览的历史记录下标
+    history_index: Option<usize>,
+    /// 浏览历史前的输入，回到最新位置或取消搜索时恢复
+    draft: Option<HistoryEntry>,
+    /// 历史反向搜索状态
+    search: Option<HistorySearch>,
+    /// 快捷发送槽位编辑状态 (编辑槽位时提交保存到槽位而不是发送)
+    pub slot: Option<SlotEdit>,
+    /// 输入内容的校验结果缓存
+    validation_cache: Option<ContentValidation>,
+}
+
+/// 输入内容的校验结果 (内容或发送设置变化时在绘制前重新计算)
+struct ContentValidation {
+    /// 计算时的编辑器版本和发送设置
+    key: ValidationKey,
+    /// 十六进制格式下非法字符的下标
+    invalid: HashSet<usize>,
+    /// 待发送的字节数或错误原因，以及是否有效
+    hint: (String, bool),
+}
+
+/// 校验结果依赖的编辑器版本和发送设置
+type ValidationKey = (u64, FormatType, LineEnding, bool);
+
+/// 重复发送设置的输入框
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum RepeatField {
+    /// 发送间隔
+    Interval,
+    /// 发送次数
+    Count,
+}
+
+/// 快捷发送槽位编辑状态
+pub struct SlotEdit {
+    /// 功能键编号
+    pub key: u8,
+    /// 槽位名称
+    pub name: TextEditor,
+    /// 是否正在编辑名称
+    pub editing_name: bool,
+}
+
+/// 历史反向搜索状态
+struct HistorySearch {
+    /// 搜索关键字
+    query: String,
+    /// 当前匹配的历史记录下标
+    index: Option<usize>,
+    /// 是否没有找到匹配
+    failed: bool,
 }
 
 /// 数据发送格式
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum FormatType {
     String,
     Hex,
+    /// 输入为文件路径，分块发送文件内容
+    File,
+}
+
+/// 字符串格式发送时追加的行结束符
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum LineEnding {
+    #[default]
+    None,
+    Lf,
+    CrLf,
+    Cr,
+}
+
+impl LineEnding {
+    /// 所有行结束符 (按切换顺序)
+    pub const ALL: [LineEnding; 4] = [LineEnding::None, LineEnding::Lf, LineEnding::CrLf, LineEnding::Cr];
+
+    /// 行结束符的字节数据
+    pub fn as_bytes(&self) -> &'static [u8] {
+        match self {
+            LineEnding::None => b"",
+            LineEnding::Lf => b"\n",
+            LineEnding::CrLf => b"\r\n",
+            LineEnding::Cr => b"\r",
+        }
+    }
+
+    /// 切换到下一种行结束符
+    pub fn next(self) -> Self {
+        let index = Self::ALL.iter().position(|l| *l == self).unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+}
+
+impl fmt::Display for LineEnding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            LineEnding::None => "None",
+            LineEnding::Lf => "LF",
+            LineEnding::CrLf => "CRLF",
+            LineEnding::Cr => "CR",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// WebSocket 帧类型
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FrameType {
+    Text,
+    Binary,
+    Ping,
+    Pong,
+    Close,
+}
+
+impl FrameType {
+    /// 所有帧类型 (按切换顺序)
+    pub const ALL: [FrameType; 5] = [
+        FrameType::Text,
+        FrameType::Binary,
+        FrameType::Ping,
+        FrameType::Pong,
+        FrameType::Close,
+    ];
+
+    /// 帧类型名称
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FrameType::Text => "Text",
+            FrameType::Binary => "Binary",
+            FrameType::Ping => "Ping",
+            FrameType::Pong => "Pong",
+            FrameType::Close => "Close",
+        }
+    }
+
+    /// 控制帧允许空负载
+    pub fn allows_empty(&self) -> bool {
+        matches!(self, FrameType::Ping | FrameType::Pong | FrameType::Close)
+    }
 }
 
 impl InputDialog {
     pub fn new() -> Self {
         Self {
-            input: String::new(),
+            editor: TextEditor::new(),
             format_type: FormatType::String,
+            line_ending: LineEnding::None,
+            escapes: true,
+            repeat_interval: TextEditor::new(),
+            repeat_count: TextEditor::new(),
+            repeat_focus: None,
             selected_client: None,
             clients: Vec::new(),
+            frame_type: None,
+            error: None,
+            hex_suggestion: false,
+            history: Vec::new(),
+            history_index: None,
+            draft: None,
+            search: None,
+            slot: None,
+            validation_cache: None,
         }
     }
 
@@ -42,29 +228,404 @@ impl InputDialog {
         }
     }
 
+    /// 选择下一个客户端
+    pub fn select_next_client(&mut self) {
+        if !self.clients.is_empty() {
+            self.selected_client = Some(self.selected_client.map_or(0, |index| (index + 1) % self.clients.len()));
+        }
+    }
+
+    /// 当前选择的客户端
+    pub fn selected_client(&self) -> Option<&String> {
+        self.selected_client.and_then(|index| self.clients.get(index))
+    }
+
+    /// 切换 WebSocket 帧类型
+    pub fn cycle_frame_type(&mut self) {
+        if let Some(frame_type) = self.frame_type {
+            let index = FrameType::ALL.iter().position(|f| *f == frame_type).unwrap_or(0);
+            self.frame_type = Some(FrameType::ALL[(index + 1) % FrameType::ALL.len()]);
+        }
+    }
+
     /// 切换格式类型
     pub fn toggle_format(&mut self) {
         self.format_type = match self.format_type {
             FormatType::String => FormatType::Hex,
-            FormatType::Hex => FormatType::String,
+            FormatType::Hex => FormatType::File,
+            FormatType::File => FormatType::String,
         };
+        self.hex_suggestion = false;
     }
 
-    /// 提交输入并返回内容
-    pub fn submit(&self) -> Option<String> {
-        if self.input.is_empty() {
-            None
+    /// 在光标处插入粘贴的文本，字符串格式下内容像十六进制数据时提示切换格式
+    pub fn paste(&mut self, text: &str) {
+        self.accept_search();
+        // 槽位名称和重复发送设置只有一行，换行替换为空格
+        if self.is_editing_field() {
+            self.active_editor().insert_str(&text.replace(['\r', '\n'], " "));
+            return;
+        }
+        self.editor.insert_str(text);
+        self.error = None;
+        self.hex_suggestion = self.format_type == FormatType::String && looks_like_hex(&self.input());
+    }
+
+    /// 获取输入文本
+    pub fn input(&self) -> String {
+        self.editor.text()
+    }
+
+    /// 当前输入对应的历史记录
+    pub fn history_entry(&self) -> HistoryEntry {
+        HistoryEntry {
+            text: self.input(),
+            format: self.format_type,
+        }
+    }
+
+    /// 载入历史记录到输入框，同时恢复其发送格式
+    fn load_entry(&mut self, entry: HistoryEntry) {
+        self.editor.set_text(&entry.text);
+        self.format_type = entry.format;
+        self.error = None;
+    }
+
+    /// 切换到更早的一条历史记录
+    pub fn history_previous(&mut self) {
+        let index = match self.history_index {
+            Some(0) => return,
+            Some(index) => index - 1,
+            None if self.history.is_empty() => return,
+            None => {
+                self.draft = Some(self.history_entry());
+                self.history.len() - 1
+            }
+        };
+        self.history_index = Some(index);
+        self.load_entry(self.history[index].clone());
+    }
+
+    /// 切换到更新的一条历史记录，越过最新一条时恢复原输入
+    pub fn history_next(&mut self) {
+        let Some(index) = self.history_index else {
+            return;
+        };
+        if index + 1 < self.history.len() {
+            self.history_index = Some(index + 1);
+            self.load_entry(self.history[index + 1].clone());
         } else {
-            Some(self.input.clone())
+            self.history_index = None;
+            if let Some(draft) = self.draft.take() {
+                self.load_entry(draft);
+            }
+        }
+    }
+
+    /// 是否处于历史搜索状态
+    pub fn is_searching(&self) -> bool {
+        self.search.is_some()
+    }
+
+    /// 开始历史反向搜索，已在搜索时查找更早的匹配
+    pub fn search_history(&mut self) {
+        match &self.search {
+            Some(search) => {
+                let end = search.index.unwrap_or(self.history.len());
+                self.search_before(end);
+            }
+            None => {
+                if self.draft.is_none() {
+                    self.draft = Some(self.history_entry());
+                }
+                self.search = Some(HistorySearch {
+                    query: String::new(),
+                    index: None,
+                    failed: false,
+                });
+            }
+        }
+    }
+
+    /// 搜索关键字追加字符
+    pub fn search_push(&mut self, c: char) {
+        if let Some(search) = &mut self.search {
+            search.query.push(c);
+            // 当前匹配仍然满足时保留
+            let end = search.index.map_or(self.history.len(), |index| index + 1);
+            self.search_before(end);
+        }
+    }
+
+    /// 搜索关键字删除最后一个字符
+    pub fn search_pop(&mut self) {
+        if let Some(search) = &mut self.search {
+            search.query.pop();
+            let end = search.index.map_or(self.history.len(), |index| index + 1);
+            self.search_before(end);
+        }
+    }
+
+    /// 接受搜索结果，之后的上下键从匹配位置继续浏览
+    pub fn accept_search(&mut self) {
+        if let Some(search) = self.search.take() {
+            self.history_index = search.index;
+        }
+    }
+
+    /// 取消搜索，恢复原输入
+    pub fn cancel_search(&mut self) {
+        self.search = None;
+        self.history_index = None;
+        if let Some(draft) = self.draft.take() {
+            self.load_entry(draft);
+        }
+    }
+
+    /// 在 `end` 之前的历史记录中查找最近一条包含关键字的记录
+    fn search_before(&mut self, end: usize) {
+        let Some(search) = &mut self.search else {
+            return;
+        };
+        let found = self.history[..end.min(self.history.len())]
+            .iter()
+            .rposition(|entry| entry.text.contains(&search.query));
+        search.failed = found.is_none();
+        if let Some(index) = found {
+            search.index = Some(index);
+            self.load_entry(self.history[index].clone());
+        }
+    }
+
+    /// 切换行结束符
+    pub fn cycle_line_ending(&mut self) {
+        self.line_ending = self.line_ending.next();
+    }
+
+    /// 载入快捷发送槽位的内容、格式、行结束符和目标
+    pub fn load_slot(&mut self, slot: &QuickSlot) {
+        self.editor.set_text(&slot.text);
+        self.format_type = slot.format;
+        self.line_ending = slot.line_ending;
+        self.escapes = slot.escapes;
+        if let Some(index) = slot.target.as_ref().and_then(|target| self.clients.iter().position(|c| c == target)) {
+            self.selected_client = Some(index);
+        }
+        // WebSocket 下十六进制数据按二进制帧发送
+        if self.format_type == FormatType::Hex && self.frame_type.is_some() {
+            self.frame_type = Some(FrameType::Binary);
+        }
+    }
+
+    /// 开始编辑快捷发送槽位，新槽位从名称开始编辑
+    pub fn edit_slot(&mut self, key: u8, slot: Option<&QuickSlot>) {
+        let mut name = TextEditor::new();
+        if let Some(slot) = slot {
+            name.set_text(&slot.name);
+            self.load_slot(slot);
         }
+        self.slot = Some(SlotEdit {
+            key,
+            name,
+            editing_name: slot.is_none(),
+        });
+    }
+
+    /// 是否正在编辑发送内容之外的单行输入框 (槽位名称或重复发送设置)
+    pub fn is_editing_field(&self) -> bool {
+        self.slot.as_ref().is_some_and(|slot| slot.editing_name) || self.repeat_focus.is_some()
+    }
+
+    /// 在槽位名称和发送内容之间切换编辑焦点
+    pub fn toggle_name_focus(&mut self) {
+        if let Some(slot) = &mut self.slot {
+            slot.editing_name = !slot.editing_name;
+        }
+    }
+
+    /// 当前接收按键的编辑器
+    pub fn active_editor(&mut self) -> &mut TextEditor {
+        match (&mut self.slot, self.repeat_focus) {
+            (Some(slot), _) if slot.editing_name => &mut slot.name,
+            (_, Some(RepeatField::Interval)) => &mut self.repeat_interval,
+            (_, Some(RepeatField::Count)) => &mut self.repeat_count,
+            _ => &mut self.editor,
+        }
+    }
+
+    /// 生成编辑后的槽位，发送内容为空时返回 None (清除槽位)
+    pub fn to_slot(&self, target: Option<String>) -> Option<QuickSlot> {
+        let slot = self.slot.as_ref()?;
+        if self.editor.is_empty() {
+            return None;
+        }
+        let name = slot.name.text().split_whitespace().collect::<Vec<_>>().join(" ");
+        Some(QuickSlot {
+            key: slot.key,
+            name: if name.is_empty() { format!("F{}", slot.key) } else { name },
+            text: self.input(),
+            format: self.format_type,
+            line_ending: self.line_ending,
+            escapes: self.escapes,
+            target,
+        })
+    }
+
+    /// 切换是否处理转义序列
+    pub fn toggle_escapes(&mut self) {
+        self.escapes = !self.escapes;
+    }
+
+    /// 切换编辑焦点 (发送内容 -> 发送间隔 -> 发送次数 -> 发送内容)
+    pub fn focus_next_repeat_field(&mut self) {
+        self.repeat_focus = match self.repeat_focus {
+            None => Some(RepeatField::Interval),
+            Some(RepeatField::Interval) => Some(RepeatField::Count),
+            Some(RepeatField::Count) => None,
+        };
+    }
+
+    /// 重复发送设置，None 表示只发送一次，输入无效时返回错误原因
+    pub fn repeat_spec(&self) -> Result<Option<RepeatSpec>, String> {
+        let interval = self.repeat_interval.text();
+        let count = self.repeat_count.text();
+        let (interval, count) = (interval.trim(), count.trim());
+        if interval.is_empty() {
+            if !count.is_empty() {
+                return Err("Enter a repeat interval to send more than once".to_string());
+            }
+            return Ok(None);
+        }
+        let interval = match interval.parse::<u64>() {
+            Ok(ms) if Duration::from_millis(ms) >= MIN_REPEAT_INTERVAL => Duration::from_millis(ms),
+            _ => {
+                return Err(format!(
+                    "Invalid repeat interval '{}', expected milliseconds >= {}",
+                    interval,
+                    MIN_REPEAT_INTERVAL.as_millis()
+                ))
+            }
+        };
+        let count = match count {
+            "" => None,
+            count => match count.parse::<u64>() {
+                Ok(count) if count >= 1 => Some(count),
+                _ => return Err(format!("Invalid repeat count '{}', expected a number >= 1", count)),
+            },
+        };
+        Ok(Some(RepeatSpec { interval, count }))
+    }
+
+    /// 按发送格式解析输入得到待发送的字节数据: 十六进制格式解码，字符串格式处理转义序列 (可关闭) 并追加行结束符
+    pub fn payload(&self) -> Result<Vec<u8>, String> {
+        match self.format_type {
+            FormatType::Hex => hex_to_bytes(&self.input()),
+            FormatType::String => {
+                let mut bytes = if self.escapes {
+                    unescape(&self.input())?
+                } else {
+                    self.input().into_bytes()
+                };
+                bytes.extend_from_slice(self.line_ending.as_bytes());
+                Ok(bytes)
+            }
+            FormatType::File => Err("File contents are sent as a transfer".to_string()),
+        }
+    }
+
+    /// 文件格式下输入的文件路径 (`~/` 开头时展开为用户目录)
+    pub fn file_path(&self) -> Option<PathBuf> {
+        if self.format_type != FormatType::File {
+            return None;
+        }
+        let input = self.input();
+        let input = input.trim();
+        match input.strip_prefix("~/").zip(dirs_next::home_dir()) {
+            Some((rest, home)) => Some(home.join(rest)),
+            None => Some(PathBuf::from(input)),
+        }
+    }
+
+    /// 校验当前输入，返回提示文本和是否有效 (显示待发送的字节数或错误原因)
+    pub fn validation(&self) -> Option<(String, bool)> {
+        if let Some(search) = &self.search {
+            let label = if search.failed { "failing reverse-i-search" } else { "reverse-i-search" };
+            return Some((format!("({}) `{}' [Ctrl+R: older, Esc: cancel]", label, search.query), !search.failed));
+        }
+        if let Some(error) = &self.error {
+            return Some((error.clone(), false));
+        }
+        if let Some(slot) = self.slot.as_ref().filter(|_| self.editor.is_empty()) {
+            return Some((format!("Enter: clear F{}", slot.key), true));
+        }
+        if self.hex_suggestion {
+            return Some(("Pasted text looks like hex, press Ctrl+F to send it as Hex".to_string(), true));
+        }
+        if let (None, Err(e)) = (&self.slot, self.repeat_spec()) {
+            return Some((e, false));
+        }
+        // 关闭帧按 "<关闭码> <原因>" 解析，不计算负载
+        if self.frame_type == Some(FrameType::Close) || !self.can_submit() {
+            return None;
+        }
+        match self.validation_cache.as_ref().filter(|cache| cache.key == self.validation_key()) {
+            Some(cache) => Some(cache.hint.clone()),
+            None => Some(self.content_hint()),
+        }
+    }
+
+    /// 校验输入内容: 文件格式显示文件大小，其他格式显示待发送的字节数
+    fn content_hint(&self) -> (String, bool) {
+        if let Some(path) = self.file_path() {
+            return match std::fs::metadata(&path) {
+                Ok(metadata) if metadata.is_file() => {
+                    (format!("File: {} ({} bytes)", format_bytes(metadata.len() as f64), metadata.len()), true)
+                }
+                Ok(_) => (format!("{} is not a file", path.display()), false),
+                Err(e) => (format!("Cannot read {}: {}", path.display(), e), false),
+            };
+        }
+        match self.payload() {
+            Ok(bytes) => (format!("{} bytes", bytes.len()), true),
+            Err(e) => (e, false),
+        }
+    }
+
+    /// 当前的编辑器版本和发送设置
+    fn validation_key(&self) -> ValidationKey {
+        (self.editor.revision(), self.format_type, self.line_ending, self.escapes)
+    }
+
+    /// 输入内容或发送设置变化后重新校验
+    fn refresh_validation(&mut self) {
+        let key = self.validation_key();
+        if self.validation_cache.as_ref().is_some_and(|cache| cache.key == key) {
+            return;
+        }
+        let invalid = match self.format_type {
+            FormatType::Hex => invalid_hex_chars(&self.input()).into_iter().map(|(index, _)| index).collect(),
+            FormatType::String | FormatType::File => HashSet::new(),
+        };
+        self.validation_cache = Some(ContentValidation {
+            key,
+            invalid,
+            hint: self.content_hint(),
+        });
+    }
+
+    /// 是否可以提交 (输入为空时只有控制帧或追加行结束符时可以提交)
+    pub fn can_submit(&self) -> bool {
+        let allows_empty = self.frame_type.is_some_and(|f| f.allows_empty())
+            || (self.format_type == FormatType::String && self.line_ending != LineEnding::None);
+        !self.editor.is_empty() || allows_empty
     }
 
     /// 绘制对话框
-    pub fn draw(&self, frame: &mut Frame) {
+    pub fn draw(&mut self, frame: &mut Frame) {
         // 计算对话框的尺寸和位置
-        let area = frame.size();
-        let width = area.width.min(60);
-        let height = 10;
+        let area = frame.area();
+        let width = area.width.min(72);
+        let height = area.height.min(19);
         let x = (area.width - width) / 2;
         let y = (area.height - height) / 2;
         let dialog_area = Rect::new(x, y, width, height);
@@ -73,8 +634,12 @@ impl InputDialog {
         frame.render_widget(Clear, dialog_area);
 
         // 创建对话框边框
+        let title = match &self.slot {
+            Some(slot) => format!("Edit Quick Slot F{}", slot.key),
+            None => "Send Message".to_string(),
+        };
         let block = Block::default()
-            .title("Send Message")
+            .title(title)
             .borders(Borders::ALL)
             .style(Style::default().bg(Color::DarkGray));
 
@@ -84,9 +649,12 @@ impl InputDialog {
             .margin(1)
             .constraints([
                 Constraint::Length(1),  // 格式选择
+                Constraint::Length(1),  // 行结束符选择
+                Constraint::Length(1),  // 重复发送设置
                 Constraint::Length(1),  // 客户端选择
-                Constraint::Length(1),  // 间距
+                Constraint::Length(1),  // 帧类型选择 (仅 WebSocket)
                 Constraint::Min(3),     // 输入区域
+                Constraint::Length(1),  // 校验提示
             ])
             .split(dialog_area);
 
@@ -97,15 +665,94 @@ impl InputDialog {
         let format_tabs = Tabs::new(vec![
             Line::from("String"),
             Line::from("Hex"),
+            Line::from("File"),
         ])
         .select(match self.format_type {
             FormatType::String => 0,
             FormatType::Hex => 1,
+            FormatType::File => 2,
         })
         .style(Style::default().fg(Color::White))
         .highlight_style(Style::default().fg(Color::Yellow));
         frame.render_widget(Paragraph::new("Format:"), chunks[0]);
-        frame.render_widget(format_tabs, chunks[0]);
+        frame.render_widget(format_tabs, value_area(chunks[0]));
+        let hint = Paragraph::new("Ctrl+F").style(Style::default().fg(Color::Gray)).right_aligned();
+        frame.render_widget(hint, chunks[0]);
+
+        // 绘制行结束符选择 (仅字符串格式生效)
+        let line_ending_tabs = Tabs::new(LineEnding::ALL.iter().map(|l| Line::from(l.to_string())).collect::<Vec<_>>())
+            .select(LineEnding::ALL.iter().position(|l| *l == self.line_ending).unwrap_or(0))
+            .style(Style::default().fg(if self.format_type == FormatType::String { Color::White } else { Color::Gray }))
+            .highlight_style(Style::default().fg(Color::Yellow));
+        frame.render_widget(Paragraph::new("Ending:"), chunks[1]);
+        frame.render_widget(line_ending_tabs, value_area(chunks[1]));
+
+        // 转义序列开关 (仅字符串格式生效)
+        let escapes_area = value_area(chunks[1]);
+        let escapes_area = Rect::new(escapes_area.x + 26, escapes_area.y, escapes_area.width.saturating_sub(26), 1);
+        let escapes = Line::from(vec![
+            Span::raw("Escapes: "),
+            Span::styled(
+                if self.escapes { "On" } else { "Off" },
+                Style::default().fg(if self.format_type == FormatType::String { Color::Yellow } else { Color::Gray }),
+            ),
+        ]);
+        frame.render_widget(Paragraph::new(escapes), escapes_area);
+        let hint = Paragraph::new("Ctrl+L/X").style(Style::default().fg(Color::Gray)).right_aligned();
+        frame.render_widget(hint, chunks[1]);
+
+        // 编辑槽位时绘制槽位名称，否则绘制重复发送设置
+        let mut field_cursor = None;
+        if let Some(slot) = &mut self.slot {
+            let name_area = value_area(chunks[2]);
+            let name_area = Rect::new(name_area.x, name_area.y, name_area.width.saturating_sub(8), 1);
+            let (lines, (cursor_x, _)) = slot.name.render(name_area.width as usize, 1, |_, _| Style::default());
+            let color = if slot.editing_name { Color::Yellow } else { Color::White };
+            frame.render_widget(Paragraph::new("Name:"), chunks[2]);
+            frame.render_widget(Paragraph::new(lines).style(Style::default().fg(color)), name_area);
+            let hint = Paragraph::new("Ctrl+E").style(Style::default().fg(Color::Gray)).right_aligned();
+            frame.render_widget(hint, chunks[2]);
+            if slot.editing_name {
+                field_cursor = Some((name_area.x + cursor_x, name_area.y));
+            }
+        } else {
+            // "every [间隔] ms (min 100), times [次数]"，为空时显示占位文本
+            frame.render_widget(Paragraph::new("Repeat:"), chunks[2]);
+            let area = value_area(chunks[2]);
+            let interval_suffix = format!(" ms (min {})", MIN_REPEAT_INTERVAL.as_millis());
+            let fields = [
+                ("every ", RepeatField::Interval, "off", interval_suffix.as_str()),
+                ("times ", RepeatField::Count, "until stopped", ""),
+            ];
+            let mut x = area.x;
+            for (label, field, placeholder, suffix) in fields {
+                let focused = self.repeat_focus == Some(field);
+                let editor = match field {
+                    RepeatField::Interval => &mut self.repeat_interval,
+                    RepeatField::Count => &mut self.repeat_count,
+                };
+                let label_area = Rect::new(x, area.y, label.len() as u16, 1).intersection(area);
+                frame.render_widget(Paragraph::new(label), label_area);
+                x += label.len() as u16;
+
+                let field_area = Rect::new(x, area.y, REPEAT_FIELD_WIDTH, 1).intersection(area);
+                let style = Style::default().fg(if focused { Color::Yellow } else { Color::White });
+                if editor.is_empty() && !focused {
+                    frame.render_widget(Paragraph::new(placeholder).style(Style::default().fg(Color::Gray)), field_area);
+                } else {
+                    let (lines, (cursor_x, _)) = editor.render(REPEAT_FIELD_WIDTH as usize, 1, |_, _| style);
+                    frame.render_widget(Paragraph::new(lines), field_area);
+                    if focused {
+                        field_cursor = Some((field_area.x + cursor_x, field_area.y));
+                    }
+                }
+                x += REPEAT_FIELD_WIDTH;
+                frame.render_widget(Paragraph::new(suffix), Rect::new(x, area.y, suffix.len() as u16, 1).intersection(area));
+                x += suffix.len() as u16 + 2;
+            }
+            let hint = Paragraph::new("Ctrl+P").style(Style::default().fg(Color::Gray)).right_aligned();
+            frame.render_widget(hint, chunks[2]);
+        }
 
         // 如果有客户端，绘制客户端选择
         if !self.clients.is_empty() {
@@ -115,25 +762,154 @@ impl InputDialog {
                 .style(Style::default().fg(Color::White))
                 .highlight_style(Style::default().fg(Color::Yellow));
             
-            frame.render_widget(Paragraph::new("Client:"), chunks[1]);
-            frame.render_widget(client_tabs, chunks[1]);
+            frame.render_widget(Paragraph::new("Client:"), chunks[3]);
+            frame.render_widget(client_tabs, value_area(chunks[3]));
+        }
+
+        // 如果是 WebSocket，绘制帧类型选择
+        if let Some(frame_type) = self.frame_type {
+            let frame_tabs = Tabs::new(FrameType::ALL.iter().map(|f| Line::from(f.as_str())).collect::<Vec<_>>())
+                .select(FrameType::ALL.iter().position(|f| *f == frame_type).unwrap_or(0))
+                .style(Style::default().fg(Color::White))
+                .highlight_style(Style::default().fg(Color::Yellow));
+
+            frame.render_widget(Paragraph::new("Frame:"), chunks[4]);
+            frame.render_widget(frame_tabs, value_area(chunks[4]));
         }
 
         // 绘制输入区域
+        let input_title = match self.format_type {
+            FormatType::File => " File Path ",
+            _ => "",
+        };
         let input_block = Block::default()
+            .title(input_title)
+            .title_bottom(Line::from(" Shift+Enter: New Line ").right_aligned())
             .borders(Borders::ALL)
             .style(Style::default());
-        
-        let input_paragraph = Paragraph::new(self.input.as_str())
+        let input_area = input_block.inner(chunks[5]);
+
+        // 十六进制格式下高亮非法字符
+        self.refresh_validation();
+        let invalid = self.validation_cache.as_ref().map(|cache| &cache.invalid);
+        let (lines, (cursor_x, cursor_y)) =
+            self.editor
+                .render(input_area.width as usize, input_area.height as usize, |index, _| {
+                    if invalid.is_some_and(|invalid| invalid.contains(&index)) {
+                        Style::default().fg(Color::White).bg(Color::Red)
+                    } else {
+                        Style::default()
+                    }
+                });
+
+        let input_paragraph = Paragraph::new(lines)
             .block(input_block)
             .style(Style::default().fg(Color::White));
         
-        frame.render_widget(input_paragraph, chunks[3]);
+        frame.render_widget(input_paragraph, chunks[5]);
+
+        // 绘制校验提示
+        if let Some((text, valid)) = self.validation() {
+            let color = if valid { Color::Gray } else { Color::LightRed };
+            frame.render_widget(Paragraph::new(text).style(Style::default().fg(color)), chunks[6]);
+        }
 
         // 显示光标
-        frame.set_cursor_position((
-            chunks[3].x + 1 + self.input.len() as u16,
-            chunks[3].y + 1,
-        ));
+        let cursor = field_cursor.unwrap_or((input_area.x + cursor_x, input_area.y + cursor_y));
+        frame.set_cursor_position(cursor);
     }
-}
\ No newline at end of file
+}
+
+/// 标签右侧的取值区域
+fn value_area(area: Rect) -> Rect {
+    let label_width = 8.min(area.width);
+    Rect::new(area.x + label_width, area.y, area.width - label_width, area.height)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_payload_escapes_toggle() {
+        let mut dialog = InputDialog::new();
+        dialog.editor.set_text("AT\\r\\n C:\\path");
+        dialog.line_ending = LineEnding::Lf;
+        assert_eq!(dialog.payload().unwrap(), b"AT\r\n C:\\path\n");
+
+        // 关闭转义后按原样发送
+        dialog.toggle_escapes();
+        assert_eq!(dialog.payload().unwrap(), b"AT\\r\\n C:\\path\n");
+
+        dialog.editor.set_text("{\"a\": \"\\x1\"}");
+        assert_eq!(dialog.payload().unwrap(), b"{\"a\": \"\\x1\"}\n");
+        dialog.toggle_escapes();
+        assert!(dialog.payload().is_err());
+    }
+
+    #[test]
+    fn test_validation_cache() {
+        let mut dialog = InputDialog::new();
+        dialog.format_type = FormatType::Hex;
+        dialog.editor.set_text("01 zz");
+        dialog.refresh_validation();
+        let cache = dialog.validation_cache.as_ref().unwrap();
+        assert_eq!(cache.invalid, HashSet::from([3, 4]));
+        assert!(!dialog.validation().unwrap().1);
+
+        // 内容或格式变化后重新校验
+        dialog.editor.backspace();
+        dialog.editor.backspace();
+        dialog.refresh_validation();
+        assert!(dialog.validation_cache.as_ref().unwrap().invalid.is_empty());
+        assert_eq!(dialog.validation(), Some(("1 bytes".to_string(), true)));
+        dialog.format_type = FormatType::String;
+        assert_eq!(dialog.validation(), Some(("3 bytes".to_string(), true)));
+    }
+
+    #[test]
+    fn test_repeat_fields() {
+        let mut dialog = InputDialog::new();
+        assert_eq!(dialog.repeat_spec(), Ok(None));
+
+        dialog.focus_next_repeat_field();
+        dialog.paste("1500");
+        dialog.focus_next_repeat_field();
+        dialog.paste("7");
+        dialog.focus_next_repeat_field();
+        dialog.paste("ping");
+        assert_eq!(dialog.input(), "ping");
+        assert_eq!(
+            dialog.repeat_spec(),
+            Ok(Some(RepeatSpec {
+                interval: Duration::from_millis(1500),
+                count: Some(7),
+            }))
+        );
+
+        dialog.repeat_count.set_text("");
+        assert_eq!(dialog.repeat_spec().unwrap().unwrap().count, None);
+        dialog.repeat_interval.set_text("0");
+        assert!(dialog.repeat_spec().is_err());
+        dialog.repeat_interval.set_text("99");
+        assert!(dialog.repeat_spec().is_err());
+        dialog.repeat_interval.set_text("100");
+        assert!(dialog.repeat_spec().is_ok());
+        dialog.repeat_interval.set_text("");
+        dialog.repeat_count.set_text("3");
+        assert!(dialog.repeat_spec().is_err());
+    }
+
+    #[test]
+    fn test_paste_into_slot_name() {
+        let mut dialog = InputDialog::new();
+        dialog.edit_slot(1, None);
+        dialog.paste("Reset\nboard");
+        assert_eq!(dialog.slot.as_ref().unwrap().name.text(), "Reset board");
+        assert!(dialog.editor.is_empty());
+
+        dialog.toggle_name_focus();
+        dialog.paste("AT+RST");
+        assert_eq!(dialog.input(), "AT+RST");
+    }
+}
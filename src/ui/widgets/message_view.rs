@@ -2,24 +2,189 @@ use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span, Text},
-    widgets::{Block, Borders, List, ListItem, Paragraph, Wrap},
+    widgets::{Block, Borders, List, ListItem},
     Frame,
 };
 
+use bytes::Bytes;
+use std::collections::HashSet;
+
+use crate::app::DisplayFormat;
 use crate::ui::widgets::tabs::TabsState;
+use crate::utils::data_format::{bytes_to_display_string, bytes_to_hex};
+
+/// 十六进制转储每行字节数
+const DUMP_BYTES_PER_LINE: usize = 16;
+/// 折叠时每条消息显示的转储行数
+const DUMP_COLLAPSED_LINES: usize = 8;
+
+/// 视图消息内容
+#[derive(Debug, Clone)]
+pub enum MessageBody {
+    /// 提示文本 (事件、错误等)，不受显示格式影响
+    Text(String),
+    /// 原始数据，显示时按显示格式渲染
+    Data(Bytes),
+}
+
+/// 视图中的单条消息
+#[derive(Debug, Clone)]
+pub struct ViewMessage {
+    /// 前缀 (时间戳、连接、帧类型等)
+    pub prefix: String,
+    /// 消息内容
+    pub body: MessageBody,
+    /// 消息编号 (加入视图时分配，用于记录选中和展开状态，0 表示未加入视图)
+    id: usize,
+}
+
+impl ViewMessage {
+    /// 创建提示文本消息
+    pub fn text(prefix: String, text: String) -> Self {
+        Self {
+            prefix,
+            body: MessageBody::Text(text),
+            id: 0,
+        }
+    }
+
+    /// 创建原始数据消息
+    pub fn data(prefix: String, data: Bytes) -> Self {
+        Self {
+            prefix,
+            body: MessageBody::Data(data),
+            id: 0,
+        }
+    }
+
+    /// 按显示格式生成显示文本
+    pub fn render(&self, format: DisplayFormat) -> String {
+        let body = match &self.body {
+            MessageBody::Text(text) => text.clone(),
+            MessageBody::Data(data) => match format {
+                DisplayFormat::String => bytes_to_display_string(data),
+                DisplayFormat::Hex => bytes_to_hex(data),
+                DisplayFormat::Both => {
+                    // 十六进制另起一行，与正文对齐
+                    let indent = " ".repeat(self.prefix.chars().count() + 1);
+                    format!("{}\n{}{}", bytes_to_display_string(data), indent, bytes_to_hex(data))
+                }
+            },
+        };
+        if self.prefix.is_empty() {
+            body
+        } else {
+            format!("{} {}", self.prefix, body)
+        }
+    }
+
+    /// 按十六进制转储格式生成显示文本，`max_lines` 为转储的最大行数 (None 表示全部显示)
+    pub fn render_dump(&self, max_lines: Option<usize>) -> Text<'static> {
+        match &self.body {
+            MessageBody::Text(_) => Text::raw(self.render(DisplayFormat::String)),
+            MessageBody::Data(data) => {
+                let header = format!("{} ({} bytes)", self.prefix, data.len());
+                let mut lines = vec![Line::raw(header.trim_start().to_string())];
+                lines.extend(hex_dump_lines(data, max_lines));
+                Text::from(lines)
+            }
+        }
+    }
+}
+
+/// 生成 xxd 风格的十六进制转储行 (偏移 / 16 字节十六进制 / ASCII)，超出 `max_lines` 的部分折叠为一行提示
+fn hex_dump_lines(data: &[u8], max_lines: Option<usize>) -> Vec<Line<'static>> {
+    let total = data.len().div_ceil(DUMP_BYTES_PER_LINE);
+    let shown = max_lines.map_or(total, |max| total.min(max));
+    let mut lines: Vec<Line> = data
+        .chunks(DUMP_BYTES_PER_LINE)
+        .take(shown)
+        .enumerate()
+        .map(|(index, chunk)| hex_dump_line(index * DUMP_BYTES_PER_LINE, chunk))
+        .collect();
+    if shown < total {
+        lines.push(Line::styled(
+            format!("... {} more lines hidden", total - shown),
+            Style::default().fg(Color::DarkGray),
+        ));
+    }
+    lines
+}
+
+/// 生成单行转储，不可打印字节高亮显示
+fn hex_dump_line(offset: usize, chunk: &[u8]) -> Line<'static> {
+    let mut spans = vec![Span::styled(format!("{:08x}:", offset), Style::default().fg(Color::Blue))];
+    for (index, byte) in chunk.iter().enumerate() {
+        // 每两个字节一组
+        if index % 2 == 0 {
+            spans.push(Span::raw(" "));
+        }
+        spans.push(Span::styled(format!("{:02x}", byte), dump_byte_style(*byte)));
+    }
+
+    // 不足一行时补齐十六进制列，使 ASCII 列对齐
+    let full_width = DUMP_BYTES_PER_LINE * 2 + DUMP_BYTES_PER_LINE / 2;
+    let width = chunk.len() * 2 + chunk.len().div_ceil(2);
+    spans.push(Span::raw(" ".repeat(full_width - width + 2)));
+
+    for byte in chunk {
+        let c = if is_printable(*byte) { *byte as char } else { '.' };
+        spans.push(Span::styled(c.to_string(), dump_byte_style(*byte)));
+    }
+    Line::from(spans)
+}
+
+/// 是否为可打印 ASCII 字符
+fn is_printable(byte: u8) -> bool {
+    (0x20..=0x7e).contains(&byte)
+}
+
+/// 转储中字节的显示样式: 空字节暗色，其他不可打印字节高亮
+fn dump_byte_style(byte: u8) -> Style {
+    match byte {
+        0 => Style::default().fg(Color::DarkGray),
+        byte if is_printable(byte) => Style::default(),
+        _ => Style::default().fg(Color::Yellow),
+    }
+}
+
+/// 每个消息列表 (包括各连接的标签页) 保留的最大消息数，避免内存占用过多
+const MAX_MESSAGES: usize = 100;
+
+/// 添加消息，只保留最新的 MAX_MESSAGES 条
+pub fn push_message(messages: &mut Vec<ViewMessage>, message: ViewMessage) {
+    messages.push(message);
+    if messages.len() > MAX_MESSAGES {
+        messages.drain(..messages.len() - MAX_MESSAGES);
+    }
+}
 
 /// 消息视图组件
 pub struct MessageView {
     /// 标题
     title: String,
     /// 消息列表
-    messages: Vec<String>,
+    messages: Vec<ViewMessage>,
     /// 是否有多个连接 (需要使用 tabs)
     has_multiple_connections: bool,
     /// 标签页状态 (用于多连接)
     tabs: Option<TabsState>,
     /// 滚动位置
     scroll: usize,
+    /// 已空闲超时的连接标签页
+    stale_connections: HashSet<String>,
+    /// 数据显示格式
+    display_format: DisplayFormat,
+    /// 是否以十六进制转储格式显示
+    hex_dump: bool,
+    /// 下一条消息的编号
+    next_id: usize,
+    /// 选中的消息编号 (None 表示未选中，跟随最新消息)
+    selected: Option<usize>,
+    /// 十六进制转储展开显示全部行的消息编号
+    expanded: HashSet<usize>,
+    /// 各连接尚未组成完整帧的数据 (按连接名称)
+    partials: Vec<(String, ViewMessage)>,
 }
 
 impl MessageView {
@@ -30,21 +195,102 @@ impl MessageView {
             has_multiple_connections: false,
             tabs: None,
             scroll: 0,
+            stale_connections: HashSet::new(),
+            display_format: DisplayFormat::String,
+            hex_dump: false,
+            next_id: 1,
+            selected: None,
+            expanded: HashSet::new(),
+            partials: Vec::new(),
         }
     }
-    /// 添加消息
-    pub fn add_message(&mut self, message: String) {
-        self.messages.push(message);
 
-        // 自动滚动到底部
-        if self.messages.len() > 100 {
-            // 保持最新的100条消息，避免内存占用过多
-            self.messages = self.messages.split_off(self.messages.len() - 100);
+    /// 设置数据显示格式
+    pub fn set_display_format(&mut self, format: DisplayFormat) {
+        self.display_format = format;
+    }
+
+    /// 切换十六进制转储显示
+    pub fn toggle_hex_dump(&mut self) {
+        self.hex_dump = !self.hex_dump;
+    }
+
+    /// 展开或折叠选中消息的十六进制转储 (未选中时为最新一条)
+    pub fn toggle_dump_expanded(&mut self) {
+        let id = self.selected.or_else(|| self.current_messages().last().map(|message| message.id));
+        if let Some(id) = id {
+            if !self.expanded.remove(&id) {
+                self.expanded.insert(id);
+            }
         }
     }
 
+    /// 选中上一条消息 (未选中时选中最新一条)
+    pub fn select_previous(&mut self) {
+        let messages = self.current_messages();
+        let index = match self.selected_position(messages) {
+            Some(index) => index.saturating_sub(1),
+            None if messages.is_empty() => return,
+            None => messages.len() - 1,
+        };
+        self.selected = Some(messages[index].id);
+    }
+
+    /// 选中下一条消息，已是最新一条时取消选中
+    pub fn select_next(&mut self) {
+        let messages = self.current_messages();
+        self.selected = match self.selected_position(messages) {
+            Some(index) if index + 1 < messages.len() => Some(messages[index + 1].id),
+            _ => None,
+        };
+    }
+
+    /// 当前显示的消息列表 (有多个连接时为当前标签页的消息)
+    fn current_messages(&self) -> &[ViewMessage] {
+        match &self.tabs {
+            Some(tabs) if self.has_multiple_connections && tabs.index < tabs.contents.len() => &tabs.contents[tabs.index],
+            _ => &self.messages,
+        }
+    }
+
+    /// 选中消息在列表中的位置
+    fn selected_position(&self, messages: &[ViewMessage]) -> Option<usize> {
+        let selected = self.selected?;
+        messages.iter().position(|message| message.id == selected)
+    }
+
+    /// 为新消息分配编号
+    fn assign_id(&mut self, mut message: ViewMessage) -> ViewMessage {
+        message.id = self.next_id;
+        self.next_id += 1;
+        message
+    }
+
+    /// 清除已移出所有消息列表的消息的选中和展开状态
+    fn prune_states(&mut self) {
+        if self.selected.is_none() && self.expanded.is_empty() {
+            return;
+        }
+        let tab_messages = self.tabs.iter().flat_map(|tabs| tabs.contents.iter().flatten());
+        let ids: HashSet<usize> = self.messages.iter().chain(tab_messages).map(|message| message.id).collect();
+        self.expanded.retain(|id| ids.contains(id));
+        self.selected = self.selected.filter(|id| ids.contains(id));
+    }
+
+    /// 添加消息
+    pub fn add_message(&mut self, message: ViewMessage) {
+        let message = self.assign_id(message);
+        // 有标签页时同时显示在默认标签页
+        if let Some(tabs) = &mut self.tabs {
+            tabs.add_message(0, message.clone());
+        }
+        push_message(&mut self.messages, message);
+        self.prune_states();
+    }
+
     /// 添加消息到指定标签页
-    pub fn add_message_to_tab(&mut self, tab_index: usize, message: String) {
+    pub fn add_message_to_tab(&mut self, tab_index: usize, message: ViewMessage) {
+        let message = self.assign_id(message);
         if let Some(tabs) = &mut self.tabs {
             tabs.add_message(tab_index, message);
         } else {
@@ -54,6 +300,7 @@ impl MessageView {
                 tabs.add_message(tab_index, message);
             }
         }
+        self.prune_states();
     }
 
     /// 初始化标签页
@@ -71,18 +318,38 @@ impl MessageView {
         }
     }
 
-    /// 添加新的连接标签页
+    /// 添加新的连接标签页，标签页已存在时仅恢复其活跃状态
     pub fn add_connection(&mut self, connection_name: &str) {
-        self.has_multiple_connections = true;
+        self.initialize_tabs();
+        self.stale_connections.remove(connection_name);
         if let Some(tabs) = &mut self.tabs {
-            tabs.add_tab(connection_name.to_string());
-        } else {
-            let mut tabs = TabsState::new(vec!["Default".to_string()]);
-            tabs.add_tab(connection_name.to_string());
-            self.tabs = Some(tabs);
+            if tabs.position(connection_name).is_none() {
+                tabs.add_tab(connection_name.to_string());
+            }
         }
     }
 
+    /// 添加消息到指定连接的标签页，没有对应标签页时添加到默认列表
+    pub fn add_message_to_connection(&mut self, connection_name: &str, message: ViewMessage) {
+        match self.tabs.as_ref().and_then(|tabs| tabs.position(connection_name)) {
+            Some(index) => self.add_message_to_tab(index, message),
+            None => self.add_message(message),
+        }
+    }
+
+    /// 设置连接尚未组成完整帧的数据，显示在消息列表末尾，None 表示清除
+    pub fn set_partial(&mut self, connection_name: &str, partial: Option<ViewMessage>) {
+        self.partials.retain(|(name, _)| name != connection_name);
+        if let Some(partial) = partial {
+            self.partials.push((connection_name.to_string(), partial));
+        }
+    }
+
+    /// 标记连接标签页为空闲超时
+    pub fn set_connection_stale(&mut self, connection_name: &str) {
+        self.stale_connections.insert(connection_name.to_string());
+    }
+
     /// 关闭连接标签页
     pub fn close_connection(&mut self, tab_index: usize) {
         if let Some(tabs) = &mut self.tabs {
@@ -94,18 +361,23 @@ impl MessageView {
     }
 
     pub fn close_connection_by_title(&mut self, title: &str) {
+        self.stale_connections.remove(title);
+        self.partials.retain(|(name, _)| name != title);
         if let Some(tabs) = &mut self.tabs {
             tabs.remove_tab_by_title(title);
             if tabs.titles.len() <= 1 {
                 self.has_multiple_connections = false;
             }
         }
+        self.prune_states();
     }
 
     /// 清除所有消息
     pub fn clear(&mut self) {
         self.messages.clear();
         self.scroll = 0;
+        self.selected = None;
+        self.expanded.clear();
 
         if let Some(tabs) = &mut self.tabs {
             for content in &mut tabs.contents {
@@ -158,7 +430,12 @@ impl MessageView {
     /// 绘制视图
     pub fn draw(&self, frame: &mut Frame, area: Rect) {
         // 创建一个带边框的块
-        let block = Block::default().title(self.title.clone()).borders(Borders::ALL);
+        let title = if self.hex_dump {
+            format!("{} [Hex Dump]", self.title)
+        } else {
+            format!("{} [{}]", self.title, self.display_format)
+        };
+        let block = Block::default().title(title).borders(Borders::ALL);
 
         // 绘制边框
         frame.render_widget(block.clone(), area);
@@ -180,7 +457,17 @@ impl MessageView {
             // 绘制标签页
             if let Some(tabs) = &self.tabs {
                 // 渲染标签页标题
-                let titles: Vec<Line> = tabs.titles.iter().map(|t| Line::from(t.as_str())).collect();
+                let titles: Vec<Line> = tabs
+                    .titles
+                    .iter()
+                    .map(|t| {
+                        if self.stale_connections.contains(t) {
+                            Line::styled(format!("{} (stale)", t), Style::default().fg(Color::DarkGray))
+                        } else {
+                            Line::from(t.as_str())
+                        }
+                    })
+                    .collect();
 
                 let tabs_widget = ratatui::widgets::Tabs::new(titles)
                     .block(Block::default().borders(Borders::BOTTOM))
@@ -192,25 +479,16 @@ impl MessageView {
 
                 // 渲染当前选中标签页的内容
                 if tabs.index < tabs.contents.len() {
-                    let messages = &tabs.contents[tabs.index];
+                    // 默认标签页显示所有连接未完成的帧
+                    let connection = (tabs.index > 0).then(|| tabs.titles[tabs.index].as_str());
+                    let mut messages = self.render_messages(&tabs.contents[tabs.index]);
+                    messages.extend(self.render_partials(connection));
                     let max_visible = chunks[1].height as usize;
 
-                    let items: Vec<ListItem> = if !messages.is_empty() {
-                        let start_idx = if messages.len() > max_visible {
-                            messages.len() - max_visible + self.scroll
-                        } else {
-                            0
-                        };
-
-                        let visible_messages = &messages[start_idx.min(messages.len())..];
-
-                        visible_messages
-                            .iter()
-                            .map(|m| ListItem::new(Line::from(vec![Span::raw(m)])))
-                            .collect()
-                    } else {
-                        Vec::new()
-                    };
+                    // 选中的消息在可见范围之上时从选中的消息开始显示
+                    let start_idx = visible_start(&messages, max_visible, self.scroll);
+                    let start_idx = self.selected_position(&tabs.contents[tabs.index]).map_or(start_idx, |index| start_idx.min(index));
+                    let items: Vec<ListItem> = messages.into_iter().skip(start_idx).map(ListItem::new).collect();
 
                     // 创建列表小部件
                     let list = List::new(items)
@@ -225,21 +503,12 @@ impl MessageView {
             let max_visible = inner_area.height as usize;
 
             // 创建消息列表
-            let start_idx = if self.messages.len() > max_visible {
-                self.messages.len() - max_visible + self.scroll
-            } else {
-                0
-            };
-
-            let visible_messages = &self.messages[start_idx.min(self.messages.len())..];
-
-            let items: Vec<ListItem> = visible_messages
-                .iter()
-                .map(|m| {
-                    // 每条消息作为列表项
-                    ListItem::new(Line::from(vec![Span::raw(m)]))
-                })
-                .collect();
+            let mut messages = self.render_messages(&self.messages);
+            messages.extend(self.render_partials(None));
+            let start_idx = visible_start(&messages, max_visible, self.scroll);
+            let start_idx = self.selected_position(&self.messages).map_or(start_idx, |index| start_idx.min(index));
+            // 每条消息作为列表项，多行消息按行展开
+            let items: Vec<ListItem> = messages.into_iter().skip(start_idx).map(ListItem::new).collect();
 
             // 创建列表小部件
             let list = List::new(items)
@@ -249,4 +518,118 @@ impl MessageView {
             frame.render_widget(list, inner_area);
         }
     }
+
+    /// 按当前显示格式渲染消息列表，选中的消息反色显示
+    fn render_messages(&self, messages: &[ViewMessage]) -> Vec<Text<'static>> {
+        messages
+            .iter()
+            .map(|m| {
+                let text = if self.hex_dump {
+                    let max_lines = (!self.expanded.contains(&m.id)).then_some(DUMP_COLLAPSED_LINES);
+                    m.render_dump(max_lines)
+                } else {
+                    Text::raw(m.render(self.display_format))
+                };
+                if m.id != 0 && self.selected == Some(m.id) {
+                    text.patch_style(Style::default().add_modifier(Modifier::REVERSED))
+                } else {
+                    text
+                }
+            })
+            .collect()
+    }
+
+    /// 渲染未完成的帧 (暗色显示)，`connection` 为 None 时渲染所有连接的
+    fn render_partials(&self, connection: Option<&str>) -> Vec<Text<'static>> {
+        let partials: Vec<ViewMessage> = self
+            .partials
+            .iter()
+            .filter(|(name, _)| connection.is_none_or(|connection| connection == name))
+            .map(|(_, partial)| partial.clone())
+            .collect();
+        self.render_messages(&partials)
+            .into_iter()
+            .map(|text| text.patch_style(Style::default().fg(Color::DarkGray)))
+            .collect()
+    }
+}
+
+/// 计算从底部开始能完整显示的第一条消息下标 (多行消息按行数计算高度)
+fn visible_start(messages: &[Text], max_height: usize, scroll: usize) -> usize {
+    let mut height = 0;
+    let mut start_idx = messages.len();
+    for (index, message) in messages.iter().enumerate().rev() {
+        height += message.height().max(1);
+        if height > max_height {
+            break;
+        }
+        start_idx = index;
+    }
+    (start_idx + scroll).min(messages.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn line_text(line: &Line) -> String {
+        line.spans.iter().map(|span| span.content.as_ref()).collect()
+    }
+
+    #[test]
+    fn test_hex_dump_line_matches_xxd() {
+        let line = hex_dump_line(0, b"hi\r\n\x00\xff\x01");
+        assert_eq!(line_text(&line), format!("00000000: 6869 0d0a 00ff 01{}hi.....", " ".repeat(24)));
+
+        let line = hex_dump_line(16, b"0123456789abcdef");
+        assert_eq!(line_text(&line), "00000010: 3031 3233 3435 3637 3839 6162 6364 6566  0123456789abcdef");
+    }
+
+    #[test]
+    fn test_hex_dump_collapse() {
+        let data = vec![0u8; DUMP_BYTES_PER_LINE * 10 + 1];
+        let lines = hex_dump_lines(&data, Some(DUMP_COLLAPSED_LINES));
+        assert_eq!(lines.len(), DUMP_COLLAPSED_LINES + 1);
+        assert_eq!(line_text(&lines[DUMP_COLLAPSED_LINES]), "... 3 more lines hidden");
+        assert_eq!(hex_dump_lines(&data, None).len(), 11);
+    }
+
+    #[test]
+    fn test_connection_tab_message_limit() {
+        let mut view = MessageView::new("Receive");
+        view.add_connection("127.0.0.1:9000");
+        for index in 0..MAX_MESSAGES + 20 {
+            view.add_message_to_connection("127.0.0.1:9000", ViewMessage::text(String::new(), index.to_string()));
+        }
+        let messages = &view.tabs.as_ref().unwrap().contents[1];
+        assert_eq!(messages.len(), MAX_MESSAGES);
+        assert_eq!(messages[0].render(DisplayFormat::String), "20");
+    }
+
+    #[test]
+    fn test_dump_expanded_per_message() {
+        let mut view = MessageView::new("Receive");
+        for _ in 0..3 {
+            view.add_message(ViewMessage::data(String::new(), Bytes::from(vec![0u8; DUMP_BYTES_PER_LINE * 10])));
+        }
+        view.toggle_hex_dump();
+        let heights = |view: &MessageView| -> Vec<usize> {
+            view.render_messages(&view.messages).iter().map(Text::height).collect()
+        };
+
+        // 未选中时展开最新一条，选中后只展开选中的消息
+        view.toggle_dump_expanded();
+        assert_eq!(heights(&view), vec![10, 10, 11]);
+        view.select_previous();
+        view.select_previous();
+        view.toggle_dump_expanded();
+        assert_eq!(heights(&view), vec![10, 11, 11]);
+        view.toggle_dump_expanded();
+        assert_eq!(heights(&view), vec![10, 10, 11]);
+
+        // 选中最新一条后继续向下取消选中
+        view.select_next();
+        view.select_next();
+        assert_eq!(view.selected, None);
+    }
 }
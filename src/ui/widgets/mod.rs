@@ -1,4 +1,6 @@
 pub mod status_bar;
 pub mod message_view;
 pub mod input_dialog;
+pub mod repeater_panel;
+pub mod text_editor;
 pub mod tabs;
\ No newline at end of file
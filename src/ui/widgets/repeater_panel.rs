@@ -0,0 +1,105 @@
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+    Frame,
+};
+
+use crate::protocols::MessageType;
+use crate::utils::data_format::bytes_to_display_string;
+use crate::utils::repeater::Repeater;
+
+/// 内容预览的最大字符数
+const PREVIEW_CHARS: usize = 32;
+
+/// 面板最多同时显示的任务数，超出时随选择滚动
+const MAX_VISIBLE: usize = 5;
+
+/// 重复发送任务面板
+#[derive(Default)]
+pub struct RepeaterPanel {
+    /// 当前选择的任务下标
+    pub selected: usize,
+}
+
+impl RepeaterPanel {
+    /// 面板高度 (边框加每个任务一行，最多显示 MAX_VISIBLE 个任务)
+    pub fn height(repeaters: &[Repeater]) -> u16 {
+        repeaters.len().min(MAX_VISIBLE) as u16 + 2
+    }
+
+    /// 选择下一个任务
+    pub fn select_next(&mut self, count: usize) {
+        if count > 0 {
+            self.selected = (self.selected + 1) % count;
+        }
+    }
+
+    /// 任务数量变化后保持选择有效
+    pub fn clamp(&mut self, count: usize) {
+        self.selected = self.selected.min(count.saturating_sub(1));
+    }
+
+    /// 绘制面板，`broadcast` 表示未指定目标的任务发送到所有客户端
+    pub fn draw(&self, frame: &mut Frame, area: Rect, repeaters: &[Repeater], broadcast: bool) {
+        let default_target = if broadcast { "all clients" } else { "current connection" };
+        // 滚动到能显示当前选择的位置
+        let visible = (area.height.saturating_sub(2) as usize).max(1);
+        let offset = self.selected.saturating_sub(visible - 1).min(repeaters.len().saturating_sub(visible));
+        let lines: Vec<Line> = repeaters
+            .iter()
+            .enumerate()
+            .skip(offset)
+            .take(visible)
+            .map(|(index, repeater)| {
+                let selected = index == self.selected;
+                let (state, color) = if repeater.paused {
+                    ("Paused ", Color::Yellow)
+                } else {
+                    ("Running", Color::Green)
+                };
+                let style = if selected {
+                    Style::default().add_modifier(Modifier::REVERSED)
+                } else {
+                    Style::default()
+                };
+                Line::from(vec![
+                    Span::styled(format!("{} #{} ", if selected { ">" } else { " " }, repeater.id), style),
+                    Span::styled(state, Style::default().fg(color)),
+                    Span::raw(format!(
+                        " sent {} | {} | to {} | {}",
+                        repeater.progress(),
+                        repeater.spec.describe(),
+                        repeater.target.as_deref().unwrap_or(default_target),
+                        preview(&repeater.content)
+                    )),
+                ])
+            })
+            .collect();
+
+        let title = if repeaters.len() > visible {
+            format!(" Repeaters ({}-{} of {}) ", offset + 1, offset + lines.len(), repeaters.len())
+        } else {
+            " Repeaters ".to_string()
+        };
+        let block = Block::default()
+            .title(title)
+            .title_bottom(Line::from(" R: Select | P: Pause/Resume | X: Remove ").right_aligned())
+            .borders(Borders::ALL);
+        frame.render_widget(Paragraph::new(lines).block(block), area);
+    }
+}
+
+/// 单行显示的内容预览
+fn preview(content: &MessageType) -> String {
+    let text = match content.to_bytes() {
+        Some(data) => bytes_to_display_string(&data).replace('\n', "\\n").replace('\t', "\\t"),
+        None => format!("{:?}", content),
+    };
+    if text.chars().count() > PREVIEW_CHARS {
+        format!("{}...", text.chars().take(PREVIEW_CHARS).collect::<String>())
+    } else {
+        text
+    }
+}
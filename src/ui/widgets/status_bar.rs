@@ -1,7 +1,7 @@
 use ratatui::{
     layout::Rect,
     style::{Color, Style},
-    text::Span,
+    text::{Line, Span},
     widgets::{Block, Borders, Paragraph},
     Frame,
 };
@@ -22,7 +22,7 @@ impl Default for StatusBar {
 impl StatusBar {
     /// 绘制顶部状态栏
     pub fn draw_top_bar(&self, frame: &mut Frame, area: Rect, app: &App) {
-        let status_text = format!(
+        let mut status_text = format!(
             " Sent: {} bytes | Received: {} bytes | Status: {} ",
             app.stats.sent_bytes,
             app.stats.received_bytes,
@@ -32,6 +32,14 @@ impl StatusBar {
                 "Disconnected"
             }
         );
+        // 显示最近一次请求的耗时
+        if let Some(ref timing) = app.stats.timing {
+            status_text.push_str(&format!("| {} ", timing.describe()));
+        }
+        // 显示文件发送进度
+        if let Some(ref transfer) = app.file_transfer {
+            status_text.push_str(&format!("| File: {} ", transfer.describe_progress()));
+        }
 
         let status_widget = Paragraph::new(Span::styled(
             status_text,
@@ -41,15 +49,34 @@ impl StatusBar {
         frame.render_widget(status_widget, area);
     }
 
-    /// 绘制底部状态栏 (快捷键提示)
-    pub fn draw_bottom_bar(&self, frame: &mut Frame, area: Rect) {
-        let help_text = " Ctrl+C: Quit | I: Input Message ";
+    /// 绘制底部状态栏 (快捷键提示和快捷发送槽位)
+    pub fn draw_bottom_bar(&self, frame: &mut Frame, area: Rect, app: &App) {
+        let help_area = Rect::new(area.x, area.y, area.width, area.height.min(1));
+        let slots_area = Rect::new(area.x, area.y + help_area.height, area.width, area.height - help_area.height);
+
+        let mut help_text =
+            " Ctrl+C: Quit | I: Input Message | F: Display Format | D: Hex Dump | Up/Down: Select | E: Expand Dump | Tab: Switch Connection ".to_string();
+        if app.file_transfer.is_some() {
+            help_text.push_str("| Esc: Cancel Transfer ");
+        }
 
         let help_widget = Paragraph::new(Span::styled(
             help_text,
             Style::default().fg(Color::Black).bg(Color::LightCyan),
         ));
 
-        frame.render_widget(help_widget, area);
+        frame.render_widget(help_widget, help_area);
+
+        // 快捷发送槽位列表
+        let mut spans = vec![Span::raw(" ")];
+        for slot in app.slots.slots() {
+            spans.push(Span::styled(format!("F{}", slot.key), Style::default().fg(Color::Black).bg(Color::Gray)));
+            spans.push(Span::raw(format!(" {}  ", slot.name)));
+        }
+        if app.slots.slots().is_empty() {
+            spans.push(Span::styled("No quick-send slots", Style::default().fg(Color::Gray)));
+        }
+        spans.push(Span::styled("| Shift+F1-F12: Edit Slot ", Style::default().fg(Color::Gray)));
+        frame.render_widget(Paragraph::new(Line::from(spans)), slots_area);
     }
 }
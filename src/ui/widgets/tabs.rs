@@ -6,6 +6,8 @@ use ratatui::{
     Frame,
 };
 
+use crate::ui::widgets::message_view::{push_message, ViewMessage};
+
 /// Tab页管理状态
 pub struct TabsState {
     /// 所有Tab标题
@@ -13,7 +15,7 @@ pub struct TabsState {
     /// 当前索引
     pub index: usize,
     /// Tab所包含的内容
-    pub contents: Vec<Vec<String>>,
+    pub contents: Vec<Vec<ViewMessage>>,
 }
 
 impl TabsState {
@@ -51,15 +53,20 @@ impl TabsState {
 
     /// 根据标签页Title移除标签页
     pub fn remove_tab_by_title(&mut self, title: &str) {
-        if let Some(index) = self.titles.iter().position(|t| t == title) {
+        if let Some(index) = self.position(title) {
             self.remove_tab(index);
         }
     }
 
+    /// 根据标签页Title查找索引
+    pub fn position(&self, title: &str) -> Option<usize> {
+        self.titles.iter().position(|t| t == title)
+    }
+
     /// 向指定Tab添加消息
-    pub fn add_message(&mut self, tab_index: usize, message: String) {
+    pub fn add_message(&mut self, tab_index: usize, message: ViewMessage) {
         if tab_index < self.contents.len() {
-            self.contents[tab_index].push(message);
+            push_message(&mut self.contents[tab_index], message);
         }
     }
 
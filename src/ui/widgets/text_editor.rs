@@ -0,0 +1,406 @@
+use crossterm::event::{KeyCode, KeyModifiers};
+use ratatui::{
+    style::Style,
+    text::{Line, Span},
+};
+use unicode_width::UnicodeWidthChar;
+
+/// 多行文本编辑器 (光标以字符为单位，显示位置按字符宽度计算)
+pub struct TextEditor {
+    /// 文本行
+    lines: Vec<String>,
+    /// 光标所在行
+    row: usize,
+    /// 光标所在列 (字符下标)
+    col: usize,
+    /// 上下移动时保持的显示列
+    preferred_width: Option<usize>,
+    /// 垂直滚动偏移 (行)
+    scroll_row: usize,
+    /// 水平滚动偏移 (显示列)
+    scroll_width: usize,
+    /// 内容版本，每次修改文本时递增 (用于缓存校验结果)
+    revision: u64,
+}
+
+impl Default for TextEditor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TextEditor {
+    pub fn new() -> Self {
+        Self {
+            lines: vec![String::new()],
+            row: 0,
+            col: 0,
+            preferred_width: None,
+            scroll_row: 0,
+            scroll_width: 0,
+            revision: 0,
+        }
+    }
+
+    /// 获取全部文本 (多行以 `\n` 连接)
+    pub fn text(&self) -> String {
+        self.lines.join("\n")
+    }
+
+    /// 替换全部文本，光标移动到末尾
+    pub fn set_text(&mut self, text: &str) {
+        *self = Self {
+            revision: self.revision + 1,
+            ..Self::new()
+        };
+        self.insert_str(text);
+    }
+
+    /// 内容版本
+    pub fn revision(&self) -> u64 {
+        self.revision
+    }
+
+    /// 光标所在行
+    pub fn cursor_row(&self) -> usize {
+        self.row
+    }
+
+    /// 行数
+    pub fn line_count(&self) -> usize {
+        self.lines.len()
+    }
+
+    /// 文本是否为空
+    pub fn is_empty(&self) -> bool {
+        self.lines.len() == 1 && self.lines[0].is_empty()
+    }
+
+    /// 处理编辑按键，返回按键是否被处理
+    pub fn handle_key(&mut self, key: KeyCode, modifiers: KeyModifiers) -> bool {
+        let word = modifiers.intersects(KeyModifiers::CONTROL | KeyModifiers::ALT);
+        match key {
+            // 换行 (Shift+Enter，不支持的终端使用 Alt+Enter 或 Ctrl+J)
+            KeyCode::Enter => self.insert_newline(),
+            KeyCode::Char('j') if modifiers.contains(KeyModifiers::CONTROL) => self.insert_newline(),
+            KeyCode::Char(c) if !modifiers.intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) => self.insert_char(c),
+            KeyCode::Backspace => self.backspace(),
+            KeyCode::Delete => self.delete(),
+            KeyCode::Left if word => self.move_word_left(),
+            KeyCode::Right if word => self.move_word_right(),
+            KeyCode::Left => self.move_left(),
+            KeyCode::Right => self.move_right(),
+            KeyCode::Up => self.move_vertical(-1),
+            KeyCode::Down => self.move_vertical(1),
+            KeyCode::Home if word => (self.row, self.col) = (0, 0),
+            KeyCode::End if word => {
+                self.row = self.lines.len() - 1;
+                self.col = self.line_len(self.row);
+            }
+            KeyCode::Home => self.col = 0,
+            KeyCode::End => self.col = self.line_len(self.row),
+            _ => return false,
+        }
+        // 上下移动之外的操作重置保持的显示列
+        if !matches!(key, KeyCode::Up | KeyCode::Down) {
+            self.preferred_width = None;
+        }
+        true
+    }
+
+    /// 在光标处插入字符
+    pub fn insert_char(&mut self, c: char) {
+        let index = self.byte_index(self.row, self.col);
+        self.lines[self.row].insert(index, c);
+        self.col += 1;
+        self.revision += 1;
+    }
+
+    /// 在光标处插入文本 (`\r\n` 和 `\r` 视为换行)，每行一次性插入
+    pub fn insert_str(&mut self, text: &str) {
+        let text = text.replace("\r\n", "\n").replace('\r', "\n");
+        for (index, segment) in text.split('\n').enumerate() {
+            if index > 0 {
+                self.insert_newline();
+            }
+            let byte_index = self.byte_index(self.row, self.col);
+            self.lines[self.row].insert_str(byte_index, segment);
+            self.col += segment.chars().count();
+        }
+        self.revision += 1;
+    }
+
+    /// 在光标处换行
+    pub fn insert_newline(&mut self) {
+        let index = self.byte_index(self.row, self.col);
+        let rest = self.lines[self.row].split_off(index);
+        self.lines.insert(self.row + 1, rest);
+        self.row += 1;
+        self.col = 0;
+        self.revision += 1;
+    }
+
+    /// 删除光标前的字符，位于行首时与上一行合并
+    pub fn backspace(&mut self) {
+        self.revision += 1;
+        if self.col > 0 {
+            self.col -= 1;
+            let index = self.byte_index(self.row, self.col);
+            self.lines[self.row].remove(index);
+        } else if self.row > 0 {
+            let line = self.lines.remove(self.row);
+            self.row -= 1;
+            self.col = self.line_len(self.row);
+            self.lines[self.row].push_str(&line);
+        }
+    }
+
+    /// 删除光标处的字符，位于行尾时与下一行合并
+    pub fn delete(&mut self) {
+        self.revision += 1;
+        if self.col < self.line_len(self.row) {
+            let index = self.byte_index(self.row, self.col);
+            self.lines[self.row].remove(index);
+        } else if self.row + 1 < self.lines.len() {
+            let line = self.lines.remove(self.row + 1);
+            self.lines[self.row].push_str(&line);
+        }
+    }
+
+    /// 光标左移，位于行首时移动到上一行末尾
+    pub fn move_left(&mut self) {
+        if self.col > 0 {
+            self.col -= 1;
+        } else if self.row > 0 {
+            self.row -= 1;
+            self.col = self.line_len(self.row);
+        }
+    }
+
+    /// 光标右移，位于行尾时移动到下一行开头
+    pub fn move_right(&mut self) {
+        if self.col < self.line_len(self.row) {
+            self.col += 1;
+        } else if self.row + 1 < self.lines.len() {
+            self.row += 1;
+            self.col = 0;
+        }
+    }
+
+    /// 光标移动到上一个单词开头
+    pub fn move_word_left(&mut self) {
+        // 跳过光标前的空白 (包括换行)，再跳过同类字符
+        while self.char_before().is_some_and(char::is_whitespace) || (self.col == 0 && self.row > 0) {
+            self.move_left();
+        }
+        if let Some(class) = self.char_before().map(char_class) {
+            while self.char_before().is_some_and(|c| char_class(c) == class) {
+                self.col -= 1;
+            }
+        }
+    }
+
+    /// 光标移动到下一个单词末尾
+    pub fn move_word_right(&mut self) {
+        // 跳过光标后的空白 (包括换行)，再跳过同类字符
+        while self.char_at().is_some_and(char::is_whitespace)
+            || (self.col == self.line_len(self.row) && self.row + 1 < self.lines.len())
+        {
+            self.move_right();
+        }
+        if let Some(class) = self.char_at().map(char_class) {
+            while self.char_at().is_some_and(|c| char_class(c) == class) {
+                self.col += 1;
+            }
+        }
+    }
+
+    /// 光标上下移动，保持显示列
+    fn move_vertical(&mut self, delta: isize) {
+        let Some(row) = self.row.checked_add_signed(delta).filter(|row| *row < self.lines.len()) else {
+            return;
+        };
+        let width = *self.preferred_width.get_or_insert_with(|| str_width(&self.lines[self.row], self.col));
+        self.row = row;
+        // 找到显示宽度不超过目标列的最后一个字符位置
+        let mut current = 0;
+        self.col = 0;
+        for c in self.lines[row].chars() {
+            current += c.width().unwrap_or(0);
+            if current > width {
+                break;
+            }
+            self.col += 1;
+        }
+    }
+
+    /// 按可见区域大小调整滚动位置，生成显示行和光标相对位置 (列, 行)
+    /// `style` 根据字符在全文中的下标 (换行计为一个字符) 返回显示样式
+    pub fn render(&mut self, width: usize, height: usize, style: impl Fn(usize, char) -> Style) -> (Vec<Line<'static>>, (u16, u16)) {
+        let cursor_width = str_width(&self.lines[self.row], self.col);
+
+        // 保证光标可见
+        if self.row < self.scroll_row {
+            self.scroll_row = self.row;
+        } else if height > 0 && self.row >= self.scroll_row + height {
+            self.scroll_row = self.row + 1 - height;
+        }
+        if cursor_width < self.scroll_width {
+            self.scroll_width = cursor_width;
+        } else if width > 0 && cursor_width >= self.scroll_width + width {
+            self.scroll_width = cursor_width + 1 - width;
+        }
+
+        let mut offset: usize = self.lines[..self.scroll_row].iter().map(|line| line.chars().count() + 1).sum();
+        let mut lines = Vec::new();
+        for line in self.lines.iter().skip(self.scroll_row).take(height) {
+            // 样式相同的连续字符合并为一个 Span
+            let mut spans = Vec::new();
+            let (mut run, mut run_style) = (String::new(), Style::default());
+            let mut column = 0;
+            for (index, c) in line.chars().enumerate() {
+                let char_width = c.width().unwrap_or(0);
+                // 只显示完全落在可见区域内的字符
+                if column + char_width > self.scroll_width + width {
+                    break;
+                }
+                if column >= self.scroll_width {
+                    let char_style = style(offset + index, c);
+                    if char_style != run_style && !run.is_empty() {
+                        spans.push(Span::styled(std::mem::take(&mut run), run_style));
+                    }
+                    run_style = char_style;
+                    run.push(c);
+                }
+                column += char_width;
+            }
+            if !run.is_empty() {
+                spans.push(Span::styled(run, run_style));
+            }
+            offset += line.chars().count() + 1;
+            lines.push(Line::from(spans));
+        }
+
+        let cursor = ((cursor_width - self.scroll_width) as u16, (self.row - self.scroll_row) as u16);
+        (lines, cursor)
+    }
+
+    /// 行的字符数
+    fn line_len(&self, row: usize) -> usize {
+        self.lines[row].chars().count()
+    }
+
+    /// 字符下标对应的字节下标
+    fn byte_index(&self, row: usize, col: usize) -> usize {
+        let line = &self.lines[row];
+        line.char_indices().nth(col).map_or(line.len(), |(index, _)| index)
+    }
+
+    /// 光标前的字符
+    fn char_before(&self) -> Option<char> {
+        self.col.checked_sub(1).and_then(|col| self.lines[self.row].chars().nth(col))
+    }
+
+    /// 光标处的字符
+    fn char_at(&self) -> Option<char> {
+        self.lines[self.row].chars().nth(self.col)
+    }
+}
+
+/// 前 `col` 个字符的显示宽度
+fn str_width(line: &str, col: usize) -> usize {
+    line.chars().take(col).map(|c| c.width().unwrap_or(0)).sum()
+}
+
+/// 单词跳转时的字符分类: 空白、单词字符、其他符号
+fn char_class(c: char) -> u8 {
+    if c.is_whitespace() {
+        0
+    } else if c.is_alphanumeric() || c == '_' {
+        1
+    } else {
+        2
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_editing_multibyte_text() {
+        let mut editor = TextEditor::new();
+        editor.insert_str("中文ab");
+        editor.move_left();
+        editor.move_left();
+        editor.backspace();
+        editor.insert_char('x');
+        assert_eq!(editor.text(), "中xab");
+
+        editor.handle_key(KeyCode::Home, KeyModifiers::NONE);
+        editor.handle_key(KeyCode::Delete, KeyModifiers::NONE);
+        assert_eq!(editor.text(), "xab");
+    }
+
+    #[test]
+    fn test_newlines_and_line_joins() {
+        let mut editor = TextEditor::new();
+        editor.insert_str("{\r\n  \"a\": 1\n}");
+        assert_eq!(editor.text(), "{\n  \"a\": 1\n}");
+
+        editor.handle_key(KeyCode::Home, KeyModifiers::NONE);
+        editor.backspace();
+        assert_eq!(editor.text(), "{\n  \"a\": 1}");
+        editor.handle_key(KeyCode::Enter, KeyModifiers::SHIFT);
+        assert_eq!(editor.text(), "{\n  \"a\": 1\n}");
+    }
+
+    #[test]
+    fn test_cursor_movement() {
+        let mut editor = TextEditor::new();
+        editor.insert_str("中文字\nab");
+        // 从显示列 2 向上移动到 "中" 之后
+        editor.handle_key(KeyCode::Up, KeyModifiers::NONE);
+        editor.insert_char('|');
+        assert_eq!(editor.text(), "中|文字\nab");
+
+        editor.set_text("foo.bar  baz");
+        editor.handle_key(KeyCode::Left, KeyModifiers::CONTROL);
+        editor.insert_char('|');
+        assert_eq!(editor.text(), "foo.bar  |baz");
+        editor.handle_key(KeyCode::Home, KeyModifiers::NONE);
+        editor.handle_key(KeyCode::Right, KeyModifiers::CONTROL);
+        editor.insert_char('|');
+        assert_eq!(editor.text(), "foo|.bar  |baz");
+    }
+
+    #[test]
+    fn test_render_scrolls_to_cursor() {
+        let mut editor = TextEditor::new();
+        editor.insert_str("1\n2\n3\n4\n中文");
+        let (lines, cursor) = editor.render(3, 2, |_, _| Style::default());
+        assert_eq!(lines.len(), 2);
+        let last: String = lines[1].spans.iter().map(|span| span.content.as_ref()).collect();
+        // 可见宽度 3 列，光标位于第 4 列，"中" 被滚动出可见区域
+        assert_eq!(last, "文");
+        assert_eq!(cursor, (2, 1));
+
+        // 样式相同的字符合并为一个 Span
+        editor.set_text("abcd");
+        let highlight = Style::default().fg(ratatui::style::Color::Red);
+        let (lines, _) = editor.render(10, 1, |index, _| if index == 2 { highlight } else { Style::default() });
+        let spans: Vec<&str> = lines[0].spans.iter().map(|span| span.content.as_ref()).collect();
+        assert_eq!(spans, vec!["ab", "c", "d"]);
+    }
+
+    #[test]
+    fn test_insert_multiline_paste() {
+        let mut editor = TextEditor::new();
+        editor.insert_str("中[]");
+        editor.move_left();
+        editor.insert_str("a\r\nb\rc");
+        assert_eq!(editor.text(), "中[a\nb\nc]");
+        editor.insert_char('|');
+        assert_eq!(editor.text(), "中[a\nb\nc|]");
+    }
+}
@@ -7,26 +7,100 @@ pub fn bytes_to_hex(bytes: &[u8]) -> String {
         .join(" ")
 }
 
-/// 将十六进制字符串转换为字节数据
+/// 将十六进制字符串转换为字节数据 (忽略空白字符)
 pub fn hex_to_bytes(hex_str: &str) -> Result<Vec<u8>, String> {
-    // 移除所有空格
-    let hex_str = hex_str.replace(' ', "");
-    
+    // 移除所有空白字符
+    let digits: Vec<char> = hex_str.chars().filter(|c| !c.is_whitespace()).collect();
+
+    // 验证所有字符都是十六进制数字
+    let invalid = invalid_hex_chars(hex_str);
+    if !invalid.is_empty() {
+        let invalid: String = invalid.into_iter().map(|(_, c)| c).collect();
+        return Err(format!("Invalid hex characters: {}", invalid));
+    }
+
     // 验证字符串长度是偶数
-    if hex_str.len() % 2 != 0 {
+    if !digits.len().is_multiple_of(2) {
         return Err("Invalid hex string length".to_string());
     }
-    
+
     // 将每两个字符转换为一个字节
-    let mut bytes = Vec::new();
-    for i in (0..hex_str.len()).step_by(2) {
-        let byte_str = &hex_str[i..i + 2];
-        match u8::from_str_radix(byte_str, 16) {
-            Ok(byte) => bytes.push(byte),
-            Err(_) => return Err(format!("Invalid hex characters: {}", byte_str)),
+    let bytes = digits
+        .chunks(2)
+        .map(|pair| {
+            let high = pair[0].to_digit(16).unwrap_or(0) as u8;
+            let low = pair[1].to_digit(16).unwrap_or(0) as u8;
+            (high << 4) | low
+        })
+        .collect();
+
+    Ok(bytes)
+}
+
+/// 判断文本是否像十六进制数据 (只含十六进制数字和空白，位数为偶数且至少两个字节)
+pub fn looks_like_hex(text: &str) -> bool {
+    let digits = text.chars().filter(|c| !c.is_whitespace()).count();
+    digits >= 4 && digits % 2 == 0 && invalid_hex_chars(text).is_empty()
+}
+
+/// 查找十六进制字符串中的非法字符，返回其字符下标和字符 (空白字符视为合法分隔符)
+pub fn invalid_hex_chars(hex_str: &str) -> Vec<(usize, char)> {
+    hex_str
+        .chars()
+        .enumerate()
+        .filter(|(_, c)| !c.is_ascii_hexdigit() && !c.is_whitespace())
+        .collect()
+}
+
+/// 解析 C 风格转义序列 (`\r`、`\n`、`\t`、`\0`、`\\`、`\xHH`、`\u{...}`)，返回转义后的字节数据
+/// 未知的转义序列 (如 `C:\path` 中的 `\p`) 和末尾单独的反斜杠按原样保留
+pub fn unescape(input: &str) -> Result<Vec<u8>, String> {
+    let mut bytes = Vec::with_capacity(input.len());
+    let mut chars = input.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            let mut buffer = [0; 4];
+            bytes.extend_from_slice(c.encode_utf8(&mut buffer).as_bytes());
+            continue;
+        }
+        match chars.next() {
+            Some('r') => bytes.push(b'\r'),
+            Some('n') => bytes.push(b'\n'),
+            Some('t') => bytes.push(b'\t'),
+            Some('0') => bytes.push(0),
+            Some('\\') => bytes.push(b'\\'),
+            Some('x') => {
+                let digits: String = chars.by_ref().take(2).collect();
+                match u8::from_str_radix(&digits, 16) {
+                    Ok(byte) if digits.len() == 2 && digits.chars().all(|c| c.is_ascii_hexdigit()) => bytes.push(byte),
+                    _ => return Err(format!("Invalid escape sequence: \\x{}", digits)),
+                }
+            }
+            Some('u') => {
+                let rest = chars.as_str();
+                let code = rest
+                    .strip_prefix('{')
+                    .and_then(|rest| rest.split_once('}'))
+                    .map(|(code, _)| code)
+                    .ok_or_else(|| "Invalid escape sequence: \\u (expected \\u{...})".to_string())?;
+                let c = u32::from_str_radix(code, 16)
+                    .ok()
+                    .filter(|_| (1..=6).contains(&code.len()) && code.chars().all(|c| c.is_ascii_hexdigit()))
+                    .and_then(char::from_u32)
+                    .ok_or_else(|| format!("Invalid escape sequence: \\u{{{}}}", code))?;
+                let mut buffer = [0; 4];
+                bytes.extend_from_slice(c.encode_utf8(&mut buffer).as_bytes());
+                // 跳过 "{...}"
+                chars = rest[code.len() + 2..].chars();
+            }
+            Some(other) => {
+                let mut buffer = [0; 4];
+                bytes.push(b'\\');
+                bytes.extend_from_slice(other.encode_utf8(&mut buffer).as_bytes());
+            }
+            None => bytes.push(b'\\'),
         }
     }
-    
     Ok(bytes)
 }
 
@@ -40,6 +114,27 @@ pub fn bytes_to_string(bytes: &[u8]) -> String {
     String::from_utf8_lossy(bytes).to_string()
 }
 
+/// 将字节数据转换为可显示的文本，控制字符和非法 UTF-8 字节转义为 `\r`、`\0`、`\xHH` 等形式 (保留换行和制表符)
+pub fn bytes_to_display_string(bytes: &[u8]) -> String {
+    let mut text = String::with_capacity(bytes.len());
+    for chunk in bytes.utf8_chunks() {
+        for c in chunk.valid().chars() {
+            match c {
+                '\n' | '\t' => text.push(c),
+                '\r' => text.push_str("\\r"),
+                '\0' => text.push_str("\\0"),
+                c if c.is_ascii_control() => text.push_str(&format!("\\x{:02X}", c as u32)),
+                c if c.is_control() => text.push_str(&c.escape_unicode().to_string()),
+                c => text.push(c),
+            }
+        }
+        for byte in chunk.invalid() {
+            text.push_str(&format!("\\x{:02X}", byte));
+        }
+    }
+    text
+}
+
 /// 格式化JSON字符串
 pub fn format_json(json_str: &str) -> String {
     match serde_json::from_str::<serde_json::Value>(json_str) {
@@ -74,4 +169,43 @@ mod tests {
         assert!(hex_to_bytes("0102ABFG").is_err()); // 非法字符 'G'
         assert!(hex_to_bytes("0102ABF").is_err());  // 奇数长度
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_looks_like_hex() {
+        assert!(looks_like_hex("01 02 AB FF"));
+        assert!(looks_like_hex("deadbeef\n0102\n"));
+        assert!(!looks_like_hex("ab"));
+        assert!(!looks_like_hex("abc"));
+        assert!(!looks_like_hex("hello world"));
+    }
+
+    #[test]
+    fn test_unescape() {
+        assert_eq!(unescape("AT\\r\\n").unwrap(), b"AT\r\n");
+        assert_eq!(unescape("a\\0b\\tc\\\\").unwrap(), b"a\0b\tc\\");
+        assert_eq!(unescape("\\x01\\xFFz").unwrap(), vec![0x01, 0xFF, b'z']);
+        assert_eq!(unescape("\\u{4e2d}!").unwrap(), "中!".as_bytes());
+        assert!(unescape("\\x1").is_err());
+        assert!(unescape("\\x+1").is_err());
+        assert!(unescape("\\u{110000}").is_err());
+        assert!(unescape("\\u4e2d").is_err());
+        // 未知的转义序列按原样保留
+        assert_eq!(unescape("C:\\path\\file").unwrap(), b"C:\\path\\file");
+        assert_eq!(unescape("\\d+\\").unwrap(), b"\\d+\\");
+    }
+
+    #[test]
+    fn test_invalid_hex_chars() {
+        assert_eq!(invalid_hex_chars("01 0g\tz中"), vec![(4, 'g'), (6, 'z'), (7, '中')]);
+        assert_eq!(hex_to_bytes("0g z中"), Err("Invalid hex characters: gz中".to_string()));
+        assert_eq!(hex_to_bytes("01\n02\tab"), Ok(vec![0x01, 0x02, 0xAB]));
+    }
+
+    #[test]
+    fn test_bytes_to_display_string() {
+        assert_eq!(bytes_to_display_string(b"hello\r\n"), "hello\\r\n");
+        assert_eq!(bytes_to_display_string(b"a\0b\tc"), "a\\0b\tc");
+        assert_eq!(bytes_to_display_string(&[0x41, 0xFF, 0x1B]), "A\\xFF\\x1B");
+        assert_eq!(bytes_to_display_string("中文".as_bytes()), "中文");
+    }
+}
@@ -1 +1,2 @@
-pub mod data_format;
\ No newline at end of file
+pub mod data_format;
+pub mod repeater;
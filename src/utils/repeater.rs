@@ -0,0 +1,129 @@
+use std::time::{Duration, Instant};
+
+use crate::protocols::MessageType;
+
+/// 最短发送间隔：重复发送由 UI 的 100 ms 定时器驱动，更短的间隔无法达到
+pub const MIN_REPEAT_INTERVAL: Duration = Duration::from_millis(100);
+
+/// 重复发送设置
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RepeatSpec {
+    /// 发送间隔
+    pub interval: Duration,
+    /// 发送次数 (None 表示不限次数)
+    pub count: Option<u64>,
+}
+
+impl RepeatSpec {
+    /// 设置描述，如 "every 1000 ms, 100 times"
+    pub fn describe(&self) -> String {
+        match self.count {
+            Some(count) => format!("every {} ms, {} times", self.interval.as_millis(), count),
+            None => format!("every {} ms, until stopped", self.interval.as_millis()),
+        }
+    }
+}
+
+/// 周期性重复发送的任务
+pub struct Repeater {
+    /// 任务编号
+    pub id: usize,
+    /// 发送内容
+    pub content: MessageType,
+    /// 发送目标 (None 表示当前连接或所有客户端)
+    pub target: Option<String>,
+    /// 重复发送设置
+    pub spec: RepeatSpec,
+    /// 已发送次数
+    pub sent: u64,
+    /// 是否暂停
+    pub paused: bool,
+    /// 下一次发送时间
+    next_due: Instant,
+}
+
+impl Repeater {
+    /// 创建重复发送任务，第一次发送立即到期
+    pub fn new(id: usize, content: MessageType, target: Option<String>, spec: RepeatSpec, now: Instant) -> Self {
+        Self {
+            id,
+            content,
+            target,
+            spec,
+            sent: 0,
+            paused: false,
+            next_due: now,
+        }
+    }
+
+    /// 检查到 `now` 为止是否应发送，到期时推进下一次发送时间
+    /// 错过多个周期时 (如主循环被阻塞) 只补发一次，避免突发大量数据
+    pub fn poll_due(&mut self, now: Instant) -> bool {
+        if self.paused || self.is_finished() || now < self.next_due {
+            return false;
+        }
+        self.next_due += self.spec.interval;
+        if self.next_due <= now {
+            self.next_due = now + self.spec.interval;
+        }
+        true
+    }
+
+    /// 记录一次成功发送
+    pub fn record_sent(&mut self) {
+        self.sent += 1;
+    }
+
+    /// 暂停或恢复，恢复时立即发送下一次
+    pub fn toggle_pause(&mut self, now: Instant) {
+        self.paused = !self.paused;
+        if !self.paused {
+            self.next_due = now;
+        }
+    }
+
+    /// 是否已达到发送次数
+    pub fn is_finished(&self) -> bool {
+        self.spec.count.is_some_and(|count| self.sent >= count)
+    }
+
+    /// 发送计数描述，如 "12/100" 或 "12"
+    pub fn progress(&self) -> String {
+        match self.spec.count {
+            Some(count) => format!("{}/{}", self.sent, count),
+            None => self.sent.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_repeater_schedule() {
+        let start = Instant::now();
+        let spec = RepeatSpec {
+            interval: Duration::from_millis(100),
+            count: Some(2),
+        };
+        let mut repeater = Repeater::new(1, MessageType::Text("ping".to_string()), None, spec, start);
+
+        // 第一次立即发送，之后按间隔发送
+        assert!(repeater.poll_due(start));
+        repeater.record_sent();
+        assert!(!repeater.poll_due(start + Duration::from_millis(50)));
+
+        // 暂停期间不发送，恢复后立即发送
+        repeater.toggle_pause(start);
+        assert!(!repeater.poll_due(start + Duration::from_millis(500)));
+        repeater.toggle_pause(start + Duration::from_millis(500));
+        assert!(repeater.poll_due(start + Duration::from_millis(500)));
+        repeater.record_sent();
+
+        // 达到次数后结束
+        assert!(repeater.is_finished());
+        assert!(!repeater.poll_due(start + Duration::from_secs(10)));
+        assert_eq!(repeater.progress(), "2/2");
+    }
+}